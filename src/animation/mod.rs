@@ -0,0 +1,162 @@
+//! Animation de la résolution étape par étape, exportée en GIF ou en APNG
+//! Étapes du solveur → une image annotée par étape (ImageGenerator) → fichier animé
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, DynamicImage, Frame};
+use crate::image_generator::ImageGenerator;
+use crate::solver::SolveStep;
+
+/// Configuration de l'animation
+#[derive(Debug, Clone)]
+pub struct AnimationConfig {
+    /// Durée d'affichage de chaque étape en millisecondes
+    pub frame_delay_ms: u16,
+    /// Durée d'affichage de la grille finale avant de recommencer
+    pub final_delay_ms: u16,
+}
+
+impl Default for AnimationConfig {
+    fn default() -> Self {
+        Self {
+            frame_delay_ms: 500,
+            final_delay_ms: 2000,
+        }
+    }
+}
+
+/// Format du fichier animé, déduit de son extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationFormat {
+    Gif,
+    Apng,
+}
+
+impl AnimationFormat {
+    pub fn from_path(path: &str) -> Result<Self, String> {
+        let extension = Path::new(path).extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("gif") => Ok(AnimationFormat::Gif),
+            Some("png") | Some("apng") => Ok(AnimationFormat::Apng),
+            _ => Err(format!("Format d'animation non pris en charge: {} (extensions .gif, .png ou .apng)", path)),
+        }
+    }
+}
+
+/// Rejoue une résolution image par image
+pub struct SolveAnimation {
+    config: AnimationConfig,
+}
+
+impl SolveAnimation {
+    pub fn new() -> Self {
+        Self::with_config(AnimationConfig::default())
+    }
+
+    pub fn with_config(config: AnimationConfig) -> Self {
+        Self { config }
+    }
+
+    /// Image de départ puis une image par étape, chacune avec les déductions cumulées
+    pub fn frames(&self, image: &DynamicImage, generator: &ImageGenerator, steps: &[SolveStep]) -> Result<Vec<DynamicImage>, String> {
+        (0..=steps.len())
+            .map(|count| generator.generate_output_image(image, &SolveStep::deductions_until(steps, count)))
+            .collect()
+    }
+
+    /// Enregistre les images dans un GIF ou un APNG qui boucle indéfiniment
+    pub fn export(&self, frames: &[DynamicImage], path: &str) -> Result<(), String> {
+        if frames.is_empty() {
+            return Err("Aucune image à animer".to_string());
+        }
+        // Format vérifié avant de créer le fichier: une extension inconnue ne laisse pas de fichier vide
+        let format = AnimationFormat::from_path(path)?;
+        let file = File::create(path)
+            .map_err(|e| format!("Erreur lors de la création du fichier: {}", e))?;
+
+        match format {
+            AnimationFormat::Gif => self.export_gif(frames, file),
+            AnimationFormat::Apng => self.export_apng(frames, file),
+        }
+    }
+
+    /// Durée d'affichage d'une image (la dernière reste affichée plus longtemps)
+    fn delay_ms(&self, index: usize, count: usize) -> u16 {
+        if index + 1 == count {
+            self.config.final_delay_ms
+        } else {
+            self.config.frame_delay_ms
+        }
+    }
+
+    fn export_gif(&self, frames: &[DynamicImage], file: File) -> Result<(), String> {
+        let mut encoder = GifEncoder::new_with_speed(BufWriter::new(file), 10);
+        encoder.set_repeat(Repeat::Infinite)
+            .map_err(|e| format!("Erreur d'encodage GIF: {}", e))?;
+
+        let animation = frames.iter().enumerate().map(|(index, image)| {
+            let delay = Delay::from_numer_denom_ms(self.delay_ms(index, frames.len()) as u32, 1);
+            Frame::from_parts(image.to_rgba8(), 0, 0, delay)
+        });
+        encoder.encode_frames(animation)
+            .map_err(|e| format!("Erreur d'encodage GIF: {}", e))
+    }
+
+    fn export_apng(&self, frames: &[DynamicImage], file: File) -> Result<(), String> {
+        let encoding_error = |e: png::EncodingError| format!("Erreur d'encodage APNG: {}", e);
+        let (width, height) = (frames[0].width(), frames[0].height());
+
+        let mut encoder = png::Encoder::new(BufWriter::new(file), width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_animated(frames.len() as u32, 0).map_err(encoding_error)?;
+
+        let mut writer = encoder.write_header().map_err(encoding_error)?;
+        for (index, image) in frames.iter().enumerate() {
+            if (image.width(), image.height()) != (width, height) {
+                return Err(format!("L'image {} n'a pas la taille de la première", index + 1));
+            }
+            writer.set_frame_delay(self.delay_ms(index, frames.len()), 1000).map_err(encoding_error)?;
+            writer.write_image_data(image.to_rgba8().as_raw()).map_err(encoding_error)?;
+        }
+        writer.finish().map_err(encoding_error)
+    }
+}
+
+impl Default for SolveAnimation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn test_export_gif_and_apng() {
+        let frames: Vec<DynamicImage> = [0u8, 128, 255].iter()
+            .map(|&value| DynamicImage::ImageRgba8(RgbaImage::from_pixel(8, 8, Rgba([value, 0, 0, 255]))))
+            .collect();
+        let animation = SolveAnimation::new();
+        let directory = std::env::temp_dir();
+
+        for name in ["nonogram_animation_test.gif", "nonogram_animation_test.png"] {
+            let path = directory.join(name).display().to_string();
+            animation.export(&frames, &path).unwrap();
+            let decoded = image::open(&path).unwrap();
+            assert_eq!((decoded.width(), decoded.height()), (8, 8));
+            let _ = std::fs::remove_file(&path);
+        }
+
+        let invalid = directory.join("nonogram_animation_test.bmp");
+        assert!(animation.export(&frames, &invalid.display().to_string()).is_err());
+        assert!(!invalid.exists());
+        assert_eq!(AnimationFormat::from_path("tuto.APNG"), Ok(AnimationFormat::Apng));
+    }
+}
@@ -0,0 +1,247 @@
+use crate::grid::{Grid, CellState, Constraints};
+use crate::solver::{
+    BacktrackingSolver, BacktrackingConfig,
+    OptimizedBacktrackingSolver, OptimizedBacktrackingConfig,
+    BranchingStrategy,
+};
+use std::time::{Duration, Instant};
+
+/// Configuration du banc d'essai
+#[derive(Debug, Clone)]
+pub struct BenchmarkConfig {
+    /// Nombre de grilles aléatoires générées en plus des fichiers fournis
+    pub random_puzzles: usize,
+    /// Taille (largeur = hauteur) des grilles aléatoires
+    pub size: usize,
+    /// Proportion de cases noires des grilles aléatoires
+    pub density: f64,
+    pub seed: u64,
+    /// Stratégies comparées
+    pub strategies: Vec<BranchingStrategy>,
+    /// Limite d'états explorés par résolution
+    pub max_states: usize,
+    pub verbose: bool,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            random_puzzles: 5,
+            size: 10,
+            density: 0.5,
+            seed: 42,
+            strategies: BranchingStrategy::ALL.to_vec(),
+            max_states: 2000,
+            verbose: false,
+        }
+    }
+}
+
+/// Résultat d'une résolution du banc d'essai
+#[derive(Debug, Clone)]
+pub struct BenchmarkResult {
+    pub puzzle: String,
+    pub solver: &'static str,
+    pub strategy: BranchingStrategy,
+    /// Grille complète et conforme aux contraintes
+    pub solved: bool,
+    pub states_explored: usize,
+    pub duration: Duration,
+}
+
+/// Générateur pseudo-aléatoire xorshift (reproductible à partir d'une graine)
+pub struct SimpleRng {
+    state: u64,
+}
+
+impl SimpleRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed.max(1) }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// Nombre flottant dans [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Banc d'essai comparant les stratégies de branchement des solveurs par backtracking
+pub struct BranchingBenchmark {
+    config: BenchmarkConfig,
+}
+
+impl BranchingBenchmark {
+    pub fn new() -> Self {
+        Self::with_config(BenchmarkConfig::default())
+    }
+
+    pub fn with_config(config: BenchmarkConfig) -> Self {
+        Self { config }
+    }
+
+    /// Génère les grilles aléatoires configurées (contraintes déduites d'une solution tirée au hasard)
+    pub fn random_puzzles(&self) -> Vec<(String, Constraints)> {
+        let mut rng = SimpleRng::new(self.config.seed);
+        let size = self.config.size;
+
+        (0..self.config.random_puzzles)
+            .map(|index| {
+                let mut solution = Grid::new(size, size);
+                for row in 0..size {
+                    for col in 0..size {
+                        let state = if rng.next_f64() < self.config.density {
+                            CellState::Filled
+                        } else {
+                            CellState::Crossed
+                        };
+                        let _ = solution.set(row, col, state);
+                    }
+                }
+                (format!("aléatoire-{}", index + 1), Constraints::from_grid(&solution))
+            })
+            .collect()
+    }
+
+    /// Résout chaque grille avec chaque solveur et chaque stratégie
+    pub fn run(&self, puzzles: &[(String, Constraints)]) -> Vec<BenchmarkResult> {
+        let mut results = Vec::new();
+
+        for (name, constraints) in puzzles {
+            for &strategy in &self.config.strategies {
+                if self.config.verbose {
+                    println!("⏱️  {} - stratégie {}", name, strategy.name());
+                }
+
+                let config = BacktrackingConfig {
+                    max_depth: constraints.width * constraints.height,
+                    max_states: self.config.max_states,
+                    branching: strategy,
+                    ..Default::default()
+                };
+                let mut solver = BacktrackingSolver::with_config(config);
+                let mut grid = Grid::new(constraints.width, constraints.height);
+                let start = Instant::now();
+                let outcome = solver.solve(&mut grid, constraints);
+                results.push(BenchmarkResult {
+                    puzzle: name.clone(),
+                    solver: "backtracking",
+                    strategy,
+                    solved: outcome.is_ok() && constraints.is_satisfied_by(&grid),
+                    states_explored: solver.states_explored(),
+                    duration: start.elapsed(),
+                });
+
+                let config = OptimizedBacktrackingConfig {
+                    max_depth: constraints.width * constraints.height,
+                    max_states: self.config.max_states,
+                    branching: strategy,
+                    ..Default::default()
+                };
+                let mut solver = OptimizedBacktrackingSolver::with_config(config);
+                let mut grid = Grid::new(constraints.width, constraints.height);
+                let start = Instant::now();
+                let outcome = solver.solve(&mut grid, constraints);
+                results.push(BenchmarkResult {
+                    puzzle: name.clone(),
+                    solver: "optimisé",
+                    strategy,
+                    solved: outcome.is_ok() && constraints.is_satisfied_by(&grid),
+                    states_explored: solver.states_explored(),
+                    duration: start.elapsed(),
+                });
+            }
+        }
+
+        results
+    }
+
+    /// Affiche le détail des résultats puis un résumé par solveur et stratégie
+    pub fn print_report(&self, results: &[BenchmarkResult]) {
+        println!("\n📊 Résultats détaillés");
+        println!("{:<20} {:<14} {:<18} {:>7} {:>8} {:>10}", "Grille", "Solveur", "Stratégie", "Résolu", "États", "Temps (ms)");
+        for result in results {
+            println!(
+                "{:<20} {:<14} {:<18} {:>7} {:>8} {:>10.1}",
+                result.puzzle,
+                result.solver,
+                result.strategy.name(),
+                if result.solved { "✓" } else { "✗" },
+                result.states_explored,
+                result.duration.as_secs_f64() * 1000.0
+            );
+        }
+
+        println!("\n📈 Résumé");
+        println!("{:<14} {:<18} {:>9} {:>12} {:>14}", "Solveur", "Stratégie", "Résolues", "États moy.", "Temps tot. (ms)");
+        for solver in ["backtracking", "optimisé"] {
+            for &strategy in &self.config.strategies {
+                let runs: Vec<&BenchmarkResult> = results.iter()
+                    .filter(|r| r.solver == solver && r.strategy == strategy)
+                    .collect();
+                if runs.is_empty() {
+                    continue;
+                }
+                let solved = runs.iter().filter(|r| r.solved).count();
+                let states: usize = runs.iter().map(|r| r.states_explored).sum();
+                let total: Duration = runs.iter().map(|r| r.duration).sum();
+                println!(
+                    "{:<14} {:<18} {:>6}/{:<2} {:>12.1} {:>14.1}",
+                    solver,
+                    strategy.name(),
+                    solved,
+                    runs.len(),
+                    states as f64 / runs.len() as f64,
+                    total.as_secs_f64() * 1000.0
+                );
+            }
+        }
+    }
+}
+
+impl Default for BranchingBenchmark {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_puzzles_are_reproducible() {
+        let config = BenchmarkConfig {
+            random_puzzles: 2,
+            size: 6,
+            ..Default::default()
+        };
+        let first = BranchingBenchmark::with_config(config.clone()).random_puzzles();
+        let second = BranchingBenchmark::with_config(config).random_puzzles();
+
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].1.rows, second[0].1.rows);
+        assert_eq!(first[1].1.columns, second[1].1.columns);
+    }
+
+    #[test]
+    fn test_run_reports_every_strategy() {
+        let rows = vec![vec![1], vec![3], vec![1]];
+        let columns = vec![vec![1], vec![3], vec![1]];
+        let constraints = Constraints::new(3, 3, rows, columns).unwrap();
+
+        let benchmark = BranchingBenchmark::new();
+        let results = benchmark.run(&[("croix".to_string(), constraints)]);
+
+        assert_eq!(results.len(), BranchingStrategy::ALL.len() * 2);
+        assert!(results.iter().all(|r| r.solved));
+    }
+}
@@ -0,0 +1,190 @@
+use crate::grid::{Grid, CellState, Constraints};
+use crate::solver::{NonogramSolver, BranchingHeuristic, BranchingStrategy};
+use crate::solver::branching::Branch;
+use super::WrongCell;
+
+/// Erreurs probables d'une grille en cours de résolution
+#[derive(Debug, Clone)]
+pub struct MistakeDiagnosis {
+    /// Cases marquées à effacer pour retrouver une grille cohérente
+    pub mistakes: Vec<WrongCell>,
+    /// Vrai si la recherche a prouvé qu'aucun ensemble plus petit ne suffit
+    pub minimal: bool,
+}
+
+impl MistakeDiagnosis {
+    /// Positions des cases suspectes
+    pub fn positions(&self) -> Vec<(usize, usize)> {
+        self.mistakes.iter().map(|cell| (cell.row, cell.col)).collect()
+    }
+
+    /// Copie de la grille où les cases suspectes sont effacées
+    pub fn cleaned_grid(&self, grid: &Grid) -> Result<Grid, String> {
+        let mut cleaned = grid.clone();
+        for cell in &self.mistakes {
+            cleaned.set(cell.row, cell.col, CellState::Empty)?;
+        }
+        Ok(cleaned)
+    }
+}
+
+/// Recherche du plus petit ensemble de marques incompatibles avec les contraintes
+///
+/// Séparation et évaluation sur les solutions: les cases marquées sont essayées
+/// d'abord avec la valeur du joueur, et toute branche qui contredit déjà autant de
+/// marques que la meilleure solution connue est abandonnée.
+pub struct Diagnoser {
+    max_nodes: usize,
+    nodes: usize,
+    truncated: bool,
+    heuristic: BranchingHeuristic,
+    best: Option<(usize, Grid)>,
+}
+
+impl Diagnoser {
+    pub fn new() -> Self {
+        Self::with_max_nodes(20000)
+    }
+
+    pub fn with_max_nodes(max_nodes: usize) -> Self {
+        Self {
+            max_nodes,
+            nodes: 0,
+            truncated: false,
+            heuristic: BranchingHeuristic::new(BranchingStrategy::FewestPlacements),
+            best: None,
+        }
+    }
+
+    /// Diagnostique les marques du joueur (les cases vides ne sont jamais suspectes)
+    pub fn diagnose(&mut self, grid: &Grid, constraints: &Constraints) -> Result<MistakeDiagnosis, String> {
+        if grid.width() != constraints.width || grid.height() != constraints.height {
+            return Err(format!(
+                "Dimensions incompatibles: grille {}x{}, contraintes {}x{}",
+                grid.width(), grid.height(), constraints.width, constraints.height
+            ));
+        }
+
+        self.nodes = 0;
+        self.truncated = false;
+        self.best = None;
+        self.search(Grid::new(grid.width(), grid.height()), grid, constraints)?;
+
+        let (_, solution) = match self.best.take() {
+            Some(best) => best,
+            None if self.truncated => {
+                return Err("Limite de recherche atteinte avant de trouver une solution".to_string());
+            }
+            None => return Err("Les contraintes n'admettent aucune solution".to_string()),
+        };
+
+        Ok(MistakeDiagnosis {
+            mistakes: Self::mismatches(&solution, grid),
+            minimal: !self.truncated,
+        })
+    }
+
+    fn search(&mut self, mut candidate: Grid, marks: &Grid, constraints: &Constraints) -> Result<(), String> {
+        if self.nodes >= self.max_nodes {
+            self.truncated = true;
+            return Ok(());
+        }
+        self.nodes += 1;
+
+        if NonogramSolver::new().solve(&mut candidate, constraints).is_err() {
+            return Ok(());
+        }
+
+        let cost = Self::mismatches(&candidate, marks).len();
+        if self.best.as_ref().is_some_and(|(best, _)| cost >= *best) {
+            return Ok(());
+        }
+
+        if candidate.count_empty_cells() == 0 {
+            if constraints.is_satisfied_by(&candidate) {
+                self.best = Some((cost, candidate));
+            }
+            return Ok(());
+        }
+
+        let branch = match Self::next_marked_cell(&candidate, marks) {
+            Some((row, col, state)) => Branch::cell(row, col, state),
+            None => match self.heuristic.choose(&candidate, constraints) {
+                Some(branch) => branch,
+                None => return Ok(()),
+            },
+        };
+
+        for alternative in branch.alternatives {
+            let mut child = candidate.clone();
+            for (row, col, state) in alternative {
+                child.set(row, col, state)?;
+            }
+            self.search(child, marks, constraints)?;
+        }
+
+        Ok(())
+    }
+
+    /// Première case marquée par le joueur et encore indéterminée
+    fn next_marked_cell(candidate: &Grid, marks: &Grid) -> Option<(usize, usize, CellState)> {
+        (0..candidate.height())
+            .flat_map(|row| (0..candidate.width()).map(move |col| (row, col)))
+            .find_map(|(row, col)| {
+                let mark = marks.get(row, col)?;
+                (candidate.get(row, col)? == CellState::Empty && mark != CellState::Empty)
+                    .then_some((row, col, mark))
+            })
+    }
+
+    /// Cases marquées en désaccord avec les cases déterminées du candidat
+    fn mismatches(candidate: &Grid, marks: &Grid) -> Vec<WrongCell> {
+        let mut mismatches = Vec::new();
+        for row in 0..candidate.height() {
+            for col in 0..candidate.width() {
+                let (Some(expected), Some(actual)) = (candidate.get(row, col), marks.get(row, col)) else {
+                    continue;
+                };
+                if expected != CellState::Empty && actual != CellState::Empty && expected != actual {
+                    mismatches.push(WrongCell { row, col, expected, actual });
+                }
+            }
+        }
+        mismatches
+    }
+}
+
+impl Default for Diagnoser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose_finds_single_wrong_mark() {
+        let constraints = Constraints::new(3, 3, vec![vec![1], vec![3], vec![1]], vec![vec![1], vec![3], vec![1]]).unwrap();
+        let grid = Grid::from_text("#..\n.#.\n...").unwrap();
+
+        let diagnosis = Diagnoser::new().diagnose(&grid, &constraints).unwrap();
+        assert!(diagnosis.minimal);
+        assert_eq!(diagnosis.positions(), vec![(0, 0)]);
+
+        let cleaned = diagnosis.cleaned_grid(&grid).unwrap();
+        assert_eq!(cleaned.get(0, 0), Some(CellState::Empty));
+        assert_eq!(cleaned.get(1, 1), Some(CellState::Filled));
+    }
+
+    #[test]
+    fn test_diagnose_keeps_consistent_marks() {
+        // Deux solutions (diagonales): les marques d'une diagonale ne sont pas des erreurs
+        let constraints = Constraints::new(2, 2, vec![vec![1], vec![1]], vec![vec![1], vec![1]]).unwrap();
+        let grid = Grid::from_text(".#\n.x").unwrap();
+
+        let diagnosis = Diagnoser::new().diagnose(&grid, &constraints).unwrap();
+        assert!(diagnosis.mistakes.is_empty());
+    }
+}
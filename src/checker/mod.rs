@@ -0,0 +1,293 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use crate::grid::{Grid, CellState, Constraints};
+use crate::solver::{OptimizedLineSolver, SolutionCount, SolutionSearch};
+use serde::Serialize;
+
+mod diagnosis;
+
+pub use diagnosis::Diagnoser;
+
+/// Configuration du vérificateur de grilles
+#[derive(Debug, Clone)]
+pub struct CheckerConfig {
+    /// Considérer les cases non marquées comme blanches (soumission terminée)
+    pub treat_empty_as_crossed: bool,
+    /// Limite de nœuds pour la recherche de la solution
+    pub max_search_nodes: usize,
+}
+
+impl Default for CheckerConfig {
+    fn default() -> Self {
+        Self {
+            treat_empty_as_crossed: false,
+            max_search_nodes: 20000,
+        }
+    }
+}
+
+/// Ligne ou colonne
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineKind {
+    Row,
+    Column,
+}
+
+impl LineKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LineKind::Row => "Ligne",
+            LineKind::Column => "Colonne",
+        }
+    }
+}
+
+/// Verdict pour une ligne ou une colonne
+#[derive(Debug, Clone, Serialize)]
+pub struct LineCheck {
+    pub kind: LineKind,
+    pub index: usize,
+    /// Indice attendu
+    pub clue: Vec<usize>,
+    /// Blocs noirs présents dans la grille
+    pub actual: Vec<usize>,
+    /// Les blocs présents correspondent exactement à l'indice
+    pub matches: bool,
+    /// La ligne peut encore être complétée pour respecter l'indice
+    pub consistent: bool,
+}
+
+/// Case marquée différemment de la solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct WrongCell {
+    pub row: usize,
+    pub col: usize,
+    pub expected: CellState,
+    pub actual: CellState,
+}
+
+/// Rapport de vérification d'une grille
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport {
+    pub rows: Vec<LineCheck>,
+    pub columns: Vec<LineCheck>,
+    /// Toutes les lignes et colonnes correspondent à leur indice
+    pub solved: bool,
+    /// Une solution prolongeant les cases marquées existe (None si la recherche n'a pas abouti)
+    pub consistent: Option<bool>,
+    /// La grille admet une solution unique (None si la recherche n'a pas abouti)
+    pub unique_solution: Option<bool>,
+    /// Première case (ordre de lecture) en désaccord avec la solution unique
+    pub first_wrong_cell: Option<WrongCell>,
+}
+
+impl CheckReport {
+    /// Lignes et colonnes qui ne peuvent plus respecter leur indice
+    pub fn violated_lines(&self) -> Vec<&LineCheck> {
+        self.rows.iter()
+            .chain(self.columns.iter())
+            .filter(|line| !line.consistent)
+            .collect()
+    }
+
+    /// Affiche le rapport
+    pub fn print(&self) {
+        if self.solved {
+            println!("✅ Grille résolue: toutes les lignes et colonnes respectent leurs indices");
+        } else {
+            let matching = self.rows.iter().chain(self.columns.iter()).filter(|line| line.matches).count();
+            println!("📋 {}/{} lignes et colonnes conformes", matching, self.rows.len() + self.columns.len());
+        }
+
+        for line in self.violated_lines() {
+            println!("   ❌ {} {}: attendu {:?}, trouvé {:?}", line.kind.label(), line.index + 1, line.clue, line.actual);
+        }
+
+        match self.consistent {
+            Some(true) => println!("✓ Les cases marquées restent compatibles avec une solution"),
+            Some(false) => println!("⚠️  Les cases marquées ne mènent à aucune solution"),
+            None => println!("❔ Cohérence indéterminée (limite de recherche atteinte)"),
+        }
+
+        match self.unique_solution {
+            Some(true) => println!("✓ La grille admet une solution unique"),
+            Some(false) => println!("ℹ️  La grille admet zéro ou plusieurs solutions"),
+            None => println!("❔ Unicité indéterminée (limite de recherche atteinte)"),
+        }
+
+        if let Some(cell) = self.first_wrong_cell {
+            println!(
+                "   ❌ Première erreur: ligne {}, colonne {} (attendu {:?}, trouvé {:?})",
+                cell.row + 1, cell.col + 1, cell.expected, cell.actual
+            );
+        }
+    }
+}
+
+/// Vérifie une grille (complète ou partielle) soumise par un joueur
+pub struct Checker {
+    config: CheckerConfig,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl Checker {
+    pub fn new() -> Self {
+        Self::with_config(CheckerConfig::default())
+    }
+
+    pub fn with_config(config: CheckerConfig) -> Self {
+        Self { config, cancel: None }
+    }
+
+    /// Drapeau d'annulation: une fois levé, les recherches de solution s'arrêtent sans conclure
+    pub fn set_cancel_flag(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
+    }
+
+    /// Vérifie la grille par rapport aux contraintes
+    pub fn check(&self, grid: &Grid, constraints: &Constraints) -> Result<CheckReport, String> {
+        if grid.width() != constraints.width || grid.height() != constraints.height {
+            return Err(format!(
+                "Dimensions incompatibles: grille {}x{}, contraintes {}x{}",
+                grid.width(), grid.height(), constraints.width, constraints.height
+            ));
+        }
+
+        let grid = if self.config.treat_empty_as_crossed {
+            let mut submitted = grid.clone();
+            for row in 0..grid.height() {
+                for col in 0..grid.width() {
+                    if grid.get(row, col) == Some(CellState::Empty) {
+                        submitted.set(row, col, CellState::Crossed)?;
+                    }
+                }
+            }
+            submitted
+        } else {
+            grid.clone()
+        };
+
+        let rows = (0..grid.height())
+            .map(|row| Self::check_line(
+                LineKind::Row,
+                row,
+                &grid.get_row(row).unwrap_or_default(),
+                constraints.get_row_constraint(row).map(Vec::as_slice).unwrap_or_default(),
+            ))
+            .collect::<Vec<_>>();
+        let columns = (0..grid.width())
+            .map(|col| Self::check_line(
+                LineKind::Column,
+                col,
+                &grid.get_column(col).unwrap_or_default(),
+                constraints.get_column_constraint(col).map(Vec::as_slice).unwrap_or_default(),
+            ))
+            .collect::<Vec<_>>();
+        let solved = rows.iter().chain(columns.iter()).all(|line| line.matches);
+
+        let mut search = SolutionSearch::with_max_nodes(self.config.max_search_nodes);
+        if let Some(cancel) = &self.cancel {
+            search.set_cancel_flag(Arc::clone(cancel));
+        }
+        let consistent = if rows.iter().chain(columns.iter()).any(|line| !line.consistent) {
+            Some(false)
+        } else if solved {
+            Some(true)
+        } else {
+            match search.count(&grid, constraints)? {
+                SolutionCount::None => Some(false),
+                SolutionCount::Unknown => None,
+                SolutionCount::Unique(_) | SolutionCount::Multiple(_, _) => Some(true),
+            }
+        };
+
+        let blank = Grid::new(grid.width(), grid.height());
+        let (unique_solution, first_wrong_cell) = match search.count(&blank, constraints)? {
+            SolutionCount::Unique(solution) => (Some(true), Self::first_wrong_cell(&grid, &solution)),
+            SolutionCount::Unknown => (None, None),
+            SolutionCount::None | SolutionCount::Multiple(_, _) => (Some(false), None),
+        };
+
+        Ok(CheckReport {
+            rows,
+            columns,
+            solved,
+            consistent,
+            unique_solution,
+            first_wrong_cell,
+        })
+    }
+
+    fn check_line(kind: LineKind, index: usize, line: &[CellState], clue: &[usize]) -> LineCheck {
+        let clue: Vec<usize> = clue.iter().copied().filter(|&block| block > 0).collect();
+        let actual = Constraints::line_clue(line);
+        let consistent = OptimizedLineSolver::new().fill_probabilities(line, &clue).is_ok();
+
+        LineCheck {
+            kind,
+            index,
+            matches: actual == clue,
+            clue,
+            actual,
+            consistent,
+        }
+    }
+
+    /// Première case marquée en contradiction avec la solution (les cases vides sont ignorées)
+    fn first_wrong_cell(grid: &Grid, solution: &Grid) -> Option<WrongCell> {
+        (0..grid.height())
+            .flat_map(|row| (0..grid.width()).map(move |col| (row, col)))
+            .find_map(|(row, col)| {
+                let actual = grid.get(row, col)?;
+                let expected = solution.get(row, col)?;
+                (actual != CellState::Empty && actual != expected)
+                    .then_some(WrongCell { row, col, expected, actual })
+            })
+    }
+}
+
+impl Default for Checker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cross_constraints() -> Constraints {
+        Constraints::new(3, 3, vec![vec![1], vec![3], vec![1]], vec![vec![1], vec![3], vec![1]]).unwrap()
+    }
+
+    #[test]
+    fn test_check_solved_grid() {
+        let grid = Grid::from_text(".#.\n###\n.#.").unwrap();
+        let checker = Checker::with_config(CheckerConfig { treat_empty_as_crossed: true, ..Default::default() });
+        let report = checker.check(&grid, &cross_constraints()).unwrap();
+
+        assert!(report.solved);
+        assert_eq!(report.consistent, Some(true));
+        assert_eq!(report.unique_solution, Some(true));
+        assert!(report.first_wrong_cell.is_none());
+        assert!(report.violated_lines().is_empty());
+    }
+
+    #[test]
+    fn test_check_reports_mistakes_in_partial_grid() {
+        let grid = Grid::from_text("#..\n...\n...").unwrap();
+        let report = Checker::new().check(&grid, &cross_constraints()).unwrap();
+
+        assert!(!report.solved);
+        assert_eq!(report.consistent, Some(false));
+        assert_eq!(report.first_wrong_cell, Some(WrongCell {
+            row: 0,
+            col: 0,
+            expected: CellState::Crossed,
+            actual: CellState::Filled,
+        }));
+        // Une seule case noire en (0, 0) reste compatible ligne par ligne
+        assert!(report.violated_lines().is_empty());
+    }
+}
@@ -1,7 +1,7 @@
-use image::{Rgb, RgbImage};
+//! Module de dessin pur Rust (remplace imageproc)
+//! Implémente les algorithmes de dessin nécessaires sans dépendances externes
 
-/// Module de dessin pur Rust (remplace imageproc)
-/// Implémente les algorithmes de dessin nécessaires sans dépendances externes
+use image::{Rgb, RgbImage};
 
 /// Dessine un cercle rempli sur une image
 /// Utilise l'algorithme de Bresenham pour les cercles
@@ -25,6 +25,28 @@ pub fn draw_filled_circle_mut(image: &mut RgbImage, center: (i32, i32), radius:
     }
 }
 
+/// Dessine un anneau (cercle non rempli) d'épaisseur donnée sur une image
+pub fn draw_ring_mut(image: &mut RgbImage, center: (i32, i32), radius: i32, thickness: i32, color: Rgb<u8>) {
+    let (cx, cy) = center;
+    let outer = radius * radius;
+    let inner = (radius - thickness).max(0).pow(2);
+
+    for y in (cy - radius)..=(cy + radius) {
+        if y < 0 || y >= image.height() as i32 {
+            continue;
+        }
+        for x in (cx - radius)..=(cx + radius) {
+            if x < 0 || x >= image.width() as i32 {
+                continue;
+            }
+            let distance = (x - cx).pow(2) + (y - cy).pow(2);
+            if distance <= outer && distance > inner {
+                image.put_pixel(x as u32, y as u32, color);
+            }
+        }
+    }
+}
+
 /// Dessine une croix sur une image
 /// Dessine deux lignes diagonales formant une croix
 pub fn draw_cross_mut(image: &mut RgbImage, center: (i32, i32), size: u32, color: Rgb<u8>) {
@@ -105,6 +127,18 @@ mod tests {
         assert_eq!(img.get_pixel(70, 50), &white);
     }
     
+    #[test]
+    fn test_draw_ring() {
+        let mut img = RgbImage::new(100, 100);
+        let white = Rgb([255, 255, 255]);
+
+        draw_ring_mut(&mut img, (50, 50), 20, 3, white);
+
+        // Le bord est dessiné, le centre reste vide
+        assert_eq!(img.get_pixel(69, 50), &white);
+        assert_eq!(img.get_pixel(50, 50), &Rgb([0, 0, 0]));
+    }
+
     #[test]
     fn test_draw_cross() {
         let mut img = RgbImage::new(100, 100);
@@ -1,13 +1,11 @@
-use image::{GrayImage, Luma, ImageBuffer};
+//! Module de détection de contours pur Rust (remplace imageproc::edges::canny)
+//! Implémente l'algorithme de détection de contours de Canny
 
-/// Module de détection de contours pur Rust (remplace imageproc::edges::canny)
-/// Implémente l'algorithme de détection de contours de Canny
+use image::{GrayImage, Luma, ImageBuffer};
 
 /// Détection de contours Canny simplifiée
 /// Retourne une image binaire avec les contours détectés
 pub fn canny(image: &GrayImage, low_threshold: f32, high_threshold: f32) -> GrayImage {
-    let (width, height) = image.dimensions();
-    
     // Étape 1: Flou gaussien (simplifié avec moyenne 3x3)
     let blurred = gaussian_blur(image);
     
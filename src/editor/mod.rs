@@ -0,0 +1,356 @@
+//! Éditeur de nonogrammes: une solution dessinée case par case devient un puzzle
+//! Solution → indices → unicité et difficulté → export JSON, XML webpbn ou image imprimable
+
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use image::{DynamicImage, Rgb, RgbImage};
+use crate::grid::{CellState, Constraints, Grid};
+use crate::ocr::digit_recognizer::digit_bitmap;
+use crate::solver::{DifficultyRater, Rating, SolutionCount, SolutionSearch};
+
+/// Taille maximale d'une grille éditée
+pub const MAX_SIZE: usize = 60;
+
+/// Résultat de la vérification d'unicité
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Uniqueness {
+    /// La solution dessinée est la seule
+    Unique,
+    /// Une autre solution existe; `cells` liste les cases où elle diffère du dessin
+    Ambiguous { cells: Vec<(usize, usize)> },
+    /// Limite de recherche atteinte avant de conclure
+    Unknown,
+}
+
+/// Nonogramme en cours de conception
+#[derive(Debug, Clone)]
+pub struct PuzzleDesign {
+    pub title: String,
+    solution: Grid,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl PuzzleDesign {
+    /// Grille blanche de la taille choisie
+    pub fn new(width: usize, height: usize) -> Result<Self, String> {
+        if !(1..=MAX_SIZE).contains(&width) || !(1..=MAX_SIZE).contains(&height) {
+            return Err(format!("Taille invalide: {}x{} (de 1 à {} cases)", width, height, MAX_SIZE));
+        }
+        Ok(Self {
+            title: String::new(),
+            solution: Grid::new(width, height),
+            cancel: None,
+        })
+    }
+
+    /// Drapeau d'annulation de la vérification d'unicité et de l'évaluation
+    pub fn set_cancel_flag(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
+    }
+
+    /// Solution dessinée (cases noires, les autres sont vides)
+    pub fn solution(&self) -> &Grid {
+        &self.solution
+    }
+
+    /// Noircit ou efface une case; renvoie vrai si elle a changé
+    pub fn paint(&mut self, row: usize, col: usize, filled: bool) -> bool {
+        let state = if filled { CellState::Filled } else { CellState::Empty };
+        if self.solution.get(row, col).is_none_or(|current| current == state) {
+            return false;
+        }
+        self.solution.set(row, col, state).is_ok()
+    }
+
+    pub fn is_filled(&self, row: usize, col: usize) -> bool {
+        self.solution.get(row, col) == Some(CellState::Filled)
+    }
+
+    /// Change la taille en gardant le dessin du coin haut gauche
+    pub fn resize(&mut self, width: usize, height: usize) -> Result<(), String> {
+        let mut resized = Self::new(width, height)?;
+        for row in 0..height.min(self.solution.height()) {
+            for col in 0..width.min(self.solution.width()) {
+                resized.paint(row, col, self.is_filled(row, col));
+            }
+        }
+        self.solution = resized.solution;
+        Ok(())
+    }
+
+    /// Efface toute la grille
+    pub fn clear(&mut self) {
+        self.solution = Grid::new(self.solution.width(), self.solution.height());
+    }
+
+    /// Indices des lignes et des colonnes du dessin
+    pub fn constraints(&self) -> Constraints {
+        let clues = |lines: Vec<Vec<CellState>>| -> Vec<Vec<usize>> {
+            lines.iter().map(|line| Constraints::line_clue(line)).collect()
+        };
+        let rows = (0..self.solution.height()).filter_map(|row| self.solution.get_row(row)).collect();
+        let columns = (0..self.solution.width()).filter_map(|col| self.solution.get_column(col)).collect();
+        Constraints {
+            width: self.solution.width(),
+            height: self.solution.height(),
+            rows: clues(rows),
+            columns: clues(columns),
+        }
+    }
+
+    /// Vérifie que les indices ne mènent qu'à la solution dessinée
+    pub fn check_uniqueness(&self) -> Result<Uniqueness, String> {
+        let constraints = self.constraints();
+        let blank = Grid::new(constraints.width, constraints.height);
+        let mut search = SolutionSearch::new();
+        if let Some(cancel) = &self.cancel {
+            search.set_cancel_flag(Arc::clone(cancel));
+        }
+        let count = search.count(&blank, &constraints)?;
+        Ok(match count {
+            SolutionCount::Unique(_) => Uniqueness::Unique,
+            SolutionCount::Multiple(first, second) => {
+                let other = if self.differences(&first).is_empty() { second } else { first };
+                Uniqueness::Ambiguous { cells: self.differences(&other) }
+            }
+            SolutionCount::Unknown => Uniqueness::Unknown,
+            SolutionCount::None => return Err("Aucune solution: le dessin ne respecte pas ses propres indices".to_string()),
+        })
+    }
+
+    /// Difficulté estimée d'après les techniques nécessaires
+    pub fn rate(&self) -> Result<Rating, String> {
+        let mut rater = DifficultyRater::new();
+        if let Some(cancel) = &self.cancel {
+            rater.set_cancel_flag(Arc::clone(cancel));
+        }
+        rater.rate(&self.constraints())
+    }
+
+    /// Indices au format JSON lu par `Constraints::from_json_file`
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(&self.constraints())
+            .map_err(|e| format!("Erreur lors de la sérialisation JSON: {}", e))
+    }
+
+    /// Puzzle au format XML de webpbn (indices et solution)
+    pub fn to_webpbn_xml(&self) -> String {
+        let constraints = self.constraints();
+        let lines = |clues: &[Vec<usize>]| -> String {
+            clues.iter()
+                .map(|clue| {
+                    let counts: String = clue.iter().map(|count| format!("<count>{}</count>", count)).collect();
+                    format!("<line>{}</line>\n", counts)
+                })
+                .collect()
+        };
+        let image: String = (0..self.solution.height())
+            .map(|row| {
+                let cells: String = (0..self.solution.width())
+                    .map(|col| if self.is_filled(row, col) { 'X' } else { '.' })
+                    .collect();
+                format!("|{}|\n", cells)
+            })
+            .collect();
+
+        format!(
+            "<?xml version=\"1.0\"?>\n\
+             <!DOCTYPE pbn SYSTEM \"http://webpbn.com/pbn-0.3.dtd\">\n\
+             <puzzleset>\n\
+             <puzzle type=\"grid\" defaultcolor=\"black\">\n\
+             <title>{}</title>\n\
+             <color name=\"white\" char=\".\">fff</color>\n\
+             <color name=\"black\" char=\"X\">000</color>\n\
+             <clues type=\"columns\">\n{}</clues>\n\
+             <clues type=\"rows\">\n{}</clues>\n\
+             <solution type=\"goal\">\n<image>\n{}</image>\n</solution>\n\
+             </puzzle>\n\
+             </puzzleset>\n",
+            escape_xml(&self.title), lines(&constraints.columns), lines(&constraints.rows), image
+        )
+    }
+
+    /// Grille vierge avec ses indices, à imprimer; les chiffres utilisent la police du lecteur intégré
+    pub fn render_printable(&self, cell_size: u32) -> DynamicImage {
+        let constraints = self.constraints();
+        let cell = cell_size.max(12);
+        let scale = (cell / 12).max(1);
+        let digit_height = 7 * scale;
+        let pitch = digit_height + 4 * scale;
+        let clue_texts = |clues: &[Vec<usize>]| -> Vec<Vec<usize>> {
+            clues.iter().map(|clue| if clue.is_empty() { vec![0] } else { clue.clone() }).collect()
+        };
+        let (rows, columns) = (clue_texts(&constraints.rows), clue_texts(&constraints.columns));
+
+        let row_width = |clue: &[usize]| -> u32 {
+            clue.iter().map(|&value| number_width(value, scale)).sum::<u32>() + 4 * scale * (clue.len() as u32 - 1)
+        };
+        let left = rows.iter().map(|clue| row_width(clue)).max().unwrap_or(0) + cell / 2;
+        let top = columns.iter().map(Vec::len).max().unwrap_or(1) as u32 * pitch + cell / 2;
+        let (width, height) = (constraints.width as u32, constraints.height as u32);
+        let (grid_x, grid_y) = (cell + left, cell + top);
+
+        let mut image = RgbImage::from_pixel(grid_x + width * cell + cell, grid_y + height * cell + cell, Rgb([255, 255, 255]));
+        let black = Rgb([0, 0, 0]);
+
+        // Traits de la grille, plus épais toutes les cinq cases
+        for i in 0..=width {
+            let thickness = if i % 5 == 0 || i == width { 2 } else { 1 };
+            for x in grid_x + i * cell..grid_x + i * cell + thickness {
+                for y in grid_y..=grid_y + height * cell {
+                    put(&mut image, x, y, black);
+                }
+            }
+        }
+        for i in 0..=height {
+            let thickness = if i % 5 == 0 || i == height { 2 } else { 1 };
+            for y in grid_y + i * cell..grid_y + i * cell + thickness {
+                for x in grid_x..=grid_x + width * cell {
+                    put(&mut image, x, y, black);
+                }
+            }
+        }
+
+        // Indices des lignes alignés à droite, des colonnes alignés en bas
+        for (row, clue) in rows.iter().enumerate() {
+            let mut x = grid_x - cell / 4 - row_width(clue);
+            let y = grid_y + row as u32 * cell + (cell - digit_height) / 2;
+            for &value in clue {
+                draw_number(&mut image, value, x, y, scale);
+                x += number_width(value, scale) + 4 * scale;
+            }
+        }
+        for (col, clue) in columns.iter().enumerate() {
+            for (line, &value) in clue.iter().enumerate() {
+                let x = grid_x + col as u32 * cell + (cell.saturating_sub(number_width(value, scale))) / 2;
+                let y = grid_y - cell / 4 - digit_height - (clue.len() - 1 - line) as u32 * pitch;
+                draw_number(&mut image, value, x, y, scale);
+            }
+        }
+
+        DynamicImage::ImageRgb8(image)
+    }
+
+    /// Exporte selon l'extension: `.json`, `.xml` (webpbn) ou une image imprimable
+    pub fn export(&self, path: &str) -> Result<(), String> {
+        let extension = Path::new(path).extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase)
+            .unwrap_or_default();
+        let write = |content: String| std::fs::write(path, content)
+            .map_err(|e| format!("Erreur lors de l'écriture du fichier: {}", e));
+
+        match extension.as_str() {
+            "json" => write(self.to_json()?),
+            "xml" | "pbn" => write(self.to_webpbn_xml()),
+            "png" | "jpg" | "jpeg" | "bmp" => self.render_printable(32).save(path)
+                .map_err(|e| format!("Erreur lors de la sauvegarde de l'image: {}", e)),
+            _ => Err(format!("Format d'export non pris en charge: {} (json, xml ou image)", path)),
+        }
+    }
+
+    /// Cases noires dans l'une des grilles et pas dans l'autre
+    fn differences(&self, other: &Grid) -> Vec<(usize, usize)> {
+        (0..self.solution.height())
+            .flat_map(|row| (0..self.solution.width()).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.is_filled(row, col) != (other.get(row, col) == Some(CellState::Filled)))
+            .collect()
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn put(image: &mut RgbImage, x: u32, y: u32, color: Rgb<u8>) {
+    if x < image.width() && y < image.height() {
+        image.put_pixel(x, y, color);
+    }
+}
+
+/// Largeur d'un nombre dessiné: chiffres séparés d'un pixel agrandi
+fn number_width(value: usize, scale: u32) -> u32 {
+    let digits = value.to_string();
+    let glyphs: u32 = digits.bytes()
+        .filter_map(|digit| digit_bitmap(digit - b'0'))
+        .map(|rows| rows[0].len() as u32 * scale)
+        .sum();
+    glyphs + scale * (digits.len() as u32 - 1)
+}
+
+fn draw_number(image: &mut RgbImage, value: usize, x: u32, y: u32, scale: u32) {
+    let mut x = x;
+    for digit in value.to_string().bytes() {
+        let Some(rows) = digit_bitmap(digit - b'0') else {
+            continue;
+        };
+        for (row, line) in rows.iter().enumerate() {
+            for (col, bit) in line.bytes().enumerate() {
+                if bit != b'1' {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        put(image, x + col as u32 * scale + dx, y + row as u32 * scale + dy, Rgb([0, 0, 0]));
+                    }
+                }
+            }
+        }
+        x += rows[0].len() as u32 * scale + scale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::LayoutAnalyzer;
+    use crate::ocr::AdvancedConstraintExtractor;
+
+    fn design(text: &str) -> PuzzleDesign {
+        let grid = Grid::from_text(text).unwrap();
+        let mut design = PuzzleDesign::new(grid.width(), grid.height()).unwrap();
+        for row in 0..grid.height() {
+            for col in 0..grid.width() {
+                design.paint(row, col, grid.get(row, col) == Some(CellState::Filled));
+            }
+        }
+        design
+    }
+
+    #[test]
+    fn test_clues_uniqueness_and_exports() {
+        let mut heart = design(".#.#.\n#####\n#####\n.###.\n..#..");
+        assert_eq!(heart.constraints().rows, vec![vec![1, 1], vec![5], vec![5], vec![3], vec![1]]);
+        assert_eq!(heart.check_uniqueness().unwrap(), Uniqueness::Unique);
+        assert!(heart.rate().is_ok());
+
+        heart.title = "Cœur <5x5>".to_string();
+        let xml = heart.to_webpbn_xml();
+        assert!(xml.contains("<title>Cœur &lt;5x5&gt;</title>"));
+        assert!(xml.contains("<line><count>1</count><count>1</count></line>"));
+        assert!(xml.contains("|.X.X.|"));
+        let json: Constraints = serde_json::from_str(&heart.to_json().unwrap()).unwrap();
+        assert_eq!(json.columns, heart.constraints().columns);
+
+        // Deux diagonales possibles: les quatre cases sont ambiguës
+        let diagonal = design("#.\n.#");
+        assert_eq!(diagonal.check_uniqueness().unwrap(), Uniqueness::Ambiguous { cells: vec![(0, 0), (0, 1), (1, 0), (1, 1)] });
+
+        heart.resize(6, 4).unwrap();
+        assert_eq!(heart.solution().to_text(), ".#.#..\n#####.\n#####.\n.###..");
+        assert!(heart.resize(0, 4).is_err());
+    }
+
+    #[test]
+    fn test_printable_image_is_read_back() {
+        let design = design("##.#######\n#.........\n.##.###.##\n..........\n##########\n#.#.#.#.#.\n..........\n.....#....\n###.......\n.........#");
+        let image = design.render_printable(28);
+
+        let layout = LayoutAnalyzer::new().analyze(&image).unwrap();
+        assert_eq!((layout.width(), layout.height()), (10, 10));
+        let read = AdvancedConstraintExtractor::extract_with_recognizer(&image, &layout).constraints().unwrap();
+        assert_eq!(read.rows, design.constraints().rows);
+        assert_eq!(read.columns, design.constraints().columns);
+    }
+}
@@ -1,4 +1,7 @@
+use std::path::{Path, PathBuf};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use super::{Grid, CellState};
 
 /// Représente les contraintes d'un nonogramme
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +17,9 @@ pub struct Constraints {
 }
 
 impl Constraints {
+    /// Extensions des fichiers de contraintes reconnus (JSON, XML webpbn, format .non)
+    pub const FILE_EXTENSIONS: [&'static str; 4] = ["json", "xml", "pbn", "non"];
+
     /// Crée de nouvelles contraintes
     pub fn new(width: usize, height: usize, rows: Vec<Vec<usize>>, columns: Vec<Vec<usize>>) -> Result<Self, String> {
         if rows.len() != height {
@@ -76,6 +82,89 @@ impl Constraints {
         )
     }
 
+    /// Charge les contraintes d'un fichier JSON, XML webpbn (`.xml`, `.pbn`) ou `.non`, selon son extension
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let extension = Path::new(path).extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase);
+        let read = || std::fs::read_to_string(path)
+            .map_err(|e| format!("Erreur lors de la lecture du fichier: {}", e));
+        match extension.as_deref() {
+            Some("xml") | Some("pbn") => Self::from_webpbn_xml(&read()?),
+            Some("non") => Self::from_non(&read()?),
+            _ => Self::from_json_file(path),
+        }
+    }
+
+    /// Fichier de contraintes portant le nom de l'image (`grille.png` → `grille.json`, `.xml`, `.pbn` ou `.non`)
+    pub fn paired_file(image_path: &Path) -> Option<PathBuf> {
+        Self::FILE_EXTENSIONS.iter()
+            .map(|extension| image_path.with_extension(extension))
+            .find(|path| path.is_file())
+    }
+
+    /// Lit les indices d'un puzzle webpbn (`<clues type="rows">`, une balise `<line>` par ligne)
+    pub fn from_webpbn_xml(xml: &str) -> Result<Self, String> {
+        let clues_block = Regex::new(r#"(?s)<clues\s+type\s*=\s*["'](rows|columns)["'][^>]*>(.*?)</clues>"#)
+            .map_err(|e| e.to_string())?;
+        let line = Regex::new(r"(?s)<line\s*/>|<line[^>]*>(.*?)</line>").map_err(|e| e.to_string())?;
+        let count = Regex::new(r"<count[^>]*>\s*(\d+)\s*</count>").map_err(|e| e.to_string())?;
+
+        let (mut rows, mut columns) = (None, None);
+        for block in clues_block.captures_iter(xml) {
+            let lines: Vec<Vec<usize>> = line.captures_iter(&block[2])
+                .map(|captures| {
+                    captures.get(1)
+                        .map(|content| count.captures_iter(content.as_str()).filter_map(|c| c[1].parse().ok()).collect())
+                        .unwrap_or_default()
+                })
+                .collect();
+            match &block[1] {
+                "rows" => rows = Some(lines),
+                _ => columns = Some(lines),
+            }
+        }
+
+        let rows = rows.ok_or("XML webpbn: indices des lignes absents")?;
+        let columns = columns.ok_or("XML webpbn: indices des colonnes absents")?;
+        Self::new(columns.len(), rows.len(), rows, columns)
+    }
+
+    /// Lit le format `.non`: `width`, `height`, puis `rows` et `columns` suivis d'un indice par ligne
+    /// (nombres séparés par des virgules, `0` ou ligne vide pour une ligne blanche)
+    pub fn from_non(text: &str) -> Result<Self, String> {
+        let (mut width, mut height) = (None, None);
+        let (mut rows, mut columns) = (Vec::new(), Vec::new());
+        let mut lines = text.lines().map(str::trim);
+
+        let parse_clue = |line: &str| -> Result<Vec<usize>, String> {
+            line.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|part| !part.is_empty())
+                .map(|part| part.parse::<usize>().map_err(|_| format!("Format .non: indice invalide « {} »", line)))
+                .filter(|count| count != &Ok(0))
+                .collect()
+        };
+
+        while let Some(line) = lines.next() {
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("width") => width = words.next().and_then(|value| value.parse::<usize>().ok()),
+                Some("height") => height = words.next().and_then(|value| value.parse::<usize>().ok()),
+                Some(section @ ("rows" | "columns")) => {
+                    let count = if section == "rows" { height } else { width }
+                        .ok_or_else(|| format!("Format .non: « {} » doit suivre width et height", section))?;
+                    let clues = lines.by_ref().take(count).map(parse_clue).collect::<Result<Vec<_>, _>>()?;
+                    if section == "rows" { rows = clues } else { columns = clues }
+                }
+                _ => {}
+            }
+        }
+
+        let width = width.ok_or("Format .non: largeur (width) absente")?;
+        let height = height.ok_or("Format .non: hauteur (height) absente")?;
+        Self::new(width, height, rows, columns)
+    }
+
     /// Calcule la longueur minimale nécessaire pour une ligne avec les contraintes données
     fn min_line_length(constraint: &[usize]) -> usize {
         if constraint.is_empty() {
@@ -94,6 +183,50 @@ impl Constraints {
     pub fn get_column_constraint(&self, col: usize) -> Option<&Vec<usize>> {
         self.columns.get(col)
     }
+
+    /// Calcule les contraintes correspondant à une grille (les cases non noircies sont blanches)
+    pub fn from_grid(grid: &Grid) -> Self {
+        let rows = (0..grid.height())
+            .map(|row| Self::line_clue(&grid.get_row(row).unwrap_or_default()))
+            .collect();
+        let columns = (0..grid.width())
+            .map(|col| Self::line_clue(&grid.get_column(col).unwrap_or_default()))
+            .collect();
+
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            rows,
+            columns,
+        }
+    }
+
+    /// Calcule la suite de blocs noirs d'une ligne
+    pub fn line_clue(line: &[CellState]) -> Vec<usize> {
+        let mut clue = Vec::new();
+        let mut run = 0;
+        for &cell in line {
+            if cell == CellState::Filled {
+                run += 1;
+            } else if run > 0 {
+                clue.push(run);
+                run = 0;
+            }
+        }
+        if run > 0 {
+            clue.push(run);
+        }
+        clue
+    }
+
+    /// Vérifie qu'une grille respecte toutes les contraintes
+    pub fn is_satisfied_by(&self, grid: &Grid) -> bool {
+        if grid.width() != self.width || grid.height() != self.height {
+            return false;
+        }
+        let actual = Self::from_grid(grid);
+        actual.rows == self.rows && actual.columns == self.columns
+    }
 }
 
 #[cfg(test)]
@@ -124,4 +257,36 @@ mod tests {
         assert_eq!(Constraints::min_line_length(&[1, 1, 1]), 5); // 1 + 1 + 1 + 1 + 1
         assert_eq!(Constraints::min_line_length(&[]), 0);
     }
+
+    #[test]
+    fn test_from_grid() {
+        let mut grid = Grid::new(3, 2);
+        grid.set(0, 0, CellState::Filled).unwrap();
+        grid.set(0, 2, CellState::Filled).unwrap();
+        grid.set(1, 1, CellState::Filled).unwrap();
+        grid.set(1, 2, CellState::Filled).unwrap();
+
+        let constraints = Constraints::from_grid(&grid);
+        assert_eq!(constraints.rows, vec![vec![1, 1], vec![2]]);
+        assert_eq!(constraints.columns, vec![vec![1], vec![1], vec![2]]);
+        assert!(constraints.is_satisfied_by(&grid));
+    }
+
+    #[test]
+    fn test_webpbn_and_non_formats() {
+        let xml = r#"<puzzleset><puzzle type="grid">
+            <clues type='rows'><line><count>3</count></line><line><count color="black">1</count><count>1</count></line><line/></clues>
+            <clues type="columns"><line><count>2</count></line><line><count>1</count></line><line><count>2</count></line></clues>
+            </puzzle></puzzleset>"#;
+        let constraints = Constraints::from_webpbn_xml(xml).unwrap();
+        assert_eq!((constraints.width, constraints.height), (3, 3));
+        assert_eq!(constraints.rows, vec![vec![3], vec![1, 1], vec![]]);
+        assert_eq!(constraints.columns, vec![vec![2], vec![1], vec![2]]);
+
+        let non = "catalogue \"exemple\"\nwidth 3\nheight 3\n\nrows\n3\n1,1\n0\n\ncolumns\n2\n1\n2\n";
+        let from_non = Constraints::from_non(non).unwrap();
+        assert_eq!(from_non.rows, constraints.rows);
+        assert_eq!(from_non.columns, constraints.columns);
+        assert!(Constraints::from_non("rows\n1\n").is_err());
+    }
 }
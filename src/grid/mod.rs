@@ -1,9 +1,13 @@
 pub mod constraints;
+pub mod move_log;
 
 pub use constraints::Constraints;
+pub use move_log::{MoveLog, MoveSource};
+
+use serde::{Deserialize, Serialize};
 
 /// Représente l'état d'une case dans la grille
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CellState {
     /// Case vide (non résolue)
     Empty,
@@ -132,6 +136,57 @@ impl Grid {
     pub fn clone_grid(&self) -> Self {
         self.clone()
     }
+
+    /// Lit une grille au format texte: une ligne par rangée,
+    /// `#` pour une case noire, `x` (ou `X`) pour une case barrée, `.` pour une case vide
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut cells = Vec::new();
+
+        for (row, line) in text.lines().map(str::trim).filter(|line| !line.is_empty()).enumerate() {
+            let states = line.chars()
+                .filter(|c| !c.is_whitespace())
+                .map(|c| match c {
+                    '#' => Ok(CellState::Filled),
+                    'x' | 'X' => Ok(CellState::Crossed),
+                    '.' => Ok(CellState::Empty),
+                    other => Err(format!("Caractère inattendu '{}' à la ligne {}", other, row + 1)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            cells.push(states);
+        }
+
+        let width = cells.first().map_or(0, Vec::len);
+        if let Some(row) = cells.iter().position(|states| states.len() != width) {
+            return Err(format!("La ligne {} contient {} cases au lieu de {}", row + 1, cells[row].len(), width));
+        }
+
+        Ok(Self {
+            width,
+            height: cells.len(),
+            cells,
+        })
+    }
+
+    /// Charge une grille au format texte depuis un fichier
+    pub fn from_text_file(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Erreur lors de la lecture du fichier: {}", e))?;
+        Self::from_text(&content)
+    }
+
+    /// Écrit la grille au format texte lu par `from_text`
+    pub fn to_text(&self) -> String {
+        self.cells.iter()
+            .map(|row| row.iter()
+                .map(|cell| match cell {
+                    CellState::Filled => '#',
+                    CellState::Crossed => 'x',
+                    CellState::Empty => '.',
+                })
+                .collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 #[cfg(test)]
@@ -170,4 +225,14 @@ mod tests {
         assert_eq!(col[2], CellState::Crossed);
         assert_eq!(col.len(), 5);
     }
+
+    #[test]
+    fn test_text_round_trip() {
+        let grid = Grid::from_text("#.x\nx##\n").unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.get(0, 2), Some(CellState::Crossed));
+        assert_eq!(grid.to_text(), "#.x\nx##");
+        assert!(Grid::from_text("##\n#").is_err());
+    }
 }
@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use super::{CellState, Grid};
+
+/// Origine d'un coup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MoveSource {
+    /// Case modifiée par le joueur
+    Player,
+    /// Indice appliqué à la demande du joueur
+    Hint,
+    /// Déduction du solveur
+    Solver,
+}
+
+/// Changement d'une case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellChange {
+    pub row: usize,
+    pub col: usize,
+    pub before: CellState,
+    pub after: CellState,
+}
+
+/// Coup: une ou plusieurs cases changées d'un seul geste (un glisser, par exemple)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Move {
+    pub source: MoveSource,
+    pub changes: Vec<CellChange>,
+}
+
+/// Point de sauvegarde nommé, pour explorer une hypothèse puis revenir en arrière
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Checkpoint {
+    name: String,
+    /// Coups appliqués au moment du point de sauvegarde
+    moves: Vec<Move>,
+}
+
+/// Journal des coups joués sur une grille, avec annulation, rétablissement et points de sauvegarde
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveLog {
+    /// Grille de départ, au format de `Grid::to_text`
+    start: String,
+    moves: Vec<Move>,
+    /// Nombre de coups appliqués; les suivants peuvent être rétablis
+    position: usize,
+    checkpoints: Vec<Checkpoint>,
+    /// Grille courante, reconstruite au chargement
+    #[serde(skip, default = "empty_grid")]
+    grid: Grid,
+}
+
+fn empty_grid() -> Grid {
+    Grid::new(0, 0)
+}
+
+impl MoveLog {
+    /// Démarre un journal vide sur une grille
+    pub fn new(start: Grid) -> Self {
+        Self {
+            start: start.to_text(),
+            moves: Vec::new(),
+            position: 0,
+            checkpoints: Vec::new(),
+            grid: start,
+        }
+    }
+
+    /// Grille courante
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Coups appliqués, du plus ancien au plus récent
+    pub fn moves(&self) -> &[Move] {
+        &self.moves[..self.position]
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.position > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.position < self.moves.len()
+    }
+
+    /// Joue un nouveau coup sur une case; les coups annulés ne peuvent plus être rétablis.
+    /// Renvoie faux si la case avait déjà cet état.
+    pub fn play(&mut self, row: usize, col: usize, state: CellState, source: MoveSource) -> Result<bool, String> {
+        let Some(change) = self.change(row, col, state)? else {
+            return Ok(false);
+        };
+        self.moves.truncate(self.position);
+        self.moves.push(Move { source, changes: vec![change] });
+        self.position += 1;
+        Ok(true)
+    }
+
+    /// Ajoute une case au dernier coup joué (suite d'un glisser)
+    pub fn extend(&mut self, row: usize, col: usize, state: CellState) -> Result<bool, String> {
+        if !self.can_undo() || self.can_redo() {
+            return Err("Aucun coup en cours à prolonger".to_string());
+        }
+        let Some(change) = self.change(row, col, state)? else {
+            return Ok(false);
+        };
+        self.moves[self.position - 1].changes.push(change);
+        Ok(true)
+    }
+
+    /// Annule le dernier coup appliqué
+    pub fn undo(&mut self) -> Option<&Move> {
+        if !self.can_undo() {
+            return None;
+        }
+        self.position -= 1;
+        let played = &self.moves[self.position];
+        for change in played.changes.iter().rev() {
+            let _ = self.grid.set(change.row, change.col, change.before);
+        }
+        Some(played)
+    }
+
+    /// Rétablit le dernier coup annulé
+    pub fn redo(&mut self) -> Option<&Move> {
+        if !self.can_redo() {
+            return None;
+        }
+        let played = &self.moves[self.position];
+        for change in &played.changes {
+            let _ = self.grid.set(change.row, change.col, change.after);
+        }
+        self.position += 1;
+        Some(played)
+    }
+
+    /// Enregistre les coups appliqués sous un nom (remplace un point de même nom)
+    pub fn checkpoint(&mut self, name: &str) {
+        let checkpoint = Checkpoint { name: name.to_string(), moves: self.moves().to_vec() };
+        match self.checkpoints.iter_mut().find(|existing| existing.name == name) {
+            Some(existing) => *existing = checkpoint,
+            None => self.checkpoints.push(checkpoint),
+        }
+    }
+
+    /// Noms des points de sauvegarde, dans l'ordre de création
+    pub fn checkpoints(&self) -> Vec<&str> {
+        self.checkpoints.iter().map(|checkpoint| checkpoint.name.as_str()).collect()
+    }
+
+    /// Revient à un point de sauvegarde; les coups joués depuis sont abandonnés
+    pub fn restore(&mut self, name: &str) -> Result<(), String> {
+        let checkpoint = self.checkpoints.iter()
+            .find(|checkpoint| checkpoint.name == name)
+            .ok_or_else(|| format!("Point de sauvegarde inconnu: {}", name))?;
+        self.grid = self.replay(&checkpoint.moves)?;
+        self.moves = checkpoint.moves.clone();
+        self.position = self.moves.len();
+        Ok(())
+    }
+
+    /// Grille après les `step` premiers coups (0: grille de départ), pour rejouer la partie
+    pub fn grid_at(&self, step: usize) -> Result<Grid, String> {
+        let moves = self.moves.get(..step)
+            .ok_or_else(|| format!("Le journal ne contient que {} coups", self.moves.len()))?;
+        self.replay(moves)
+    }
+
+    /// Sérialise le journal en JSON
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("Erreur lors de la sérialisation JSON: {}", e))
+    }
+
+    /// Relit un journal JSON et rejoue ses coups pour retrouver la grille courante
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let mut log: MoveLog = serde_json::from_str(json)
+            .map_err(|e| format!("Erreur lors du parsing JSON: {}", e))?;
+        if log.position > log.moves.len() {
+            return Err(format!("Position {} au-delà des {} coups", log.position, log.moves.len()));
+        }
+        // Tous les coups, y compris ceux à rétablir, doivent s'enchaîner
+        log.replay(&log.moves)?;
+        log.grid = log.grid_at(log.position)?;
+        Ok(log)
+    }
+
+    /// Enregistre le journal dans un fichier JSON
+    pub fn save_json_file(&self, path: &str) -> Result<(), String> {
+        std::fs::write(path, self.to_json()?)
+            .map_err(|e| format!("Erreur lors de l'écriture du fichier: {}", e))
+    }
+
+    /// Charge un journal depuis un fichier JSON
+    pub fn from_json_file(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Erreur lors de la lecture du fichier: {}", e))?;
+        Self::from_json(&content)
+    }
+
+    /// Changement de la case vers `state`, appliqué à la grille courante
+    fn change(&mut self, row: usize, col: usize, state: CellState) -> Result<Option<CellChange>, String> {
+        let before = self.grid.get(row, col)
+            .ok_or_else(|| format!("Position ({}, {}) hors limites", row, col))?;
+        if before == state {
+            return Ok(None);
+        }
+        self.grid.set(row, col, state)?;
+        Ok(Some(CellChange { row, col, before, after: state }))
+    }
+
+    /// Rejoue des coups depuis la grille de départ en vérifiant chaque case
+    fn replay(&self, moves: &[Move]) -> Result<Grid, String> {
+        let mut grid = Grid::from_text(&self.start)?;
+        for (index, played) in moves.iter().enumerate() {
+            for change in &played.changes {
+                if grid.get(change.row, change.col) != Some(change.before) {
+                    return Err(format!("Coup {} incohérent en ({}, {})", index + 1, change.row, change.col));
+                }
+                grid.set(change.row, change.col, change.after)?;
+            }
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_redo_and_branching() {
+        let mut log = MoveLog::new(Grid::new(3, 2));
+        assert!(log.play(0, 0, CellState::Filled, MoveSource::Player).unwrap());
+        assert!(log.extend(0, 1, CellState::Filled).unwrap());
+        assert!(!log.play(0, 0, CellState::Filled, MoveSource::Player).unwrap());
+        log.play(1, 2, CellState::Crossed, MoveSource::Hint).unwrap();
+
+        // Le glisser s'annule d'un seul coup
+        assert_eq!(log.undo().unwrap().source, MoveSource::Hint);
+        assert_eq!(log.undo().unwrap().changes.len(), 2);
+        assert_eq!(log.grid().to_text(), "...\n...");
+        log.redo();
+        assert_eq!(log.grid().to_text(), "##.\n...");
+
+        // Un nouveau coup abandonne ceux qui restaient à rétablir
+        log.play(1, 0, CellState::Filled, MoveSource::Solver).unwrap();
+        assert!(!log.can_redo());
+
+        // Hypothèse explorée puis abandonnée
+        log.checkpoint("avant hypothèse");
+        log.play(1, 1, CellState::Filled, MoveSource::Player).unwrap();
+        log.restore("avant hypothèse").unwrap();
+        assert_eq!(log.grid().to_text(), "##.\n#..");
+        assert_eq!(log.moves().len(), 2);
+        assert!(log.restore("inconnu").is_err());
+    }
+
+    #[test]
+    fn test_json_round_trip_and_replay() {
+        let mut log = MoveLog::new(Grid::from_text("x..\n...").unwrap());
+        log.play(0, 1, CellState::Filled, MoveSource::Player).unwrap();
+        log.play(1, 1, CellState::Filled, MoveSource::Solver).unwrap();
+        log.undo();
+
+        let loaded = MoveLog::from_json(&log.to_json().unwrap()).unwrap();
+        assert_eq!(loaded.grid().to_text(), "x#.\n...");
+        assert!(loaded.can_redo());
+        assert_eq!(loaded.grid_at(2).unwrap().to_text(), "x#.\n.#.");
+        assert_eq!(loaded.grid_at(0).unwrap().to_text(), "x..\n...");
+
+        // Un coup qui ne s'enchaîne pas est refusé
+        let tampered = log.to_json().unwrap().replacen("\"before\": \"Empty\"", "\"before\": \"Filled\"", 1);
+        assert!(MoveLog::from_json(&tampered).is_err());
+    }
+}
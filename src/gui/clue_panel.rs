@@ -0,0 +1,155 @@
+use crate::checker::LineKind;
+use crate::grid::Constraints;
+
+/// Indice d'une ligne ou d'une colonne tel que saisi dans le panneau
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClueEntry {
+    pub kind: LineKind,
+    pub index: usize,
+    pub text: String,
+    /// Problème détecté sur cet indice (vide si valide)
+    pub error: String,
+}
+
+impl ClueEntry {
+    pub fn label(&self) -> String {
+        format!("{} {}", self.kind.label(), self.index + 1)
+    }
+}
+
+/// Indices éditables d'un nonogramme, revalidés à chaque modification
+#[derive(Debug, Clone, Default)]
+pub struct CluePanel {
+    pub rows: Vec<ClueEntry>,
+    pub columns: Vec<ClueEntry>,
+    /// Problème global (sommes des lignes et des colonnes différentes)
+    pub summary: String,
+}
+
+impl CluePanel {
+    /// Panneau rempli avec les indices extraits
+    pub fn from_constraints(constraints: &Constraints) -> Self {
+        let entries = |kind: LineKind, clues: &[Vec<usize>]| -> Vec<ClueEntry> {
+            clues.iter().enumerate()
+                .map(|(index, clue)| ClueEntry { kind, index, text: format_clue(clue), error: String::new() })
+                .collect()
+        };
+        let mut panel = Self {
+            rows: entries(LineKind::Row, &constraints.rows),
+            columns: entries(LineKind::Column, &constraints.columns),
+            summary: String::new(),
+        };
+        panel.validate();
+        panel
+    }
+
+    /// Remplace le texte d'un indice et revalide tout le panneau
+    pub fn edit(&mut self, kind: LineKind, index: usize, text: &str) {
+        let entries = match kind {
+            LineKind::Row => &mut self.rows,
+            LineKind::Column => &mut self.columns,
+        };
+        if let Some(entry) = entries.get_mut(index) {
+            entry.text = text.to_string();
+        }
+        self.validate();
+    }
+
+    /// Les indices saisis sont tous valides
+    pub fn is_valid(&self) -> bool {
+        self.summary.is_empty() && self.rows.iter().chain(&self.columns).all(|entry| entry.error.is_empty())
+    }
+
+    /// Contraintes correspondant aux indices saisis, si elles sont valides
+    pub fn constraints(&self) -> Option<Constraints> {
+        if !self.is_valid() {
+            return None;
+        }
+        let clues = |entries: &[ClueEntry]| -> Option<Vec<Vec<usize>>> {
+            entries.iter().map(|entry| parse_clue(&entry.text).ok()).collect()
+        };
+        Constraints::new(self.columns.len(), self.rows.len(), clues(&self.rows)?, clues(&self.columns)?).ok()
+    }
+
+    /// Vérifie chaque indice (saisie, place dans la ligne) puis l'égalité des sommes
+    fn validate(&mut self) {
+        let (width, height) = (self.columns.len(), self.rows.len());
+        let (mut row_sum, mut column_sum) = (0, 0);
+        let mut all_parsed = true;
+
+        for (entries, length, sum) in [(&mut self.rows, width, &mut row_sum), (&mut self.columns, height, &mut column_sum)] {
+            for entry in entries.iter_mut() {
+                entry.error = match parse_clue(&entry.text) {
+                    Ok(clue) => {
+                        let needed = clue.iter().sum::<usize>() + clue.len().saturating_sub(1);
+                        *sum += clue.iter().sum::<usize>();
+                        if needed > length {
+                            format!("trop long: {} cases pour {}", needed, length)
+                        } else {
+                            String::new()
+                        }
+                    }
+                    Err(e) => {
+                        all_parsed = false;
+                        e
+                    }
+                };
+            }
+        }
+
+        self.summary = if all_parsed && row_sum != column_sum {
+            format!("Les lignes totalisent {} cases noires, les colonnes {}", row_sum, column_sum)
+        } else {
+            String::new()
+        };
+    }
+}
+
+/// Lit un indice saisi: nombres séparés par des espaces ou des virgules (vide ou « 0 »: ligne blanche)
+pub fn parse_clue(text: &str) -> Result<Vec<usize>, String> {
+    let mut clue = Vec::new();
+    for part in text.split(|c: char| c.is_whitespace() || c == ',').filter(|part| !part.is_empty()) {
+        let value: usize = part.parse().map_err(|_| format!("« {} » n'est pas un nombre", part))?;
+        if value > 0 {
+            clue.push(value);
+        }
+    }
+    Ok(clue)
+}
+
+/// Texte d'un indice pour le panneau (« 0 » pour une ligne blanche)
+pub fn format_clue(clue: &[usize]) -> String {
+    if clue.is_empty() {
+        return "0".to_string();
+    }
+    clue.iter().map(usize::to_string).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_clue() {
+        assert_eq!(parse_clue("3 1,2"), Ok(vec![3, 1, 2]));
+        assert_eq!(parse_clue(" 0 "), Ok(vec![]));
+        assert!(parse_clue("3 a").is_err());
+    }
+
+    #[test]
+    fn test_edit_reports_errors_then_accepts_fix() {
+        let constraints = Constraints::new(3, 3, vec![vec![1], vec![3], vec![1]], vec![vec![1], vec![3], vec![1]]).unwrap();
+        let mut panel = CluePanel::from_constraints(&constraints);
+        assert!(panel.constraints().is_some());
+
+        // Indice trop long pour la ligne, et sommes différentes
+        panel.edit(LineKind::Row, 1, "2 2");
+        assert_eq!(panel.rows[1].error, "trop long: 5 cases pour 3");
+        assert!(!panel.summary.is_empty());
+        assert!(panel.constraints().is_none());
+
+        panel.edit(LineKind::Row, 1, "3");
+        assert!(panel.is_valid());
+        assert_eq!(panel.constraints().unwrap().rows[1], vec![3]);
+    }
+}
@@ -1,14 +1,76 @@
 use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::PathBuf;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
 use anyhow::Result;
+use image::DynamicImage;
+use crate::grid::Constraints;
 
+/// État de la dernière résolution d'un nonogramme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SolveStatus {
+    /// Entrée d'une version précédente, enregistrée après une résolution réussie
+    #[default]
+    Unknown,
+    Solved,
+    /// Des cases restent inconnues après la résolution
+    Partial,
+    Failed,
+}
+
+impl SolveStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SolveStatus::Unknown => "Résolu",
+            SolveStatus::Solved => "Résolu",
+            SolveStatus::Partial => "Partiellement résolu",
+            SolveStatus::Failed => "Échec",
+        }
+    }
+}
+
+/// Avancement de la dernière partie jouée sur ce nonogramme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayProgress {
+    /// Cases marquées (noires ou barrées)
+    pub marked: usize,
+    pub total: usize,
+    pub elapsed_secs: u64,
+    pub complete: bool,
+}
+
+/// Nonogramme de la bibliothèque (une entrée par fichier, la plus récente en tête)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoryEntry {
     pub file_path: String,
     pub timestamp: String,
     pub solver_mode: String,
     pub deductions_count: usize,
+    #[serde(default)]
+    pub status: SolveStatus,
+    #[serde(default)]
+    pub solve_time_ms: Option<u64>,
+    /// Indices extraits (et éventuellement corrigés), réutilisés à la prochaine ouverture
+    #[serde(default)]
+    pub constraints: Option<Constraints>,
+    /// Miniature enregistrée dans le répertoire de configuration
+    #[serde(default)]
+    pub thumbnail: Option<String>,
+    #[serde(default)]
+    pub play: Option<PlayProgress>,
+    #[serde(default)]
+    pub favourite: bool,
+}
+
+impl HistoryEntry {
+    /// Nom du fichier, pour l'affichage et la recherche
+    pub fn name(&self) -> String {
+        Path::new(&self.file_path).file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.file_path.clone())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,65 +79,162 @@ pub struct History {
 }
 
 impl History {
-    const MAX_ENTRIES: usize = 20;
-    
+    const MAX_ENTRIES: usize = 200;
+    const THUMBNAIL_SIZE: u32 = 96;
+
     pub fn new() -> Self {
         Self {
             entries: Vec::new(),
         }
     }
-    
+
     pub fn load() -> Result<Self> {
-        let history_path = Self::get_history_path()?;
-        
-        if history_path.exists() {
-            let content = fs::read_to_string(&history_path)?;
-            let history: History = serde_json::from_str(&content)?;
-            Ok(history)
-        } else {
-            Ok(Self::new())
+        Self::load_from(&Self::get_history_path()?)
+    }
+
+    /// Lit la bibliothèque; un fichier illisible est mis de côté (`.corrupt`) et remplacé par une bibliothèque vide
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        match serde_json::from_str::<History>(&content) {
+            Ok(mut history) => {
+                history.normalize();
+                Ok(history)
+            }
+            Err(_) => {
+                fs::rename(path, path.with_extension("json.corrupt"))?;
+                Ok(Self::new())
+            }
         }
     }
-    
-    pub fn save(&self) -> Result<()> {
-        let history_path = Self::get_history_path()?;
-        
+
+    /// Écrit la bibliothèque d'un bloc (fichier temporaire puis renommage)
+    pub fn save_to(&self, path: &Path) -> Result<()> {
         // Créer le répertoire parent si nécessaire
-        if let Some(parent) = history_path.parent() {
+        if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
-        
+
         let content = serde_json::to_string_pretty(self)?;
-        fs::write(&history_path, content)?;
-        
+        let temporary = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        fs::write(&temporary, content)?;
+        fs::rename(&temporary, path)?;
+
         Ok(())
     }
-    
-    pub fn add_entry(&mut self, entry: HistoryEntry) {
+
+    /// Modifie la bibliothèque enregistrée sans écraser les changements d'une autre instance:
+    /// relecture, modification et écriture se font sous verrou. Renvoie la bibliothèque à jour.
+    pub fn update(change: impl FnOnce(&mut History)) -> Result<Self> {
+        Self::update_at(&Self::get_history_path()?, change)
+    }
+
+    pub fn update_at(path: &Path, change: impl FnOnce(&mut History)) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let _lock = FileLock::acquire(path)?;
+        let mut history = Self::load_from(path)?;
+        change(&mut history);
+        history.save_to(path)?;
+        Ok(history)
+    }
+
+    /// Ajoute ou met à jour l'entrée du fichier, placée en tête; le favori, la partie,
+    /// la miniature et les indices déjà connus sont conservés
+    pub fn add_entry(&mut self, mut entry: HistoryEntry) {
+        if let Some(index) = self.entries.iter().position(|existing| existing.file_path == entry.file_path) {
+            let previous = self.entries.remove(index);
+            entry.favourite |= previous.favourite;
+            entry.play = entry.play.or(previous.play);
+            entry.thumbnail = entry.thumbnail.or(previous.thumbnail);
+            entry.constraints = entry.constraints.or(previous.constraints);
+        }
+
         // Ajouter au début
         self.entries.insert(0, entry);
-        
-        // Limiter à MAX_ENTRIES
-        if self.entries.len() > Self::MAX_ENTRIES {
-            self.entries.truncate(Self::MAX_ENTRIES);
+
+        // Limiter à MAX_ENTRIES, en gardant les favoris; la miniature de l'entrée retirée est supprimée
+        while self.entries.len() > Self::MAX_ENTRIES {
+            match self.entries.iter().rposition(|entry| !entry.favourite) {
+                Some(index) => {
+                    Self::remove_thumbnail(&self.entries.remove(index));
+                }
+                None => break,
+            }
         }
     }
-    
-    pub fn get_recent(&self, count: usize) -> Vec<HistoryEntry> {
-        self.entries.iter().take(count).cloned().collect()
+
+    pub fn entry(&self, file_path: &str) -> Option<&HistoryEntry> {
+        self.entries.iter().find(|entry| entry.file_path == file_path)
     }
-    
-    pub fn clear(&mut self) {
-        self.entries.clear();
+
+    pub fn set_play_progress(&mut self, file_path: &str, progress: PlayProgress) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.file_path == file_path) {
+            entry.play = Some(progress);
+        }
+    }
+
+    /// Ajoute ou retire un favori; renvoie le nouvel état
+    pub fn toggle_favourite(&mut self, file_path: &str) -> bool {
+        match self.entries.iter_mut().find(|entry| entry.file_path == file_path) {
+            Some(entry) => {
+                entry.favourite = !entry.favourite;
+                entry.favourite
+            }
+            None => false,
+        }
     }
-    
+
+    /// Entrées dont le chemin contient tous les mots cherchés, favoris en tête
+    pub fn search(&self, query: &str, favourites_only: bool) -> Vec<&HistoryEntry> {
+        let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        let mut found: Vec<&HistoryEntry> = self.entries.iter()
+            .filter(|entry| !favourites_only || entry.favourite)
+            .filter(|entry| {
+                let path = entry.file_path.to_lowercase();
+                words.iter().all(|word| path.contains(word.as_str()))
+            })
+            .collect();
+        found.sort_by_key(|entry| !entry.favourite);
+        found
+    }
+
+    /// Enregistre la miniature d'une image dans le répertoire de configuration
+    pub fn save_thumbnail(file_path: &str, image: &DynamicImage) -> Result<String> {
+        let mut path = Self::get_history_path()?;
+        path.set_file_name("thumbnails");
+        fs::create_dir_all(&path)?;
+        path.push(format!("{:016x}.png", fnv_hash(file_path)));
+
+        image.thumbnail(Self::THUMBNAIL_SIZE, Self::THUMBNAIL_SIZE).save(&path)?;
+        Ok(path.display().to_string())
+    }
+
+    /// Supprime la miniature d'une entrée retirée de la bibliothèque (déjà absente: rien à faire)
+    fn remove_thumbnail(entry: &HistoryEntry) {
+        if let Some(thumbnail) = &entry.thumbnail {
+            let _ = fs::remove_file(thumbnail);
+        }
+    }
+
+    /// Une seule entrée par fichier (les versions précédentes gardaient chaque résolution)
+    fn normalize(&mut self) {
+        let mut seen = std::collections::HashSet::new();
+        self.entries.retain(|entry| seen.insert(entry.file_path.clone()));
+    }
+
     fn get_history_path() -> Result<PathBuf> {
         let mut path = dirs::config_dir()
             .ok_or_else(|| anyhow::anyhow!("Impossible de trouver le répertoire de configuration"))?;
-        
+
         path.push("nonogram-solver");
         path.push("history.json");
-        
+
         Ok(path)
     }
 }
@@ -85,3 +244,133 @@ impl Default for History {
         Self::new()
     }
 }
+
+/// Verrou entre instances: verrou exclusif du système sur le fichier `.lock`, libéré à la
+/// fermeture du fichier, y compris par le système si l'instance s'arrête brutalement.
+/// Le fichier lui-même reste en place: le supprimer laisserait une autre instance verrouiller
+/// l'ancien fichier pendant qu'une troisième en crée un nouveau.
+struct FileLock {
+    _file: File,
+}
+
+impl FileLock {
+    fn acquire(target: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(target.with_extension("json.lock"))?;
+        for _ in 0..250 {
+            match file.try_lock() {
+                Ok(()) => return Ok(Self { _file: file }),
+                Err(TryLockError::WouldBlock) => thread::sleep(Duration::from_millis(20)),
+                Err(TryLockError::Error(e)) => return Err(e.into()),
+            }
+        }
+        Err(anyhow::anyhow!("Bibliothèque verrouillée par une autre instance"))
+    }
+}
+
+/// Hachage FNV-1a, stable d'une version à l'autre (nom des miniatures)
+fn fnv_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(file_path: &str) -> HistoryEntry {
+        HistoryEntry {
+            file_path: file_path.to_string(),
+            timestamp: "2025-01-01 10:00:00".to_string(),
+            solver_mode: "Ultime".to_string(),
+            deductions_count: 3,
+            status: SolveStatus::Solved,
+            solve_time_ms: Some(12),
+            constraints: None,
+            thumbnail: None,
+            play: None,
+            favourite: false,
+        }
+    }
+
+    fn store(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("nonogram-history-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        directory.join("history.json")
+    }
+
+    #[test]
+    fn test_library_merges_entries_and_searches() {
+        let mut history = History::new();
+        history.add_entry(entry("/puzzles/chat.png"));
+        history.add_entry(entry("/puzzles/coeur.png"));
+        assert!(history.toggle_favourite("/puzzles/chat.png"));
+        history.set_play_progress("/puzzles/chat.png", PlayProgress { marked: 4, total: 25, elapsed_secs: 30, complete: false });
+
+        // Nouvelle résolution du même fichier: une seule entrée, favori et partie conservés
+        history.add_entry(entry("/puzzles/chat.png"));
+        assert_eq!(history.entries.len(), 2);
+        let chat = history.entry("/puzzles/chat.png").unwrap();
+        assert!(chat.favourite);
+        assert_eq!(chat.play.unwrap().marked, 4);
+
+        assert_eq!(history.search("COEUR", false).len(), 1);
+        assert_eq!(history.search("", true)[0].name(), "chat.png");
+        assert!(history.search("chien", false).is_empty());
+    }
+
+    #[test]
+    fn test_evicted_entries_lose_their_thumbnail() {
+        let directory = store("thumbnails").with_file_name("thumbnails");
+        fs::create_dir_all(&directory).unwrap();
+        let thumbnail = directory.join("ancien.png");
+        fs::write(&thumbnail, b"png").unwrap();
+
+        let mut history = History::new();
+        history.add_entry(HistoryEntry { thumbnail: Some(thumbnail.display().to_string()), ..entry("/puzzles/ancien.png") });
+        for index in 0..History::MAX_ENTRIES - 1 {
+            history.add_entry(entry(&format!("/puzzles/{}.png", index)));
+        }
+        assert!(thumbnail.exists());
+
+        // La bibliothèque est pleine: la plus ancienne entrée et sa miniature disparaissent
+        history.add_entry(entry("/puzzles/nouveau.png"));
+        assert!(history.entry("/puzzles/ancien.png").is_none());
+        assert!(!thumbnail.exists());
+        let _ = fs::remove_dir_all(directory.parent().unwrap());
+    }
+
+    #[test]
+    fn test_store_survives_corruption_and_concurrent_updates() {
+        let path = store("concurrent");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{ pas du json").unwrap();
+        assert!(History::load_from(&path).unwrap().entries.is_empty());
+        assert!(path.with_extension("json.corrupt").exists());
+
+        let writers: Vec<_> = (0..8)
+            .map(|index| {
+                let path = path.clone();
+                thread::spawn(move || {
+                    History::update_at(&path, |history| history.add_entry(entry(&format!("/puzzles/{}.png", index)))).unwrap();
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        assert_eq!(History::load_from(&path).unwrap().entries.len(), 8);
+
+        // Le verrou tenu n'est jamais repris, quel que soit son âge; libéré, il ne gêne plus
+        let lock = FileLock::acquire(&path).unwrap();
+        let other = File::open(path.with_extension("json.lock")).unwrap();
+        assert!(matches!(other.try_lock(), Err(TryLockError::WouldBlock)));
+        drop(lock);
+        assert!(other.try_lock().is_ok());
+        drop(other);
+        History::update_at(&path, |history| history.add_entry(entry("/puzzles/apres.png"))).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -1,71 +1,184 @@
-use slint::{Image, SharedPixelBuffer, Rgb8Pixel, VecModel, ModelRc};
-use std::path::PathBuf;
+use slint::{Image, Model, SharedPixelBuffer, Rgba8Pixel, VecModel, ModelRc};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
+use crate::checker::LineKind;
+use crate::editor::{PuzzleDesign, Uniqueness};
+use crate::grid::{CellState, Constraints};
+use crate::image_parser::Calibration;
+use crate::image_parser::calibration::CalibrationHandle;
+use crate::gui::clue_panel::{format_clue, ClueEntry, CluePanel};
+use crate::gui::history::{History, HistoryEntry, PlayProgress, SolveStatus};
+use crate::gui::presenter::{CellInfo, GridSettings, Presenter, SolverMode, Stage};
+use crate::play::{HintReason, LineState, PlaySession, Tool};
+use crate::solver::SolveStep;
 
 slint::include_modules!();
 
 pub fn run_gui() -> Result<(), Box<dyn std::error::Error>> {
     let app = MainWindow::new()?;
-    
+
     // État partagé
-    let current_file = Arc::new(Mutex::new(Option::<PathBuf>::None));
-    let current_constraints = Arc::new(Mutex::new(Option::<crate::grid::Constraints>::None));
-    let result_image = Arc::new(Mutex::new(Option::<image::DynamicImage>::None));
-    let history = Arc::new(Mutex::new(crate::gui::history::History::load().unwrap_or_default()));
-    
+    let presenter = Arc::new(Mutex::new(Presenter::new()));
+    let history = Arc::new(Mutex::new(History::load().unwrap_or_default()));
+    let session: Arc<Mutex<Option<PlaySession>>> = Arc::new(Mutex::new(None));
+    // Fichier du nonogramme en cours de partie, pour enregistrer son avancement
+    let play_path = Arc::new(Mutex::new(String::new()));
+    let play_timer = Rc::new(slint::Timer::default());
+    let design: Arc<Mutex<Option<DesignState>>> = Arc::new(Mutex::new(None));
+    let calibration: Arc<Mutex<Option<CalibrationState>>> = Arc::new(Mutex::new(None));
+    // Fichier d'indices déposé avant son image
+    let pending_constraints: Arc<Mutex<Option<PathBuf>>> = Arc::new(Mutex::new(None));
+
     // Callback: Parcourir fichier
     {
         let app_weak = app.as_weak();
         app.on_browse_file(move || {
             let app = app_weak.upgrade().unwrap();
-            
+
             // Utiliser rfd pour le dialogue de fichier
             if let Some(path) = rfd::FileDialog::new()
-                .add_filter("Images", &["png", "jpg", "jpeg", "bmp", "gif", "tiff", "webp"])
+                .add_filter("Images", &IMAGE_EXTENSIONS)
+                .add_filter("Indices (JSON, XML webpbn, .non)", &Constraints::FILE_EXTENSIONS)
                 .pick_file()
             {
+                app.invoke_import_file(path.display().to_string().into());
+            }
+        });
+    }
+
+    // Fichiers glissés depuis le gestionnaire de fichiers
+    {
+        use slint::winit_030::winit::event::WindowEvent;
+        use slint::winit_030::{EventResult, WinitWindowAccessor};
+
+        let app_weak = app.as_weak();
+        app.window().on_winit_window_event(move |_, event| {
+            let app_weak = app_weak.clone();
+            match event {
+                WindowEvent::HoveredFile(_) => {
+                    let _ = app_weak.upgrade_in_event_loop(|app| app.set_drop_hover(true));
+                }
+                WindowEvent::HoveredFileCancelled => {
+                    let _ = app_weak.upgrade_in_event_loop(|app| app.set_drop_hover(false));
+                }
+                WindowEvent::DroppedFile(path) => {
+                    let path = path.display().to_string();
+                    let _ = app_weak.upgrade_in_event_loop(move |app| {
+                        app.set_drop_hover(false);
+                        app.invoke_import_file(path.into());
+                    });
+                }
+                _ => {}
+            }
+            EventResult::Propagate
+        });
+    }
+
+    // Callback: Ouvrir une image ou un fichier d'indices (dialogue, glisser-déposer)
+    {
+        let app_weak = app.as_weak();
+        let presenter_clone = presenter.clone();
+        let pending_clone = pending_constraints.clone();
+
+        app.on_import_file(move |path| {
+            let app = app_weak.upgrade().unwrap();
+            if app.get_is_solving() {
+                app.set_status("Résolution en cours: réessayez une fois terminée".into());
+                return;
+            }
+            let path = PathBuf::from(path.as_str());
+            let extension = path.extension()
+                .and_then(|extension| extension.to_str())
+                .map(str::to_lowercase)
+                .unwrap_or_default();
+
+            if Constraints::FILE_EXTENSIONS.contains(&extension.as_str()) {
+                if presenter_clone.lock().unwrap().image().is_none() {
+                    app.set_status(format!("Indices de {} en attente: déposez l'image correspondante", path.display()).into());
+                    *pending_clone.lock().unwrap() = Some(path);
+                    return;
+                }
+                apply_constraints(&app, &presenter_clone, &path);
+            } else if IMAGE_EXTENSIONS.contains(&extension.as_str()) {
                 app.set_file_path(path.display().to_string().into());
                 app.set_can_load(true);
+                app.invoke_load_image();
+                if let Some(pending) = pending_clone.lock().unwrap().take() {
+                    apply_constraints(&app, &presenter_clone, &pending);
+                }
+            } else {
+                app.set_status(format!("Format non pris en charge: {}", path.display()).into());
+            }
+        });
+    }
+
+    // Callback: Coller une image (capture d'écran d'une grille en ligne, par exemple)
+    {
+        let app_weak = app.as_weak();
+        let presenter_clone = presenter.clone();
+        let history_clone = history.clone();
+
+        app.on_paste_image(move || {
+            let app = app_weak.upgrade().unwrap();
+            let mut clipboard = match arboard::Clipboard::new() {
+                Ok(clipboard) => clipboard,
+                Err(e) => {
+                    app.set_status(format!("Erreur: presse-papiers inaccessible ({})", e).into());
+                    return;
+                }
+            };
+
+            match clipboard.get_image() {
+                Ok(data) => {
+                    let Some(buffer) = image::RgbaImage::from_raw(data.width as u32, data.height as u32, data.bytes.into_owned()) else {
+                        app.set_status("Erreur: image du presse-papiers illisible".into());
+                        return;
+                    };
+                    let directory = dirs::config_dir().unwrap_or_else(std::env::temp_dir).join("nonogram-solver").join("collages");
+                    let mut presenter = presenter_clone.lock().unwrap();
+                    match presenter.set_pasted_image(image::DynamicImage::ImageRgba8(buffer), &directory) {
+                        Ok(_) => {
+                            let path = presenter.path().map(|path| path.display().to_string()).unwrap_or_default();
+                            app.set_file_path(path.clone().into());
+                            app.set_can_load(true);
+                            show_loaded(&app, &mut presenter, &history_clone.lock().unwrap());
+                            app.set_status(format!("Image collée (enregistrée dans {})", path).into());
+                        }
+                        Err(e) => {
+                            app.set_status(format!("Erreur: {}", e).into());
+                        }
+                    }
+                }
+                // Un fichier copié dans le gestionnaire de fichiers arrive comme texte (chemin ou URI)
+                Err(_) => match clipboard.get_text().ok().and_then(|text| copied_file(&text)) {
+                    Some(path) => app.invoke_import_file(path.display().to_string().into()),
+                    None => app.set_status("Le presse-papiers ne contient pas d'image".into()),
+                },
             }
         });
     }
-    
+
     // Callback: Charger image
     {
         let app_weak = app.as_weak();
-        let current_file_clone = current_file.clone();
-        
+        let presenter_clone = presenter.clone();
+        let history_clone = history.clone();
+
         app.on_load_image(move || {
             let app = app_weak.upgrade().unwrap();
             let file_path = app.get_file_path().to_string();
-            
+
             if file_path.is_empty() {
                 return;
             }
-            
-            // Charger l'image
-            match image::open(&file_path) {
-                Ok(img) => {
-                    // Convertir en Slint Image
-                    let rgba_img = img.to_rgba8();
-                    let (width, height) = rgba_img.dimensions();
-                    
-                    let buffer = SharedPixelBuffer::<Rgb8Pixel>::clone_from_slice(
-                        &rgba_img.as_raw().chunks(4).map(|chunk| {
-                            Rgb8Pixel { r: chunk[0], g: chunk[1], b: chunk[2] }
-                        }).collect::<Vec<_>>(),
-                        width,
-                        height,
-                    );
-                    
-                    let slint_image = Image::from_rgb8(buffer);
-                    app.set_input_image(slint_image);
-                    app.set_can_solve(true);
-                    app.set_status("Image chargée avec succès".into());
-                    
-                    // Sauvegarder le chemin
-                    *current_file_clone.lock().unwrap() = Some(PathBuf::from(file_path));
+
+            let mut presenter = presenter_clone.lock().unwrap();
+            match presenter.load(&PathBuf::from(&file_path)) {
+                Ok(_) => {
+                    show_loaded(&app, &mut presenter, &history_clone.lock().unwrap());
                 }
                 Err(e) => {
                     app.set_status(format!("Erreur: {}", e).into());
@@ -73,257 +186,799 @@ pub fn run_gui() -> Result<(), Box<dyn std::error::Error>> {
             }
         });
     }
-    
+
     // Callback: Résoudre
     {
         let app_weak = app.as_weak();
-        let current_file_clone = current_file.clone();
-        let result_image_clone = result_image.clone();
+        let presenter_clone = presenter.clone();
         let history_clone = history.clone();
-        
+
         app.on_solve(move || {
             let app = app_weak.upgrade().unwrap();
-            let file_path_opt = current_file_clone.lock().unwrap().clone();
-            
-            if file_path_opt.is_none() {
+            let mode = match SolverMode::from_index(app.get_solver_mode()) {
+                Ok(mode) => mode,
+                Err(e) => {
+                    app.set_status(e.into());
+                    return;
+                }
+            };
+            let settings = if app.get_auto_detect() {
+                GridSettings::Auto
+            } else {
+                GridSettings::Manual {
+                    cell_size: app.get_cell_size().max(1) as u32,
+                    margin_left: app.get_margin_left().max(0) as u32,
+                    margin_top: app.get_margin_top().max(0) as u32,
+                }
+            };
+
+            // Le calcul se fait sur une copie; elle remplace l'état partagé une fois terminée
+            let mut work = presenter_clone.lock().unwrap().clone();
+            if work.image().is_none() {
                 app.set_status("Veuillez d'abord charger une image".into());
                 return;
             }
-            
-            let file_path = file_path_opt.unwrap();
-            let solver_mode = app.get_solver_mode();
-            let auto_detect = app.get_auto_detect();
-            let cell_size = app.get_cell_size() as u32;
-            let margin_left = app.get_margin_left() as u32;
-            let margin_top = app.get_margin_top() as u32;
-            
+            let had_clues = work.clues().is_some();
+
             // Marquer comme en cours de résolution
             app.set_is_solving(true);
             app.set_progress(0.0);
-            app.set_status("Chargement de l'image...".into());
-            
+            app.set_status(Stage::Loaded.progress().1.into());
+
             // Lancer la résolution dans un thread séparé
             let app_weak_clone = app_weak.clone();
+            let presenter_shared = presenter_clone.clone();
+            let history_shared = history_clone.clone();
             thread::spawn(move || {
-                // Charger l'image
-                let img = match image::open(&file_path) {
-                    Ok(img) => img,
-                    Err(e) => {
-                        let app = app_weak_clone.upgrade().unwrap();
-                        app.set_status(format!("Erreur: {}", e).into());
-                        app.set_is_solving(false);
-                        return;
+                let started = Instant::now();
+                let outcome = work.run(settings, mode, |stage| {
+                    let (progress, message) = stage.progress();
+                    let _ = app_weak_clone.upgrade_in_event_loop(move |app| {
+                        app.set_progress(progress);
+                        app.set_status(message.into());
+                    });
+                });
+                let solve_time_ms = started.elapsed().as_millis() as u64;
+
+                // Ajouter à la bibliothèque (relue et réécrite sous verrou, hors de la boucle d'événements)
+                let library = work.path().map(|path| path.display().to_string()).and_then(|file_path| {
+                    let status = match (&outcome, work.solved()) {
+                        (Err(_), _) => SolveStatus::Failed,
+                        (Ok(_), Some(grid)) if grid.count_empty_cells() == 0 => SolveStatus::Solved,
+                        (Ok(_), _) => SolveStatus::Partial,
+                    };
+                    let entry = HistoryEntry {
+                        thumbnail: work.image().and_then(|image| History::save_thumbnail(&file_path, image).ok()),
+                        file_path,
+                        timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                        solver_mode: mode.name().to_string(),
+                        deductions_count: *outcome.as_ref().unwrap_or(&0),
+                        status,
+                        solve_time_ms: Some(solve_time_ms),
+                        constraints: work.clues().and_then(|clues| clues.constraints()),
+                        play: None,
+                        favourite: false,
+                    };
+                    History::update(|history| history.add_entry(entry)).ok()
+                });
+
+                let _ = app_weak_clone.upgrade_in_event_loop(move |app| {
+                    if let Some(library) = library {
+                        *history_shared.lock().unwrap() = library;
+                        show_library(&app, &history_shared.lock().unwrap());
                     }
-                };
-                
-                // Charger les contraintes
-                // Essayer d'abord l'extraction automatique, puis fallback sur JSON
-                let constraints = {
-                    // Essayer l'extraction automatique
-                    match crate::gui::constraint_extractor::ConstraintExtractor::extract(&img) {
-                        Ok(c) => {
-                            let app = app_weak_clone.upgrade().unwrap();
-                            app.set_status("Contraintes extraites automatiquement".into());
-                            c
-                        },
-                        Err(_) => {
-                            // Fallback: essayer de charger depuis JSON
-                            let constraints_path = file_path.with_extension("json");
-                            match std::fs::read_to_string(&constraints_path) {
-                                Ok(json) => match serde_json::from_str(&json) {
-                                    Ok(c) => {
-                                        let app = app_weak_clone.upgrade().unwrap();
-                                        app.set_status("Contraintes chargées depuis JSON".into());
-                                        c
-                                    },
-                                    Err(e) => {
-                                        let app = app_weak_clone.upgrade().unwrap();
-                                        app.set_status(format!("Erreur: extraction auto échouée et JSON invalide: {}", e).into());
-                                        app.set_is_solving(false);
-                                        return;
-                                    }
-                                },
-                                Err(_) => {
-                                    let app = app_weak_clone.upgrade().unwrap();
-                                    app.set_status("Erreur: extraction auto échouée et pas de fichier JSON".into());
-                                    app.set_is_solving(false);
-                                    return;
-                                }
-                            }
+
+                    if !had_clues {
+                        if let Some(clues) = work.clues() {
+                            show_clues(&app, clues);
                         }
                     }
-                };
-                
-                // Mettre à jour le statut
-                {
-                    let app = app_weak_clone.upgrade().unwrap();
-                    app.set_progress(10.0);
-                    app.set_status("Analyse de l'image...".into());
-                }
-                
-                // Parser l'image
-                let config = if auto_detect {
-                    match crate::image_parser::ImageParser::auto_detect_config(&img, &constraints) {
-                        Ok(c) => c,
+
+                    match outcome {
+                        Ok(count) => {
+                            if let Some(result) = work.result() {
+                                app.set_result_image(to_slint_image(result));
+                            }
+                            app.set_progress(100.0);
+                            let note = if had_clues { String::new() } else { format!(" — {}", work.clue_note()) };
+                            app.set_status(format!("Résolution terminée ! {} déductions trouvées{}", count, note).into());
+                            app.set_can_save(true);
+                            app.set_can_play(true);
+                            show_steps(&app, work.steps(), work.steps().len());
+                        }
                         Err(e) => {
-                            let app = app_weak_clone.upgrade().unwrap();
-                            app.set_status(format!("Erreur de détection: {}", e).into());
-                            app.set_is_solving(false);
-                            return;
+                            app.set_status(format!("Erreur: {}", e).into());
                         }
                     }
-                } else {
-                    crate::image_parser::ImageParserConfig {
-                        cell_size,
-                        margin_left,
-                        margin_top,
-                    }
-                };
-                
-                let parser = crate::image_parser::ImageParser::new(config);
-                let mut grid = match parser.parse(&img, &constraints) {
-                    Ok(g) => g,
-                    Err(e) => {
-                        let app = app_weak_clone.upgrade().unwrap();
-                        app.set_status(format!("Erreur de parsing: {}", e).into());
-                        app.set_is_solving(false);
-                        return;
+
+                    // Garder le résultat seulement si l'image n'a pas changé entre-temps
+                    let mut presenter = presenter_shared.lock().unwrap();
+                    if presenter.path() == work.path() {
+                        show_grid_overlay(&app, &work);
+                        *presenter = work;
                     }
-                };
-                
-                // Mettre à jour le statut
-                {
-                    let app = app_weak_clone.upgrade().unwrap();
-                    app.set_progress(20.0);
-                    app.set_status("Résolution en cours...".into());
-                }
-                
-                // Résoudre selon le mode
-                let deductions = match solver_mode {
-                    0 => {
-                        // Basique
-                        let mut solver = crate::solver::NonogramSolver::new(constraints.clone());
-                        match solver.solve(&mut grid, true) {
-                            Ok(d) => d,
-                            Err(e) => {
-                                let app = app_weak_clone.upgrade().unwrap();
-                                app.set_status(format!("Erreur de résolution: {}", e).into());
-                                app.set_is_solving(false);
-                                return;
-                            }
+                    app.set_is_solving(false);
+                });
+            });
+        });
+    }
+
+    // Callback: Correction d'un indice
+    {
+        let app_weak = app.as_weak();
+        let presenter_clone = presenter.clone();
+
+        app.on_clue_edited(move |column, index, text| {
+            let app = app_weak.upgrade().unwrap();
+            let kind = if column { LineKind::Column } else { LineKind::Row };
+
+            let valid = {
+                let mut presenter = presenter_clone.lock().unwrap();
+                let valid = presenter.edit_clue(kind, index as usize, text.as_str());
+                if let Some(clues) = presenter.clues() {
+                    update_clue_errors(&app, clues);
+                }
+                valid
+            };
+
+            // Relancer la résolution dès que les indices redeviennent cohérents
+            if valid && app.get_can_solve() && !app.get_is_solving() {
+                app.invoke_solve();
+            }
+        });
+    }
+
+    // Callback: Démarrer une partie
+    {
+        let app_weak = app.as_weak();
+        let presenter_clone = presenter.clone();
+        let session_clone = session.clone();
+        let play_path_clone = play_path.clone();
+        let timer = play_timer.clone();
+
+        app.on_start_play(move || {
+            let app = app_weak.upgrade().unwrap();
+            let started = {
+                let presenter = presenter_clone.lock().unwrap();
+                *play_path_clone.lock().unwrap() = presenter.path().map(|path| path.display().to_string()).unwrap_or_default();
+                presenter.play()
+            };
+            match started {
+                Ok(new_session) => {
+                    show_play(&app, &new_session, None);
+                    let note = if new_session.has_unique_solution() {
+                        ""
+                    } else {
+                        " (solution non unique: la partie se termine quand tous les indices sont respectés)"
+                    };
+                    app.set_status(format!("🎮 Partie commencée{}", note).into());
+                    *session_clone.lock().unwrap() = Some(new_session);
+                    app.set_play_mode(true);
+
+                    // Chronomètre
+                    let app_weak_timer = app_weak.clone();
+                    let session_timer = session_clone.clone();
+                    timer.start(slint::TimerMode::Repeated, Duration::from_secs(1), move || {
+                        if let (Some(app), Some(session)) = (app_weak_timer.upgrade(), session_timer.lock().unwrap().as_ref()) {
+                            app.set_play_time(format_time(session.elapsed()).into());
                         }
+                    });
+                }
+                Err(e) => {
+                    app.set_status(format!("Erreur: {}", e).into());
+                }
+            }
+        });
+    }
+
+    // Callback: Quitter la partie
+    {
+        let app_weak = app.as_weak();
+        let session_clone = session.clone();
+        let history_clone = history.clone();
+        let play_path_clone = play_path.clone();
+        let timer = play_timer.clone();
+
+        app.on_stop_play(move || {
+            let app = app_weak.upgrade().unwrap();
+            timer.stop();
+            if let Some(session) = session_clone.lock().unwrap().take() {
+                record_play(&history_clone, &play_path_clone.lock().unwrap(), &session);
+            }
+            app.set_play_mode(false);
+            app.set_status("Partie terminée".into());
+        });
+    }
+
+    // Callbacks: Coups du joueur (clic, glisser, relâcher)
+    {
+        let app_weak = app.as_weak();
+        let session_clone = session.clone();
+        let history_clone = history.clone();
+        let play_path_clone = play_path.clone();
+
+        app.on_cell_pressed(move |row, col, secondary| {
+            let app = app_weak.upgrade().unwrap();
+            let tool = if secondary || app.get_play_tool() == 1 { Tool::Cross } else { Tool::Fill };
+            let mut guard = session_clone.lock().unwrap();
+            if let Some(session) = guard.as_mut() {
+                if row >= 0 && col >= 0 && session.press(row as usize, col as usize, tool) {
+                    show_play(&app, session, None);
+                    if session.is_complete() {
+                        record_play(&history_clone, &play_path_clone.lock().unwrap(), session);
                     }
-                    1 => {
-                        // Avancé
-                        let mut solver = crate::solver::AdvancedSolver::new(constraints.clone());
-                        match solver.solve(&mut grid, true) {
-                            Ok(d) => d,
-                            Err(e) => {
-                                let app = app_weak_clone.upgrade().unwrap();
-                                app.set_status(format!("Erreur de résolution: {}", e).into());
-                                app.set_is_solving(false);
-                                return;
-                            }
-                        }
+                }
+            }
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let session_clone = session.clone();
+        let history_clone = history.clone();
+        let play_path_clone = play_path.clone();
+
+        app.on_cell_dragged(move |row, col| {
+            let app = app_weak.upgrade().unwrap();
+            let mut guard = session_clone.lock().unwrap();
+            if let Some(session) = guard.as_mut() {
+                if row >= 0 && col >= 0 && session.drag(row as usize, col as usize) {
+                    show_play(&app, session, None);
+                    if session.is_complete() {
+                        record_play(&history_clone, &play_path_clone.lock().unwrap(), session);
                     }
-                    2 => {
-                        // Ultime
-                        let mut solver = crate::solver::UltimateSolver::new(constraints.clone());
-                        match solver.solve(&mut grid, true) {
-                            Ok(d) => d,
-                            Err(e) => {
-                                let app = app_weak_clone.upgrade().unwrap();
-                                app.set_status(format!("Erreur de résolution: {}", e).into());
-                                app.set_is_solving(false);
-                                return;
-                            }
+                }
+            }
+        });
+    }
+    {
+        let session_clone = session.clone();
+
+        app.on_cell_released(move || {
+            if let Some(session) = session_clone.lock().unwrap().as_mut() {
+                session.release();
+            }
+        });
+    }
+
+    // Callback: Indice
+    {
+        let app_weak = app.as_weak();
+        let session_clone = session.clone();
+        let history_clone = history.clone();
+        let play_path_clone = play_path.clone();
+
+        app.on_request_hint(move || {
+            let app = app_weak.upgrade().unwrap();
+            let mut guard = session_clone.lock().unwrap();
+            let Some(session) = guard.as_mut() else {
+                return;
+            };
+            match session.hint() {
+                Some(hint) => {
+                    let cell = format!("ligne {}, colonne {}", hint.row + 1, hint.col + 1);
+                    let message = match hint.reason {
+                        HintReason::Mistake => format!("💡 Erreur effacée en {}", cell),
+                        HintReason::Deduction => format!("💡 Déduction logique en {}", cell),
+                        HintReason::Reveal => format!("💡 Case révélée en {}", cell),
+                    };
+                    show_play(&app, session, Some((hint.row, hint.col)));
+                    if session.is_complete() {
+                        record_play(&history_clone, &play_path_clone.lock().unwrap(), session);
+                    } else {
+                        app.set_status(message.into());
+                    }
+                }
+                None => {
+                    app.set_status("Aucun indice disponible".into());
+                }
+            }
+        });
+    }
+
+    // Callbacks: Annuler / rétablir un coup
+    {
+        let app_weak = app.as_weak();
+        let session_clone = session.clone();
+
+        app.on_undo_move(move || {
+            let app = app_weak.upgrade().unwrap();
+            if let Some(session) = session_clone.lock().unwrap().as_mut() {
+                if session.undo() {
+                    show_play(&app, session, None);
+                }
+            }
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let session_clone = session.clone();
+
+        app.on_redo_move(move || {
+            let app = app_weak.upgrade().unwrap();
+            if let Some(session) = session_clone.lock().unwrap().as_mut() {
+                if session.redo() {
+                    show_play(&app, session, None);
+                }
+            }
+        });
+    }
+
+    // Callbacks: Bibliothèque
+    {
+        let app_weak = app.as_weak();
+        let history_clone = history.clone();
+
+        app.on_show_library(move || {
+            let app = app_weak.upgrade().unwrap();
+            // Relire: une autre instance a pu compléter la bibliothèque
+            if let Ok(library) = History::load() {
+                *history_clone.lock().unwrap() = library;
+            }
+            app.set_library_open(true);
+            show_library(&app, &history_clone.lock().unwrap());
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let history_clone = history.clone();
+
+        app.on_library_search(move || {
+            let app = app_weak.upgrade().unwrap();
+            show_library(&app, &history_clone.lock().unwrap());
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let history_clone = history.clone();
+
+        app.on_library_toggle_favourite(move |path| {
+            let app = app_weak.upgrade().unwrap();
+            match History::update(|history| {
+                history.toggle_favourite(path.as_str());
+            }) {
+                Ok(library) => {
+                    *history_clone.lock().unwrap() = library;
+                    show_library(&app, &history_clone.lock().unwrap());
+                }
+                Err(e) => app.set_status(format!("Erreur: {}", e).into()),
+            }
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+
+        app.on_library_open_entry(move |path| {
+            let app = app_weak.upgrade().unwrap();
+            if app.get_play_mode() {
+                app.invoke_stop_play();
+            }
+            app.set_library_open(false);
+            app.set_file_path(path);
+            app.set_can_load(true);
+            app.invoke_load_image();
+        });
+    }
+
+    // Callbacks: Inspection de la case survolée
+    {
+        let app_weak = app.as_weak();
+        let presenter_clone = presenter.clone();
+
+        app.on_image_hovered(move |x, y| {
+            let app = app_weak.upgrade().unwrap();
+            let info = if x < 0.0 || y < 0.0 {
+                None
+            } else {
+                let presenter = presenter_clone.lock().unwrap();
+                presenter.cell_at(x as u32, y as u32).and_then(|(row, col)| presenter.inspect(row, col))
+            };
+            match info {
+                Some(info) => {
+                    app.set_hover_info(describe_cell(&info).into());
+                    app.set_hover_x(info.rect.x0 as f32);
+                    app.set_hover_y(info.rect.y0 as f32);
+                    app.set_hover_width(info.rect.width() as f32);
+                    app.set_hover_height(info.rect.height() as f32);
+                    app.set_hover_visible(true);
+                }
+                None => {
+                    app.set_hover_info("".into());
+                    app.set_hover_visible(false);
+                }
+            }
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+
+        app.on_image_left(move || {
+            let app = app_weak.upgrade().unwrap();
+            app.set_hover_info("".into());
+            app.set_hover_visible(false);
+        });
+    }
+
+    // Callbacks: Étalonnage manuel de la grille
+    {
+        let app_weak = app.as_weak();
+        let presenter_clone = presenter.clone();
+        let calibration_clone = calibration.clone();
+
+        app.on_start_calibration(move || {
+            let app = app_weak.upgrade().unwrap();
+            let mut presenter = presenter_clone.lock().unwrap();
+            let had_clues = presenter.clues().is_some();
+            match presenter.start_calibration() {
+                Ok(start) => {
+                    if !had_clues {
+                        if let Some(clues) = presenter.clues() {
+                            show_clues(&app, clues);
                         }
                     }
-                    _ => {
-                        let app = app_weak_clone.upgrade().unwrap();
-                        app.set_status("Mode de solveur invalide".into());
-                        app.set_is_solving(false);
-                        return;
+                    let state = CalibrationState { calibration: start, grab: None };
+                    show_calibration(&app, &presenter, &state.calibration);
+                    *calibration_clone.lock().unwrap() = Some(state);
+                    app.set_calibrating(true);
+                    app.set_status("📐 Glissez les coins orange pour cadrer la grille, ou un trait pour l'ajuster".into());
+                }
+                Err(e) => {
+                    app.set_status(format!("Erreur: {}", e).into());
+                }
+            }
+        });
+    }
+    {
+        let calibration_clone = calibration.clone();
+
+        app.on_calibration_pressed(move |x, y, tolerance| {
+            match calibration_clone.lock().unwrap().as_mut() {
+                Some(state) => {
+                    state.grab = state.calibration.handle_at(x, y, tolerance);
+                    state.grab.is_some()
+                }
+                None => false,
+            }
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let presenter_clone = presenter.clone();
+        let calibration_clone = calibration.clone();
+
+        app.on_calibration_dragged(move |x, y| {
+            let app = app_weak.upgrade().unwrap();
+            let mut guard = calibration_clone.lock().unwrap();
+            if let Some(state) = guard.as_mut() {
+                if let Some(handle) = state.grab {
+                    state.calibration.drag(handle, x.max(0.0).round() as u32, y.max(0.0).round() as u32);
+                    show_calibration(&app, &presenter_clone.lock().unwrap(), &state.calibration);
+                }
+            }
+        });
+    }
+    {
+        let calibration_clone = calibration.clone();
+
+        app.on_calibration_released(move || {
+            if let Some(state) = calibration_clone.lock().unwrap().as_mut() {
+                state.grab = None;
+            }
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let presenter_clone = presenter.clone();
+        let calibration_clone = calibration.clone();
+
+        app.on_save_calibration(move || {
+            let app = app_weak.upgrade().unwrap();
+            let Some(state) = calibration_clone.lock().unwrap().take() else {
+                return;
+            };
+            let saved = {
+                let mut presenter = presenter_clone.lock().unwrap();
+                let saved = presenter.set_calibration(state.calibration);
+                show_grid_overlay(&app, &presenter);
+                saved
+            };
+            app.set_calibrating(false);
+            match saved {
+                Ok(()) => {
+                    app.set_has_calibration(true);
+                    // L'étalonnage remplace la détection automatique et les paramètres manuels
+                    app.set_auto_detect(true);
+                    app.set_status("📐 Étalonnage enregistré".into());
+                    if app.get_can_solve() && !app.get_is_solving() {
+                        app.invoke_solve();
+                    }
+                }
+                Err(e) => {
+                    app.set_status(format!("Erreur: {}", e).into());
+                }
+            }
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let calibration_clone = calibration.clone();
+
+        app.on_cancel_calibration(move || {
+            let app = app_weak.upgrade().unwrap();
+            *calibration_clone.lock().unwrap() = None;
+            app.set_calibrating(false);
+            app.set_status("Étalonnage annulé".into());
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let presenter_clone = presenter.clone();
+
+        app.on_clear_calibration(move || {
+            let app = app_weak.upgrade().unwrap();
+            let cleared = presenter_clone.lock().unwrap().clear_calibration();
+            match cleared {
+                Ok(()) => {
+                    app.set_has_calibration(false);
+                    app.set_status("Étalonnage supprimé: la grille sera détectée automatiquement".into());
+                    if app.get_can_solve() && !app.get_is_solving() {
+                        app.invoke_solve();
+                    }
+                }
+                Err(e) => {
+                    app.set_status(format!("Erreur: {}", e).into());
+                }
+            }
+        });
+    }
+
+    // Callback: Curseur des étapes de la résolution
+    {
+        let app_weak = app.as_weak();
+        let presenter_clone = presenter.clone();
+
+        app.on_step_changed(move |count| {
+            let app = app_weak.upgrade().unwrap();
+            let count = count.max(0) as usize;
+            if count == app.get_current_step() as usize {
+                return;
+            }
+            let presenter = presenter_clone.lock().unwrap();
+            match presenter.render_step(count) {
+                Ok(image) => {
+                    app.set_result_image(to_slint_image(&image));
+                    show_steps(&app, presenter.steps(), count);
+                }
+                Err(e) => {
+                    app.set_status(format!("Erreur: {}", e).into());
+                }
+            }
+        });
+    }
+
+    // Callback: Exporter l'animation de la résolution
+    {
+        let app_weak = app.as_weak();
+        let presenter_clone = presenter.clone();
+
+        app.on_export_animation(move || {
+            let app = app_weak.upgrade().unwrap();
+
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("GIF", &["gif"])
+                .add_filter("APNG", &["png", "apng"])
+                .set_file_name("resolution.gif")
+                .save_file()
+            {
+                match presenter_clone.lock().unwrap().export_animation(&path) {
+                    Ok(()) => {
+                        app.set_status(format!("Animation sauvegardée: {}", path.display()).into());
                     }
-                };
-                
-                // Mettre à jour le statut
-                {
-                    let app = app_weak_clone.upgrade().unwrap();
-                    app.set_progress(80.0);
-                    app.set_status("Génération de l'image de résultat...".into());
-                }
-                
-                // Générer l'image de résultat
-                let generator = crate::image_generator::ImageGenerator::new(
-                    crate::image_generator::ImageGeneratorConfig::default()
-                );
-                
-                let result_img = match generator.generate(&img, &grid, &deductions, &config) {
-                    Ok(img) => img,
                     Err(e) => {
-                        let app = app_weak_clone.upgrade().unwrap();
-                        app.set_status(format!("Erreur de génération: {}", e).into());
-                        app.set_is_solving(false);
+                        app.set_status(format!("Erreur de sauvegarde: {}", e).into());
+                    }
+                }
+            }
+        });
+    }
+
+    // Callbacks: Éditeur de nonogrammes (le dessin est conservé en quittant l'éditeur)
+    {
+        let app_weak = app.as_weak();
+        let design_clone = design.clone();
+
+        app.on_start_editor(move || {
+            let app = app_weak.upgrade().unwrap();
+            let mut guard = design_clone.lock().unwrap();
+            if guard.is_none() {
+                match PuzzleDesign::new(app.get_design_width().max(1) as usize, app.get_design_height().max(1) as usize) {
+                    Ok(new_design) => *guard = Some(DesignState::new(new_design)),
+                    Err(e) => {
+                        app.set_status(format!("Erreur: {}", e).into());
                         return;
                     }
-                };
-                
-                // Stocker l'image de résultat pour la sauvegarde
-                *result_image_clone.lock().unwrap() = Some(result_img.clone());
-                
-                // Convertir en Slint Image
-                let rgba_img = result_img.to_rgba8();
-                let (width, height) = rgba_img.dimensions();
-                
-                let buffer = SharedPixelBuffer::<Rgb8Pixel>::clone_from_slice(
-                    &rgba_img.as_raw().chunks(4).map(|chunk| {
-                        Rgb8Pixel { r: chunk[0], g: chunk[1], b: chunk[2] }
-                    }).collect::<Vec<_>>(),
-                    width,
-                    height,
-                );
-                
-                let slint_image = Image::from_rgb8(buffer);
-                
-                // Mettre à jour l'interface
-                let app = app_weak_clone.upgrade().unwrap();
-                app.set_result_image(slint_image);
-                app.set_progress(100.0);
-                app.set_status(format!("Résolution terminée ! {} déductions trouvées", deductions.len()).into());
-                app.set_can_save(true);
-                app.set_is_solving(false);
-                
-                // Ajouter à l'historique
-                let solver_mode_name = match solver_mode {
-                    0 => "Basique",
-                    1 => "Avancé",
-                    2 => "Ultime",
-                    _ => "Inconnu",
-                };
-                
-                let entry = crate::gui::history::HistoryEntry {
-                    file_path: file_path.display().to_string(),
-                    timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-                    solver_mode: solver_mode_name.to_string(),
-                    deductions_count: deductions.len(),
-                };
-                
-                let mut hist = history_clone.lock().unwrap();
-                hist.add_entry(entry);
-                let _ = hist.save();
-            });
+                }
+            }
+            if let Some(state) = guard.as_ref() {
+                show_design(&app, state);
+            }
+            app.set_editor_mode(true);
+            app.set_status("✏️ Dessinez la solution: clic pour noircir ou effacer, clic droit pour effacer".into());
         });
     }
-    
+    {
+        let app_weak = app.as_weak();
+
+        app.on_stop_editor(move || {
+            let app = app_weak.upgrade().unwrap();
+            app.set_editor_mode(false);
+            app.set_status("Prêt".into());
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let design_clone = design.clone();
+
+        app.on_design_pressed(move |row, col, secondary| {
+            let app = app_weak.upgrade().unwrap();
+            let mut guard = design_clone.lock().unwrap();
+            let Some(state) = guard.as_mut() else {
+                return;
+            };
+            if row < 0 || col < 0 {
+                return;
+            }
+            let (row, col) = (row as usize, col as usize);
+            let filled = !secondary && !state.design.is_filled(row, col);
+            state.stroke = Some(filled);
+            if state.paint(row, col, filled) {
+                show_design(&app, state);
+            }
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let design_clone = design.clone();
+
+        app.on_design_dragged(move |row, col| {
+            let app = app_weak.upgrade().unwrap();
+            let mut guard = design_clone.lock().unwrap();
+            let Some(state) = guard.as_mut() else {
+                return;
+            };
+            if let (Some(filled), true) = (state.stroke, row >= 0 && col >= 0) {
+                if state.paint(row as usize, col as usize, filled) {
+                    show_design(&app, state);
+                }
+            }
+        });
+    }
+    {
+        let design_clone = design.clone();
+
+        app.on_design_released(move || {
+            if let Some(state) = design_clone.lock().unwrap().as_mut() {
+                state.stroke = None;
+            }
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let design_clone = design.clone();
+
+        app.on_design_resized(move |width, height| {
+            let app = app_weak.upgrade().unwrap();
+            if let Some(state) = design_clone.lock().unwrap().as_mut() {
+                match state.design.resize(width.max(0) as usize, height.max(0) as usize) {
+                    Ok(()) => state.changed(),
+                    Err(e) => app.set_status(format!("Erreur: {}", e).into()),
+                }
+                show_design(&app, state);
+            }
+        });
+    }
+    {
+        let design_clone = design.clone();
+
+        app.on_design_title_edited(move |title| {
+            if let Some(state) = design_clone.lock().unwrap().as_mut() {
+                state.design.title = title.to_string();
+            }
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let design_clone = design.clone();
+
+        app.on_design_clear(move || {
+            let app = app_weak.upgrade().unwrap();
+            if let Some(state) = design_clone.lock().unwrap().as_mut() {
+                state.design.clear();
+                state.changed();
+                show_design(&app, state);
+            }
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let design_clone = design.clone();
+
+        app.on_design_check(move || {
+            let app = app_weak.upgrade().unwrap();
+            let mut guard = design_clone.lock().unwrap();
+            let Some(state) = guard.as_mut() else {
+                return;
+            };
+            state.report = match state.design.check_uniqueness() {
+                Ok(Uniqueness::Unique) => "✅ Solution unique".to_string(),
+                Ok(Uniqueness::Ambiguous { cells }) => {
+                    let report = format!("⚠️ Plusieurs solutions: {} case(s) ambiguë(s), entourées en orange", cells.len());
+                    state.ambiguous = cells;
+                    report
+                }
+                Ok(Uniqueness::Unknown) => "❓ Recherche interrompue avant de conclure (grille trop grande)".to_string(),
+                Err(e) => format!("Erreur: {}", e),
+            };
+            show_design(&app, state);
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let design_clone = design.clone();
+
+        app.on_design_rate(move || {
+            let app = app_weak.upgrade().unwrap();
+            let mut guard = design_clone.lock().unwrap();
+            let Some(state) = guard.as_mut() else {
+                return;
+            };
+            state.report = match state.design.rate() {
+                Ok(rating) => format!(
+                    "📊 Difficulté: {} ({} passe(s), {}/{} cases par logique ligne par ligne)",
+                    rating.difficulty.label(), rating.passes, rating.logic_cells, rating.total_cells
+                ),
+                Err(e) => format!("Erreur: {}", e),
+            };
+            show_design(&app, state);
+        });
+    }
+    {
+        let app_weak = app.as_weak();
+        let design_clone = design.clone();
+
+        app.on_design_export(move || {
+            let app = app_weak.upgrade().unwrap();
+            let guard = design_clone.lock().unwrap();
+            let Some(state) = guard.as_ref() else {
+                return;
+            };
+
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("JSON", &["json"])
+                .add_filter("XML webpbn", &["xml"])
+                .add_filter("Image imprimable", &["png", "jpg", "jpeg", "bmp"])
+                .set_file_name("nonogramme.json")
+                .save_file()
+            {
+                match state.design.export(&path.display().to_string()) {
+                    Ok(()) => {
+                        app.set_status(format!("Nonogramme exporté: {}", path.display()).into());
+                    }
+                    Err(e) => {
+                        app.set_status(format!("Erreur d'export: {}", e).into());
+                    }
+                }
+            }
+        });
+    }
+
     // Callback: Sauvegarder résultat
     {
         let app_weak = app.as_weak();
-        let result_image_clone = result_image.clone();
-        
+        let presenter_clone = presenter.clone();
+
         app.on_save_result(move || {
             let app = app_weak.upgrade().unwrap();
-            
+
             // Ouvrir dialogue de sauvegarde
             if let Some(path) = rfd::FileDialog::new()
                 .add_filter("PNG", &["png"])
@@ -331,26 +986,357 @@ pub fn run_gui() -> Result<(), Box<dyn std::error::Error>> {
                 .set_file_name("result.png")
                 .save_file()
             {
-                // Récupérer l'image de résultat depuis l'état partagé
-                let result_img_opt = result_image_clone.lock().unwrap().clone();
-                
-                if let Some(result_img) = result_img_opt {
-                    // Sauvegarder l'image
-                    match result_img.save(&path) {
-                        Ok(_) => {
-                            app.set_status(format!("Résultat sauvegardé: {}", path.display()).into());
-                        }
-                        Err(e) => {
-                            app.set_status(format!("Erreur de sauvegarde: {}", e).into());
-                        }
+                match presenter_clone.lock().unwrap().save(&path) {
+                    Ok(()) => {
+                        app.set_status(format!("Résultat sauvegardé: {}", path.display()).into());
+                    }
+                    Err(e) => {
+                        app.set_status(format!("Erreur de sauvegarde: {}", e).into());
                     }
-                } else {
-                    app.set_status("Aucun résultat à sauvegarder".into());
                 }
             }
         });
     }
-    
+
     app.run()?;
     Ok(())
 }
+
+/// Convertit une image en image Slint
+fn to_slint_image(img: &image::DynamicImage) -> Image {
+    let rgba_img = img.to_rgba8();
+    let buffer = SharedPixelBuffer::<Rgba8Pixel>::clone_from_slice(rgba_img.as_raw(), rgba_img.width(), rgba_img.height());
+    Image::from_rgba8(buffer)
+}
+
+/// Dessin en cours dans l'éditeur
+struct DesignState {
+    design: PuzzleDesign,
+    /// Noircir (vrai) ou effacer les cases parcourues pendant un glisser
+    stroke: Option<bool>,
+    /// Cases signalées par la dernière vérification d'unicité
+    ambiguous: Vec<(usize, usize)>,
+    report: String,
+}
+
+impl DesignState {
+    fn new(design: PuzzleDesign) -> Self {
+        Self { design, stroke: None, ambiguous: Vec::new(), report: String::new() }
+    }
+
+    fn paint(&mut self, row: usize, col: usize, filled: bool) -> bool {
+        let changed = self.design.paint(row, col, filled);
+        if changed {
+            self.changed();
+        }
+        changed
+    }
+
+    /// Le dessin a changé: les vérifications précédentes ne valent plus
+    fn changed(&mut self) {
+        self.ambiguous.clear();
+        self.report.clear();
+    }
+}
+
+/// Affiche le dessin et ses indices, recalculés à chaque modification
+fn show_design(app: &MainWindow, state: &DesignState) {
+    let grid = state.design.solution();
+    let constraints = state.design.constraints();
+
+    let cells: Vec<PlayCell> = (0..grid.height())
+        .flat_map(|row| (0..grid.width()).map(move |col| (row, col)))
+        .map(|(row, col)| PlayCell {
+            state: if state.design.is_filled(row, col) { 1 } else { 0 },
+            hinted: state.ambiguous.contains(&(row, col)),
+        })
+        .collect();
+    let clues = |clues: &[Vec<usize>], separator: &str| -> Vec<PlayClue> {
+        clues.iter()
+            .map(|clue| PlayClue { text: format_clue(clue).replace(' ', separator).into(), satisfied: false, error: false })
+            .collect()
+    };
+
+    app.set_design_width(grid.width() as i32);
+    app.set_design_height(grid.height() as i32);
+    app.set_play_width(grid.width() as i32);
+    app.set_play_height(grid.height() as i32);
+    app.set_play_cells(ModelRc::new(VecModel::from(cells)));
+    app.set_play_rows(ModelRc::new(VecModel::from(clues(&constraints.rows, " "))));
+    app.set_play_columns(ModelRc::new(VecModel::from(clues(&constraints.columns, "\n"))));
+    app.set_design_report(state.report.clone().into());
+}
+
+/// Position du curseur des étapes et description de l'étape affichée
+fn show_steps(app: &MainWindow, steps: &[SolveStep], count: usize) {
+    app.set_step_count(steps.len() as i32);
+    app.set_current_step(count as i32);
+    let label = match count.checked_sub(1).and_then(|index| steps.get(index)) {
+        Some(step) => format!("Étape {}/{}: {} ({} cases)", count, steps.len(), step.label(), step.deductions.len()),
+        None => format!("Départ (0/{})", steps.len()),
+    };
+    app.set_step_label(label.into());
+}
+
+/// Extensions des images acceptées (dialogue, glisser-déposer)
+const IMAGE_EXTENSIONS: [&str; 8] = ["png", "jpg", "jpeg", "bmp", "gif", "tiff", "tif", "webp"];
+
+/// Affiche l'image qui vient d'être chargée et remet l'interface à zéro
+fn show_loaded(app: &MainWindow, presenter: &mut Presenter, history: &History) {
+    if let Some(image) = presenter.image() {
+        app.set_input_image(to_slint_image(image));
+    }
+    app.set_result_image(Image::default());
+    app.set_can_solve(true);
+    app.set_can_save(false);
+    app.set_can_play(false);
+    app.set_play_mode(false);
+    show_steps(app, &[], 0);
+    app.set_view_zoom(1.0);
+    app.set_view_pan_x(0.0);
+    app.set_view_pan_y(0.0);
+    app.set_calibrating(false);
+    app.set_has_calibration(presenter.calibration().is_some());
+    app.set_status("Image chargée avec succès".into());
+
+    // Indices déjà vérifiés dans la bibliothèque, sinon extraits de la nouvelle image
+    let file_path = presenter.path().map(|path| path.display().to_string()).unwrap_or_default();
+    let cached = history.entry(&file_path).and_then(|entry| entry.constraints.clone());
+    match cached.and_then(|constraints| presenter.restore_clues(&constraints, "indices repris de la bibliothèque").ok()) {
+        Some(clues) => {
+            show_clues(app, clues);
+            app.set_status("Image chargée — indices repris de la bibliothèque".into());
+        }
+        None => show_clues(app, &CluePanel::default()),
+    }
+    show_grid_overlay(app, presenter);
+}
+
+/// Indices d'un fichier pour l'image chargée; la résolution est relancée avec eux
+fn apply_constraints(app: &MainWindow, presenter: &Mutex<Presenter>, path: &Path) {
+    let loaded = {
+        let mut presenter = presenter.lock().unwrap();
+        let loaded = presenter.load_constraints(path).map(|clues| show_clues(app, clues));
+        show_grid_overlay(app, &presenter);
+        loaded
+    };
+    match loaded {
+        Ok(()) => {
+            app.set_result_image(Image::default());
+            app.set_can_save(false);
+            app.set_can_play(false);
+            show_steps(app, &[], 0);
+            app.set_status(format!("Contraintes chargées depuis {}", path.display()).into());
+            if app.get_can_solve() && !app.get_is_solving() {
+                app.invoke_solve();
+            }
+        }
+        Err(e) => {
+            app.set_status(format!("Erreur: {}", e).into());
+        }
+    }
+}
+
+/// Fichier copié dans le gestionnaire de fichiers: chemin ou URI `file://` sur la première ligne
+/// (URI décodée: `%20` pour une espace, caractères accentués encodés)
+fn copied_file(text: &str) -> Option<PathBuf> {
+    let line = text.lines().next()?.trim();
+    let path = if line.starts_with("file:") {
+        url::Url::parse(line).ok()?.to_file_path().ok()?
+    } else {
+        PathBuf::from(line)
+    };
+    path.is_file().then_some(path)
+}
+
+/// Étalonnage en cours: poignée saisie à la souris
+struct CalibrationState {
+    calibration: Calibration,
+    grab: Option<CalibrationHandle>,
+}
+
+/// Traits de l'étalonnage et aperçu des cases lues avec lui
+fn show_calibration(app: &MainWindow, presenter: &Presenter, calibration: &Calibration) {
+    let lines = |lines: &[u32]| ModelRc::new(VecModel::from(lines.iter().map(|&line| line as f32).collect::<Vec<_>>()));
+    app.set_calibration_columns(lines(&calibration.geometry.columns));
+    app.set_calibration_rows(lines(&calibration.geometry.rows));
+    match presenter.calibration_preview(calibration) {
+        Ok(preview) => app.set_calibration_preview(to_slint_image(&preview)),
+        Err(e) => app.set_status(format!("Erreur: {}", e).into()),
+    }
+}
+
+/// Traits de la grille détectée, superposables aux images
+fn show_grid_overlay(app: &MainWindow, presenter: &Presenter) {
+    match presenter.grid_overlay() {
+        Some(overlay) => app.set_grid_overlay(to_slint_image(&overlay)),
+        None => {
+            app.set_grid_overlay(Image::default());
+            app.set_show_grid(false);
+        }
+    }
+    app.set_hover_visible(false);
+}
+
+/// Ligne d'inspection: position, état, étape de la résolution et indices de la case
+fn describe_cell(info: &CellInfo) -> String {
+    let state = match info.state {
+        Some(CellState::Filled) => "noire",
+        Some(CellState::Crossed) => "barrée",
+        Some(CellState::Empty) => "inconnue",
+        None => "non analysée",
+    };
+    let origin = match (&info.technique, info.state) {
+        (Some(technique), _) => format!("trouvée par: {}", technique),
+        (None, Some(CellState::Filled | CellState::Crossed)) => "lue sur l'image".to_string(),
+        _ => String::new(),
+    };
+    let clue = |clue: &Option<Vec<usize>>| clue.as_deref().map(format_clue).unwrap_or_else(|| "?".to_string());
+
+    let mut text = format!("Ligne {}, colonne {} — {}", info.row + 1, info.col + 1, state);
+    if !origin.is_empty() {
+        text.push_str(&format!(" ({})", origin));
+    }
+    text.push_str(&format!(" — indices: ligne {} / colonne {}", clue(&info.row_clue), clue(&info.column_clue)));
+    text
+}
+
+/// Affiche les entrées de la bibliothèque qui correspondent à la recherche
+fn show_library(app: &MainWindow, history: &History) {
+    if !app.get_library_open() {
+        return;
+    }
+    let items: Vec<LibraryItem> = history.search(app.get_library_query().as_str(), app.get_library_favourites())
+        .into_iter()
+        .map(|entry| {
+            let thumbnail = entry.thumbnail.as_ref()
+                .and_then(|path| image::open(path).ok())
+                .map(|image| to_slint_image(&image))
+                .unwrap_or_default();
+            let mut details = format!("{} — {} — {} déductions", entry.status.label(), entry.solver_mode, entry.deductions_count);
+            if let Some(ms) = entry.solve_time_ms {
+                details.push_str(&format!(" en {} ms", ms));
+            }
+            if let Some(constraints) = &entry.constraints {
+                details.push_str(&format!(" — {}×{}", constraints.width, constraints.height));
+            }
+            let progress = match entry.play {
+                Some(play) if play.complete => format!("🏆 Partie terminée en {}", format_time(Duration::from_secs(play.elapsed_secs))),
+                Some(play) => format!("🎮 Partie en cours: {}/{} cases, {}", play.marked, play.total, format_time(Duration::from_secs(play.elapsed_secs))),
+                None => String::new(),
+            };
+            LibraryItem {
+                path: entry.file_path.clone().into(),
+                name: entry.name().into(),
+                thumbnail,
+                details: details.into(),
+                progress: progress.into(),
+                date: entry.timestamp.clone().into(),
+                favourite: entry.favourite,
+            }
+        })
+        .collect();
+    app.set_library_items(ModelRc::new(VecModel::from(items)));
+}
+
+/// Enregistre l'avancement de la partie dans la bibliothèque, sans bloquer l'interface
+fn record_play(history: &Arc<Mutex<History>>, file_path: &str, session: &PlaySession) {
+    if file_path.is_empty() {
+        return;
+    }
+    let grid = session.grid();
+    let total = grid.width() * grid.height();
+    let progress = PlayProgress {
+        marked: total - grid.count_empty_cells(),
+        total,
+        elapsed_secs: session.elapsed().as_secs(),
+        complete: session.is_complete(),
+    };
+    let history = history.clone();
+    let file_path = file_path.to_string();
+    thread::spawn(move || {
+        if let Ok(library) = History::update(|library| library.set_play_progress(&file_path, progress)) {
+            *history.lock().unwrap() = library;
+        }
+    });
+}
+
+/// Durée au format mm:ss
+fn format_time(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Affiche la grille de jeu, ses indices et la fin de partie
+fn show_play(app: &MainWindow, session: &PlaySession, hinted: Option<(usize, usize)>) {
+    let grid = session.grid();
+    let constraints = session.constraints();
+
+    let cells: Vec<PlayCell> = (0..grid.height())
+        .flat_map(|row| (0..grid.width()).map(move |col| (row, col)))
+        .map(|(row, col)| PlayCell {
+            state: match grid.get(row, col) {
+                Some(CellState::Filled) => 1,
+                Some(CellState::Crossed) => 2,
+                _ => 0,
+            },
+            hinted: hinted == Some((row, col)),
+        })
+        .collect();
+
+    let clues = |kind: LineKind, clues: &[Vec<usize>], separator: &str| -> Vec<PlayClue> {
+        clues.iter().enumerate()
+            .map(|(index, clue)| {
+                let state = session.line_state(kind, index);
+                PlayClue {
+                    text: format_clue(clue).replace(' ', separator).into(),
+                    satisfied: state == LineState::Satisfied,
+                    error: state == LineState::Error,
+                }
+            })
+            .collect()
+    };
+
+    app.set_play_width(grid.width() as i32);
+    app.set_play_height(grid.height() as i32);
+    app.set_play_cells(ModelRc::new(VecModel::from(cells)));
+    app.set_play_rows(ModelRc::new(VecModel::from(clues(LineKind::Row, &constraints.rows, " "))));
+    app.set_play_columns(ModelRc::new(VecModel::from(clues(LineKind::Column, &constraints.columns, "\n"))));
+    app.set_play_time(format_time(session.elapsed()).into());
+    app.set_play_complete(session.is_complete());
+    app.set_can_undo(session.log().can_undo());
+    app.set_can_redo(session.log().can_redo());
+    if session.is_complete() {
+        app.set_status(format!("🎉 Grille terminée en {} !", format_time(session.elapsed())).into());
+    }
+}
+
+/// Lignes du panneau d'indices pour l'interface
+fn clue_lines(entries: &[ClueEntry]) -> Vec<ClueLine> {
+    entries.iter()
+        .map(|entry| ClueLine {
+            label: entry.label().into(),
+            text: entry.text.clone().into(),
+            error: entry.error.clone().into(),
+        })
+        .collect()
+}
+
+/// Remplace les indices affichés (nouvelle extraction)
+fn show_clues(app: &MainWindow, panel: &CluePanel) {
+    app.set_row_clues(ModelRc::new(VecModel::from(clue_lines(&panel.rows))));
+    app.set_column_clues(ModelRc::new(VecModel::from(clue_lines(&panel.columns))));
+    app.set_clue_summary(panel.summary.clone().into());
+}
+
+/// Met à jour les erreurs affichées sans recréer les champs (la saisie en cours est conservée)
+fn update_clue_errors(app: &MainWindow, panel: &CluePanel) {
+    for (model, entries) in [(app.get_row_clues(), &panel.rows), (app.get_column_clues(), &panel.columns)] {
+        for (row, line) in clue_lines(entries).into_iter().enumerate() {
+            if model.row_data(row).is_some_and(|shown| shown.error != line.error) {
+                model.set_row_data(row, line);
+            }
+        }
+    }
+    app.set_clue_summary(panel.summary.clone().into());
+}
@@ -1,5 +1,7 @@
 pub mod logic;
 pub mod constraint_extractor;
 pub mod history;
+pub mod clue_panel;
+pub mod presenter;
 
 pub use logic::run_gui;
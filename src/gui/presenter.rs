@@ -0,0 +1,645 @@
+use std::path::{Path, PathBuf};
+use image::{DynamicImage, Rgba, RgbaImage};
+use crate::checker::LineKind;
+use crate::grid::{CellState, Constraints, Grid};
+use crate::image_generator::ImageGenerator;
+use crate::image_parser::{Calibration, CellRect, GridGeometry, ImageParser, ParserConfig};
+use crate::layout::{LayoutAnalyzer, PuzzleLayout};
+use crate::play::PlaySession;
+use crate::animation::SolveAnimation;
+use crate::solver::{AdvancedSolver, Deduction, NonogramSolver, SolveStep, UltimateSolver};
+use super::clue_panel::CluePanel;
+use super::constraint_extractor::ConstraintExtractor;
+
+/// Solveur choisi dans l'interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverMode {
+    Basic,
+    Advanced,
+    Ultimate,
+}
+
+impl SolverMode {
+    /// Mode correspondant au bouton radio (0: basique, 1: avancé, 2: ultime)
+    pub fn from_index(index: i32) -> Result<Self, String> {
+        match index {
+            0 => Ok(SolverMode::Basic),
+            1 => Ok(SolverMode::Advanced),
+            2 => Ok(SolverMode::Ultimate),
+            _ => Err("Mode de solveur invalide".to_string()),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SolverMode::Basic => "Basique",
+            SolverMode::Advanced => "Avancé",
+            SolverMode::Ultimate => "Ultime",
+        }
+    }
+}
+
+/// Position de la grille: détectée automatiquement ou saisie
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridSettings {
+    Auto,
+    Manual { cell_size: u32, margin_left: u32, margin_top: u32 },
+}
+
+/// État du traitement, chaque étape dépendant des précédentes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Empty,
+    Loaded,
+    Extracted,
+    Parsed,
+    Solved,
+    Rendered,
+}
+
+impl Stage {
+    /// Avancement (en %) et message affichés pendant que l'étape est calculée
+    pub fn progress(&self) -> (f32, &'static str) {
+        match self {
+            Stage::Empty => (0.0, "Prêt"),
+            Stage::Loaded => (0.0, "Chargement de l'image..."),
+            Stage::Extracted => (5.0, "Extraction des indices..."),
+            Stage::Parsed => (15.0, "Analyse de l'image..."),
+            Stage::Solved => (30.0, "Résolution en cours..."),
+            Stage::Rendered => (80.0, "Génération de l'image de résultat..."),
+        }
+    }
+}
+
+/// Case survolée dans l'image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellInfo {
+    pub row: usize,
+    pub col: usize,
+    /// Position de la case dans l'image, en pixels
+    pub rect: CellRect,
+    /// État résolu, ou à défaut lu sur l'image
+    pub state: Option<CellState>,
+    /// Étape de la résolution qui a trouvé la case (None si lue sur l'image ou inconnue)
+    pub technique: Option<String>,
+    pub row_clue: Option<Vec<usize>>,
+    pub column_clue: Option<Vec<usize>>,
+}
+
+/// Logique de l'interface, indépendante de Slint:
+/// chargement → extraction des indices → analyse de la grille → résolution → rendu → sauvegarde.
+///
+/// Chaque étape invalide les suivantes; `run` enchaîne celles qui restent à faire.
+#[derive(Clone, Default)]
+pub struct Presenter {
+    path: Option<PathBuf>,
+    image: Option<DynamicImage>,
+    /// Mise en page trouvée lors de l'extraction (sert aussi à localiser les cases)
+    layout: Option<PuzzleLayout>,
+    clues: Option<CluePanel>,
+    /// Origine des indices, pour le statut
+    clue_note: String,
+    /// Étalonnage manuel enregistré pour l'image, prioritaire sur la détection automatique
+    calibration: Option<Calibration>,
+    parser_config: Option<ParserConfig>,
+    parsed: Option<Grid>,
+    solved: Option<Grid>,
+    deductions: Vec<Deduction>,
+    /// Déductions regroupées dans l'ordre de la résolution, pour la rejouer
+    steps: Vec<SolveStep>,
+    result: Option<DynamicImage>,
+}
+
+impl Presenter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Étape atteinte
+    pub fn stage(&self) -> Stage {
+        if self.result.is_some() {
+            Stage::Rendered
+        } else if self.solved.is_some() {
+            Stage::Solved
+        } else if self.parsed.is_some() {
+            Stage::Parsed
+        } else if self.clues.is_some() {
+            Stage::Extracted
+        } else if self.image.is_some() {
+            Stage::Loaded
+        } else {
+            Stage::Empty
+        }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn image(&self) -> Option<&DynamicImage> {
+        self.image.as_ref()
+    }
+
+    pub fn clues(&self) -> Option<&CluePanel> {
+        self.clues.as_ref()
+    }
+
+    /// Origine des indices affichés (lecture, corrections, fichier JSON)
+    pub fn clue_note(&self) -> &str {
+        &self.clue_note
+    }
+
+    pub fn calibration(&self) -> Option<&Calibration> {
+        self.calibration.as_ref()
+    }
+
+    /// Grille résolue
+    pub fn solved(&self) -> Option<&Grid> {
+        self.solved.as_ref()
+    }
+
+    pub fn steps(&self) -> &[SolveStep] {
+        &self.steps
+    }
+
+    pub fn result(&self) -> Option<&DynamicImage> {
+        self.result.as_ref()
+    }
+
+    /// Charge une image depuis un fichier
+    pub fn load(&mut self, path: &Path) -> Result<&DynamicImage, String> {
+        let image = ImageParser::load_image(&path.display().to_string())?;
+        self.set_image(path.to_path_buf(), image);
+        self.image().ok_or_else(|| "Aucune image chargée".to_string())
+    }
+
+    /// Remplace l'image courante; les indices seront extraits de la nouvelle image
+    pub fn set_image(&mut self, path: PathBuf, image: DynamicImage) {
+        *self = Self {
+            // Un étalonnage illisible est ignoré: la détection automatique prend le relais
+            calibration: Calibration::load_for(&path).ok().flatten(),
+            path: Some(path),
+            image: Some(image),
+            ..Self::default()
+        };
+    }
+
+    /// Colle une image (presse-papiers): elle est enregistrée en PNG dans `directory`
+    /// pour que la bibliothèque et l'étalonnage puissent la retrouver
+    pub fn set_pasted_image(&mut self, image: DynamicImage, directory: &Path) -> Result<&DynamicImage, String> {
+        std::fs::create_dir_all(directory)
+            .map_err(|e| format!("Erreur lors de la création du répertoire: {}", e))?;
+        let path = directory.join(format!("collage-{}.png", chrono::Local::now().format("%Y%m%d-%H%M%S-%3f")));
+        ImageGenerator::save_image(&image, &path.display().to_string())?;
+        self.set_image(path, image);
+        self.image().ok_or_else(|| "Aucune image chargée".to_string())
+    }
+
+    /// Lit les indices dans le fichier du même nom que l'image (`grille.png` → `grille.json`, `.xml`, `.pbn`, `.non`),
+    /// sinon sur l'image
+    pub fn extract(&mut self) -> Result<&CluePanel, String> {
+        if let Some(paired) = self.path.as_deref().and_then(Constraints::paired_file) {
+            return self.load_constraints(&paired);
+        }
+        let image = self.image.as_ref().ok_or("Veuillez d'abord charger une image")?;
+
+        let extraction = ConstraintExtractor::extract(image)
+            .map_err(|e| format!("Extraction automatique échouée ({}) et pas de fichier d'indices à côté de l'image", e))?;
+        let mut note = "Contraintes extraites automatiquement".to_string();
+        if !extraction.corrections.is_empty() {
+            note.push_str(&format!(" ({} indice(s) corrigé(s))", extraction.corrections.len()));
+        }
+        if extraction.inconsistent {
+            note.push_str(": indices contradictoires, vérifiez-les");
+        } else if extraction.unverified {
+            note.push_str(": corrections non vérifiées, vérifiez-les");
+        }
+
+        self.invalidate(Stage::Loaded);
+        self.layout = Some(extraction.layout);
+        self.clue_note = note;
+        Ok(self.clues.insert(CluePanel::from_constraints(&extraction.constraints)))
+    }
+
+    /// Reprend les indices d'un fichier de contraintes (JSON, XML webpbn, `.non`) pour l'image chargée
+    pub fn load_constraints(&mut self, path: &Path) -> Result<&CluePanel, String> {
+        let constraints = Constraints::from_file(&path.display().to_string())?;
+        self.restore_clues(&constraints, &format!("Contraintes chargées depuis {}", path.display()))
+    }
+
+    /// Reprend des indices déjà vérifiés (bibliothèque) au lieu de les relire sur l'image;
+    /// la mise en page sert toujours à localiser les cases
+    pub fn restore_clues(&mut self, constraints: &Constraints, note: &str) -> Result<&CluePanel, String> {
+        if self.image.is_none() {
+            return Err("Veuillez d'abord charger une image".to_string());
+        }
+        self.invalidate(Stage::Loaded);
+        self.layout = self.image.as_ref().and_then(|image| LayoutAnalyzer::new().analyze(image).ok());
+        self.clue_note = note.to_string();
+        Ok(self.clues.insert(CluePanel::from_constraints(constraints)))
+    }
+
+    /// Corrige un indice; renvoie vrai si tous les indices sont de nouveau valides
+    pub fn edit_clue(&mut self, kind: LineKind, index: usize, text: &str) -> bool {
+        let Some(clues) = self.clues.as_mut() else {
+            return false;
+        };
+        clues.edit(kind, index, text);
+        let valid = clues.is_valid();
+        // Les dimensions ne changent pas: la grille lue reste valable
+        self.invalidate(Stage::Parsed);
+        valid
+    }
+
+    /// Lit l'état des cases sur l'image
+    pub fn parse(&mut self, settings: GridSettings) -> Result<&Grid, String> {
+        let image = self.image.as_ref().ok_or("Veuillez d'abord charger une image")?;
+        let constraints = self.constraints()?;
+
+        let config = match settings {
+            GridSettings::Manual { cell_size, margin_left, margin_top } => ParserConfig {
+                cell_size,
+                margin_left,
+                margin_top,
+                ..Default::default()
+            },
+            GridSettings::Auto => match (&self.calibration, &self.layout) {
+                (Some(calibration), _) if calibration.matches(image, constraints.width, constraints.height) => calibration.parser_config(),
+                (_, Some(layout)) if layout.width() == constraints.width && layout.height() == constraints.height => layout.parser_config(),
+                _ => ImageParser::auto_detect_config(image, constraints.width, constraints.height)
+                    .map_err(|e| format!("Erreur de détection: {}", e))?,
+            },
+        };
+        let grid = ImageParser::new(config.clone()).parse_image(image, constraints.width, constraints.height)
+            .map_err(|e| format!("Erreur de parsing: {}", e))?;
+
+        self.invalidate(Stage::Extracted);
+        self.parser_config = Some(config);
+        Ok(self.parsed.insert(grid))
+    }
+
+    /// Résout la grille lue; renvoie le nombre de déductions
+    pub fn solve(&mut self, mode: SolverMode) -> Result<usize, String> {
+        let constraints = self.constraints()?;
+        let mut grid = self.parsed.clone().ok_or("Grille non analysée")?;
+
+        let steps = match mode {
+            SolverMode::Basic => NonogramSolver::new().solve_steps(&mut grid, &constraints),
+            SolverMode::Advanced => AdvancedSolver::new().solve_steps(&mut grid, &constraints),
+            SolverMode::Ultimate => UltimateSolver::new().solve_steps(&mut grid, &constraints),
+        }.map_err(|e| format!("Erreur de résolution: {}", e))?;
+        let deductions = SolveStep::deductions_until(&steps, steps.len());
+
+        self.invalidate(Stage::Parsed);
+        self.solved = Some(grid);
+        self.deductions = deductions;
+        self.steps = steps;
+        Ok(self.deductions.len())
+    }
+
+    /// Marque les déductions sur l'image d'entrée
+    pub fn render(&mut self) -> Result<&DynamicImage, String> {
+        let image = self.image.as_ref().ok_or("Veuillez d'abord charger une image")?;
+        let output = self.generator()?.generate_output_image(image, &self.deductions)
+            .map_err(|e| format!("Erreur de génération: {}", e))?;
+        Ok(self.result.insert(output))
+    }
+
+    /// Image de la résolution après les `count` premières étapes
+    pub fn render_step(&self, count: usize) -> Result<DynamicImage, String> {
+        let image = self.image.as_ref().ok_or("Veuillez d'abord charger une image")?;
+        self.generator()?.generate_output_image(image, &SolveStep::deductions_until(&self.steps, count))
+            .map_err(|e| format!("Erreur de génération: {}", e))
+    }
+
+    /// Enregistre la résolution étape par étape en GIF ou en APNG (selon l'extension)
+    pub fn export_animation(&self, path: &Path) -> Result<(), String> {
+        let image = self.image.as_ref().ok_or("Veuillez d'abord charger une image")?;
+        let animation = SolveAnimation::new();
+        let frames = animation.frames(image, &self.generator()?, &self.steps)?;
+        animation.export(&frames, &path.display().to_string())
+    }
+
+    /// Géométrie de la grille: celle utilisée pour lire les cases, sinon celle de la mise en page détectée
+    pub fn geometry(&self) -> Option<GridGeometry> {
+        match (&self.parser_config, self.constraints()) {
+            (Some(config), Ok(constraints)) => Some(config.geometry(constraints.width, constraints.height)),
+            _ => self.layout.as_ref().map(|layout| layout.parser_config().geometry(layout.width(), layout.height())),
+        }
+    }
+
+    /// Case sous le point (x, y) de l'image
+    pub fn cell_at(&self, x: u32, y: u32) -> Option<(usize, usize)> {
+        let geometry = self.geometry()?;
+        let index = |lines: &[u32], value: u32| {
+            let index = lines.partition_point(|&line| line <= value);
+            (index > 0 && index < lines.len()).then(|| index - 1)
+        };
+        Some((index(&geometry.rows, y)?, index(&geometry.columns, x)?))
+    }
+
+    /// État, origine et indices d'une case
+    pub fn inspect(&self, row: usize, col: usize) -> Option<CellInfo> {
+        let rect = self.geometry()?.cell_rect(row, col)?;
+        let state = self.solved.as_ref().or(self.parsed.as_ref()).and_then(|grid| grid.get(row, col));
+        let technique = self.steps.iter()
+            .find(|step| step.deductions.iter().any(|deduction| deduction.row == row && deduction.col == col))
+            .map(SolveStep::label);
+        let constraints = self.constraints().ok();
+        Some(CellInfo {
+            row,
+            col,
+            rect,
+            state,
+            technique,
+            row_clue: constraints.as_ref().and_then(|c| c.get_row_constraint(row).cloned()),
+            column_clue: constraints.as_ref().and_then(|c| c.get_column_constraint(col).cloned()),
+        })
+    }
+
+    /// Traits de la grille détectée sur fond transparent, à superposer à l'image
+    pub fn grid_overlay(&self) -> Option<DynamicImage> {
+        let image = self.image.as_ref()?;
+        let mut overlay = RgbaImage::new(image.width(), image.height());
+        Self::draw_lines(&mut overlay, &self.geometry()?);
+        Some(DynamicImage::ImageRgba8(overlay))
+    }
+
+    /// Étalonnage de départ: celui enregistré, sinon la grille détectée, sinon une grille régulière centrée
+    pub fn start_calibration(&mut self) -> Result<Calibration, String> {
+        if self.clues.is_none() {
+            self.extract()?;
+        }
+        let constraints = self.constraints()?;
+        let (width, height) = (constraints.width, constraints.height);
+        let image = self.image.as_ref().ok_or("Veuillez d'abord charger une image")?;
+
+        if let Some(calibration) = self.calibration.as_ref().filter(|calibration| calibration.matches(image, width, height)) {
+            return Ok(calibration.clone());
+        }
+        let detected = self.geometry()
+            .or_else(|| ImageParser::auto_detect_config(image, width, height).ok().map(|config| config.geometry(width, height)))
+            .filter(|geometry| geometry.width() == width && geometry.height() == height)
+            .filter(|geometry| geometry.grid_area().x1 < image.width() && geometry.grid_area().y1 < image.height());
+        match detected {
+            Some(geometry) => Ok(Calibration::from_geometry(image, geometry)),
+            None => {
+                let (image_width, image_height) = (image.width(), image.height());
+                Calibration::from_corners(
+                    image,
+                    (image_width / 5, image_height / 5),
+                    (image_width * 9 / 10, image_height * 9 / 10),
+                    width,
+                    height,
+                )
+            }
+        }
+    }
+
+    /// Aperçu de l'étalonnage: traits et cases lues (noires en vert, barrées en rouge) sur fond transparent
+    pub fn calibration_preview(&self, calibration: &Calibration) -> Result<DynamicImage, String> {
+        let image = self.image.as_ref().ok_or("Veuillez d'abord charger une image")?;
+        let geometry = &calibration.geometry;
+        let grid = ImageParser::new(calibration.parser_config()).parse_image(image, geometry.width(), geometry.height())?;
+
+        let mut overlay = RgbaImage::new(image.width(), image.height());
+        for row in 0..geometry.height() {
+            for col in 0..geometry.width() {
+                let color = match grid.get(row, col) {
+                    Some(CellState::Filled) => Rgba([0, 200, 80, 110]),
+                    Some(CellState::Crossed) => Rgba([230, 40, 40, 110]),
+                    _ => continue,
+                };
+                let Some(rect) = geometry.cell_rect(row, col) else {
+                    continue;
+                };
+                for y in rect.y0..rect.y1.min(overlay.height()) {
+                    for x in rect.x0..rect.x1.min(overlay.width()) {
+                        overlay.put_pixel(x, y, color);
+                    }
+                }
+            }
+        }
+        Self::draw_lines(&mut overlay, geometry);
+        Ok(DynamicImage::ImageRgba8(overlay))
+    }
+
+    /// Enregistre l'étalonnage à côté de l'image; la grille sera relue avec lui
+    pub fn set_calibration(&mut self, calibration: Calibration) -> Result<(), String> {
+        let path = self.path.as_ref().ok_or("Veuillez d'abord charger une image")?;
+        calibration.save_for(path)?;
+        self.calibration = Some(calibration);
+        self.invalidate(Stage::Extracted);
+        Ok(())
+    }
+
+    /// Supprime l'étalonnage enregistré: la grille sera de nouveau détectée automatiquement
+    pub fn clear_calibration(&mut self) -> Result<(), String> {
+        if let Some(path) = &self.path {
+            Calibration::remove_for(path)?;
+        }
+        self.calibration = None;
+        self.invalidate(Stage::Extracted);
+        Ok(())
+    }
+
+    /// Enchaîne les étapes restantes jusqu'au rendu; `progress` est appelé avant chacune
+    pub fn run(&mut self, settings: GridSettings, mode: SolverMode, mut progress: impl FnMut(Stage)) -> Result<usize, String> {
+        if self.stage() < Stage::Extracted {
+            progress(Stage::Extracted);
+            self.extract()?;
+        }
+        if self.stage() < Stage::Parsed {
+            progress(Stage::Parsed);
+            self.parse(settings)?;
+        }
+        progress(Stage::Solved);
+        let count = self.solve(mode)?;
+        progress(Stage::Rendered);
+        self.render()?;
+        Ok(count)
+    }
+
+    /// Démarre une partie sur la grille lue, avec les indices affichés
+    pub fn play(&self) -> Result<PlaySession, String> {
+        let constraints = self.constraints()?;
+        let grid = self.parsed.clone().ok_or("Grille non analysée")?;
+        PlaySession::new(constraints, grid)
+    }
+
+    /// Sauvegarde l'image résultat
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let result = self.result.as_ref().ok_or("Aucun résultat à sauvegarder")?;
+        ImageGenerator::save_image(result, &path.display().to_string())
+    }
+
+    /// Générateur d'images placé sur les cases de la grille analysée
+    fn generator(&self) -> Result<ImageGenerator, String> {
+        if self.solved.is_none() {
+            return Err("Grille non résolue".to_string());
+        }
+        let config = self.parser_config.as_ref().ok_or("Grille non analysée")?;
+        Ok(ImageGenerator::new(ImageGenerator::from_parser(config)))
+    }
+
+    fn draw_lines(overlay: &mut RgbaImage, geometry: &GridGeometry) {
+        let (width, height) = (overlay.width(), overlay.height());
+        let color = Rgba([0, 160, 255, 200]);
+        let area = geometry.grid_area();
+
+        for &x in geometry.columns.iter().filter(|&&x| x < width) {
+            for y in area.y0..area.y1.min(height) {
+                overlay.put_pixel(x, y, color);
+            }
+        }
+        for &y in geometry.rows.iter().filter(|&&y| y < height) {
+            for x in area.x0..area.x1.min(width) {
+                overlay.put_pixel(x, y, color);
+            }
+        }
+    }
+
+    /// Contraintes des indices affichés, s'ils sont valides
+    fn constraints(&self) -> Result<Constraints, String> {
+        let clues = self.clues.as_ref().ok_or("Indices non extraits")?;
+        clues.constraints().ok_or_else(|| "Indices invalides: corrigez-les pour relancer la résolution".to_string())
+    }
+
+    /// Oublie les résultats des étapes postérieures à `stage`
+    fn invalidate(&mut self, stage: Stage) {
+        if stage < Stage::Extracted {
+            self.layout = None;
+            self.clues = None;
+            self.clue_note.clear();
+        }
+        if stage < Stage::Parsed {
+            self.parser_config = None;
+            self.parsed = None;
+        }
+        if stage < Stage::Solved {
+            self.solved = None;
+            self.deductions.clear();
+            self.steps.clear();
+        }
+        self.result = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_parser::calibration::CalibrationHandle;
+
+    fn example(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("examples").join(name)
+    }
+
+    #[test]
+    fn test_full_pipeline_on_example() {
+        let mut presenter = Presenter::new();
+        assert!(presenter.run(GridSettings::Auto, SolverMode::Basic, |_| {}).is_err());
+
+        presenter.load(&example("simple_5x5_empty.png")).unwrap();
+        let mut stages = Vec::new();
+        let count = presenter.run(GridSettings::Auto, SolverMode::Basic, |stage| stages.push(stage)).unwrap();
+
+        assert_eq!(stages, vec![Stage::Extracted, Stage::Parsed, Stage::Solved, Stage::Rendered]);
+        assert_eq!(presenter.stage(), Stage::Rendered);
+        assert!(count > 0);
+        let clues = presenter.clues().unwrap().constraints().unwrap();
+        assert_eq!(clues.rows, vec![vec![2], vec![1, 1], vec![5], vec![1, 1], vec![2]]);
+
+        // La dernière étape de l'animation correspond au résultat
+        let steps = presenter.steps().len();
+        assert!(steps > 0);
+        assert_eq!(SolveStep::deductions_until(presenter.steps(), steps).len(), count);
+        assert_eq!(presenter.render_step(steps).unwrap().to_rgba8(), presenter.result().unwrap().to_rgba8());
+
+        // Inspection: la case sous un point de l'image, son état et l'étape qui l'a trouvée
+        let info = presenter.inspect(2, 3).unwrap();
+        assert_eq!(presenter.cell_at((info.rect.x0 + info.rect.x1) / 2, (info.rect.y0 + info.rect.y1) / 2), Some((2, 3)));
+        assert_eq!(info.state, Some(CellState::Filled));
+        assert_eq!(info.row_clue, Some(vec![5]));
+        assert!(info.technique.is_some());
+        assert_eq!(presenter.cell_at(0, 0), None);
+        let overlay = presenter.grid_overlay().unwrap().to_rgba8();
+        assert_eq!(overlay.get_pixel(info.rect.x0, info.rect.y0 + 2)[3], 200);
+        assert_eq!(overlay.get_pixel(info.rect.x0 + 3, info.rect.y0 + 3)[3], 0);
+
+        let output = std::env::temp_dir().join(format!("nonogram-presenter-{}.png", std::process::id()));
+        presenter.save(&output).unwrap();
+        assert!(output.exists());
+        let _ = std::fs::remove_file(output);
+    }
+
+    #[test]
+    fn test_calibration_is_saved_and_reused() {
+        // Copie de l'exemple: l'étalonnage est enregistré à côté de l'image
+        let directory = std::env::temp_dir().join(format!("nonogram-presenter-calibration-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("grille.png");
+        std::fs::copy(example("simple_5x5_empty.png"), &path).unwrap();
+
+        let mut presenter = Presenter::new();
+        presenter.load(&path).unwrap();
+        let detected = presenter.start_calibration().unwrap();
+        assert_eq!(detected.geometry.width(), 5);
+        let preview = presenter.calibration_preview(&detected).unwrap();
+        assert_eq!(preview.width(), presenter.image().unwrap().width());
+
+        // Grille décalée d'une case: la lecture suit l'étalonnage, y compris après rechargement
+        let mut shifted = detected.clone();
+        let (x0, y0) = (detected.geometry.columns[1], detected.geometry.rows[0]);
+        let (x1, y1) = (2 * detected.geometry.columns[5] - detected.geometry.columns[4], detected.geometry.rows[5]);
+        shifted.drag(CalibrationHandle::BottomRight, x1, y1);
+        shifted.drag(CalibrationHandle::TopLeft, x0, y0);
+        presenter.set_calibration(shifted.clone()).unwrap();
+
+        let mut reloaded = Presenter::new();
+        reloaded.load(&path).unwrap();
+        assert_eq!(reloaded.calibration(), Some(&shifted));
+        reloaded.run(GridSettings::Auto, SolverMode::Basic, |_| {}).unwrap();
+        assert_eq!(reloaded.geometry(), Some(shifted.geometry.clone()));
+
+        reloaded.clear_calibration().unwrap();
+        assert!(!Calibration::path_for(&path).exists());
+        let _ = std::fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn test_pasted_image_and_paired_constraints() {
+        let directory = std::env::temp_dir().join(format!("nonogram-presenter-import-{}", std::process::id()));
+        let image = ImageParser::load_image(&example("simple_5x5_empty.png").display().to_string()).unwrap();
+        let mut presenter = Presenter::new();
+        presenter.set_pasted_image(image, &directory).unwrap();
+        let path = presenter.path().unwrap().to_path_buf();
+        assert!(path.starts_with(&directory) && path.exists());
+
+        // Un fichier .non du même nom que l'image est préféré à la lecture des indices
+        std::fs::write(path.with_extension("non"), "width 5\nheight 5\nrows\n2\n1,1\n5\n1,1\n2\ncolumns\n2\n1,1\n5\n1,1\n2\n").unwrap();
+        presenter.load(&path).unwrap();
+        presenter.extract().unwrap();
+        assert!(presenter.clue_note().ends_with(".non"));
+        assert_eq!(presenter.clues().unwrap().constraints().unwrap().columns[2], vec![5]);
+        let _ = std::fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn test_clue_edit_invalidates_and_resolves() {
+        let mut presenter = Presenter::new();
+        presenter.load(&example("simple_5x5_empty.png")).unwrap();
+        presenter.run(GridSettings::Auto, SolverMode::Advanced, |_| {}).unwrap();
+
+        // Indice invalide: plus de résultat, et la résolution est refusée
+        assert!(!presenter.edit_clue(LineKind::Row, 2, "6"));
+        assert_eq!(presenter.stage(), Stage::Parsed);
+        assert!(presenter.run(GridSettings::Auto, SolverMode::Advanced, |_| {}).is_err());
+
+        // Indice corrigé: la grille lue est réutilisée, seule la résolution est refaite
+        assert!(presenter.edit_clue(LineKind::Row, 2, "5"));
+        let mut stages = Vec::new();
+        presenter.run(GridSettings::Auto, SolverMode::Advanced, |stage| stages.push(stage)).unwrap();
+        assert_eq!(stages, vec![Stage::Solved, Stage::Rendered]);
+        assert!(presenter.result().is_some());
+    }
+}
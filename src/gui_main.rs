@@ -1,13 +1,6 @@
-mod grid;
-mod solver;
-mod image_parser;
-mod image_generator;
-mod drawing;
-mod edge_detection;
-mod gui;
+use nonogram_solver::{animation, checker, editor, grid, image_generator, image_parser, layout, ocr, play, solver};
 
-#[cfg(feature = "ocr")]
-mod ocr;
+mod gui;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     gui::run_gui()
@@ -1,7 +1,8 @@
 use image::{DynamicImage, Rgba, RgbaImage, Rgb};
-use crate::drawing::{draw_filled_circle_mut, draw_cross_mut};
-use crate::solver::Deduction;
-use crate::grid::CellState;
+use crate::drawing::{draw_filled_circle_mut, draw_cross_mut, draw_ring_mut};
+use crate::solver::{Deduction, HeatMap};
+use crate::grid::{Grid, CellState};
+use crate::image_parser::{CellRect, GridGeometry, ParserConfig};
 
 /// Configuration pour le générateur d'image
 #[derive(Debug, Clone)]
@@ -15,6 +16,10 @@ pub struct GeneratorConfig {
     pub highlight_color: Rgba<u8>,
     /// Rayon du cercle de marquage (en proportion de la taille de case)
     pub marker_radius_ratio: f32,
+    /// Couleur des cercles entourant les erreurs probables du joueur (bleu par défaut)
+    pub mistake_color: Rgb<u8>,
+    /// Positions détectées des traits de la grille (sinon: marges + i * taille de case)
+    pub geometry: Option<GridGeometry>,
 }
 
 impl Default for GeneratorConfig {
@@ -25,6 +30,8 @@ impl Default for GeneratorConfig {
             margin_left: 50,
             highlight_color: Rgba([255, 0, 0, 180]), // Rouge semi-transparent
             marker_radius_ratio: 0.3,
+            mistake_color: Rgb([0, 90, 255]),
+            geometry: None,
         }
     }
 }
@@ -62,11 +69,27 @@ impl ImageGenerator {
         Ok(DynamicImage::ImageRgba8(output))
     }
 
+    /// Rectangle d'une case, d'après la géométrie détectée ou les marges
+    fn cell_rect(&self, row: usize, col: usize) -> CellRect {
+        if let Some(rect) = self.config.geometry.as_ref().and_then(|geometry| geometry.cell_rect(row, col)) {
+            return rect;
+        }
+        let x0 = self.config.margin_left + col as u32 * self.config.cell_size;
+        let y0 = self.config.margin_top + row as u32 * self.config.cell_size;
+        CellRect { x0, y0, x1: x0 + self.config.cell_size, y1: y0 + self.config.cell_size }
+    }
+
+    /// Taille d'une case pour dimensionner les marqueurs
+    fn marker_cell_size(&self, rect: &CellRect) -> u32 {
+        rect.width().min(rect.height())
+    }
+
     /// Marque une déduction sur l'image
     fn mark_deduction(&self, image: &mut RgbaImage, deduction: &Deduction) -> Result<(), String> {
         // Calculer le centre de la case
-        let center_x = self.config.margin_left + (deduction.col as u32 * self.config.cell_size) + (self.config.cell_size / 2);
-        let center_y = self.config.margin_top + (deduction.row as u32 * self.config.cell_size) + (self.config.cell_size / 2);
+        let rect = self.cell_rect(deduction.row, deduction.col);
+        let center_x = (rect.x0 + rect.x1) / 2;
+        let center_y = (rect.y0 + rect.y1) / 2;
 
         // Vérifier que le point est dans l'image
         if center_x >= image.width() || center_y >= image.height() {
@@ -77,7 +100,7 @@ impl ImageGenerator {
         }
 
         // Calculer le rayon du marqueur
-        let radius = (self.config.cell_size as f32 * self.config.marker_radius_ratio) as i32;
+        let radius = (self.marker_cell_size(&rect) as f32 * self.config.marker_radius_ratio) as i32;
 
         // Dessiner le marqueur selon le type de déduction
         match deduction.state {
@@ -169,6 +192,91 @@ impl ImageGenerator {
         Ok(())
     }
 
+    /// Génère l'image de sortie en entourant en plus les cases suspectées d'être
+    /// des erreurs du joueur, dans une couleur distincte de celle des déductions
+    pub fn generate_diagnosis_image(
+        &self,
+        input_image: &DynamicImage,
+        deductions: &[Deduction],
+        mistakes: &[(usize, usize)],
+    ) -> Result<DynamicImage, String> {
+        let mut output = self.generate_output_image(input_image, deductions)?.to_rgb8();
+        for &(row, col) in mistakes {
+            let rect = self.cell_rect(row, col);
+            let cell_size = self.marker_cell_size(&rect) as i32;
+            let radius = (cell_size / 2).max(2);
+            let thickness = (cell_size / 10).max(2);
+            let center_x = (rect.x0 + rect.x1) / 2;
+            let center_y = (rect.y0 + rect.y1) / 2;
+            if center_x >= output.width() || center_y >= output.height() {
+                return Err(format!(
+                    "Position ({}, {}) hors de l'image ({}x{})",
+                    center_x, center_y, output.width(), output.height()
+                ));
+            }
+            draw_ring_mut(&mut output, (center_x as i32, center_y as i32), radius, thickness, self.config.mistake_color);
+        }
+
+        Ok(DynamicImage::ImageRgb8(output))
+    }
+
+    /// Génère une image où chaque case vide est teintée selon sa probabilité
+    /// d'être noire (bleu = improbable, rouge = probable)
+    pub fn generate_heatmap_image(
+        &self,
+        input_image: &DynamicImage,
+        grid: &Grid,
+        heat_map: &HeatMap,
+    ) -> Result<DynamicImage, String> {
+        let mut output = input_image.to_rgba8();
+        let alpha = 0.45;
+
+        for row in 0..grid.height() {
+            for col in 0..grid.width() {
+                if grid.get(row, col) != Some(CellState::Empty) {
+                    continue;
+                }
+                let probability = heat_map.get(row, col)
+                    .ok_or_else(|| format!("Case ({}, {}) hors de la carte de chaleur", row, col))?;
+                let color = Self::heat_color(probability);
+
+                let rect = self.cell_rect(row, col);
+                let inset = (self.marker_cell_size(&rect) / 10).max(1);
+                let x0 = rect.x0 + inset;
+                let y0 = rect.y0 + inset;
+                let x1 = rect.x1.saturating_sub(inset).min(output.width());
+                let y1 = rect.y1.saturating_sub(inset).min(output.height());
+
+                for y in y0..y1 {
+                    for x in x0..x1 {
+                        let original = output.get_pixel(x, y);
+                        let blended = Rgba([
+                            (color[0] as f32 * alpha + original[0] as f32 * (1.0 - alpha)) as u8,
+                            (color[1] as f32 * alpha + original[1] as f32 * (1.0 - alpha)) as u8,
+                            (color[2] as f32 * alpha + original[2] as f32 * (1.0 - alpha)) as u8,
+                            255,
+                        ]);
+                        output.put_pixel(x, y, blended);
+                    }
+                }
+            }
+        }
+
+        Ok(DynamicImage::ImageRgba8(output))
+    }
+
+    /// Dégradé bleu (0) → blanc (0.5) → rouge (1)
+    fn heat_color(probability: f64) -> Rgb<u8> {
+        let p = probability.clamp(0.0, 1.0);
+        if p < 0.5 {
+            let t = p * 2.0;
+            Rgb([(255.0 * t) as u8, (255.0 * t) as u8, 255])
+        } else {
+            let t = (1.0 - p) * 2.0;
+            Rgb([255, (255.0 * t) as u8, (255.0 * t) as u8])
+        }
+    }
+
     /// Sauvegarde l'image dans un fichier
     pub fn save_image(image: &DynamicImage, path: &str) -> Result<(), String> {
         image.save(path).map_err(|e| format!("Erreur lors de la sauvegarde de l'image: {}", e))
@@ -187,6 +295,14 @@ impl ImageGenerator {
             ..Default::default()
         }
     }
+
+    /// Crée une configuration reprenant la géométrie de la grille utilisée par le parseur
+    pub fn from_parser(config: &ParserConfig) -> GeneratorConfig {
+        GeneratorConfig {
+            geometry: config.geometry.clone(),
+            ..Self::from_parser_config(config.cell_size, config.margin_top, config.margin_left)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -214,4 +330,52 @@ mod tests {
         assert_eq!(config.margin_top, 60);
         assert_eq!(config.margin_left, 70);
     }
+
+    #[test]
+    fn test_diagnosis_circles_mistakes() {
+        let generator = ImageGenerator::new(ImageGenerator::from_parser_config(20, 0, 0));
+        let input = DynamicImage::ImageRgba8(RgbaImage::from_pixel(40, 20, Rgba([255, 255, 255, 255])));
+        let output = generator.generate_diagnosis_image(&input, &[], &[(0, 1)]).unwrap().to_rgb8();
+
+        let mistake_color = GeneratorConfig::default().mistake_color;
+        assert_eq!(output.get_pixel(30, 1), &mistake_color);
+        assert_eq!(output.get_pixel(10, 1), &Rgb([255, 255, 255]));
+        assert!(generator.generate_diagnosis_image(&input, &[], &[(3, 0)]).is_err());
+    }
+
+    #[test]
+    fn test_markers_follow_detected_geometry() {
+        // La 2e case est décalée par un séparateur épais: le marqueur suit les traits détectés
+        let parser_config = ParserConfig {
+            geometry: Some(GridGeometry::new(vec![0, 20, 44], vec![0, 20]).unwrap()),
+            ..Default::default()
+        };
+        let generator = ImageGenerator::new(ImageGenerator::from_parser(&parser_config));
+        let input = DynamicImage::ImageRgba8(RgbaImage::from_pixel(50, 20, Rgba([255, 255, 255, 255])));
+        let deduction = Deduction { row: 0, col: 1, state: CellState::Filled };
+        let output = generator.generate_output_image(&input, &[deduction]).unwrap().to_rgba8();
+
+        assert_ne!(output.get_pixel(32, 10), &Rgba([255, 255, 255, 255]));
+        assert_eq!(output.get_pixel(10, 10), &Rgba([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn test_heat_map_overlay() {
+        use crate::grid::Constraints;
+
+        let constraints = Constraints::new(2, 1, vec![vec![1]], vec![vec![1], vec![]]).unwrap();
+        let mut grid = Grid::new(2, 1);
+        grid.set(0, 1, CellState::Crossed).unwrap();
+        let heat_map = HeatMap::compute(&grid, &constraints).unwrap();
+
+        let generator = ImageGenerator::new(ImageGenerator::from_parser_config(10, 0, 0));
+        let input = DynamicImage::ImageRgba8(RgbaImage::from_pixel(20, 10, Rgba([255, 255, 255, 255])));
+        let output = generator.generate_heatmap_image(&input, &grid, &heat_map).unwrap().to_rgba8();
+
+        // Case certaine teintée en rouge, case barrée inchangée
+        let tinted = output.get_pixel(5, 5);
+        assert_eq!(tinted[0], 255);
+        assert!(tinted[1] < 255);
+        assert_eq!(output.get_pixel(15, 5), &Rgba([255, 255, 255, 255]));
+    }
 }
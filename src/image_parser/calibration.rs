@@ -0,0 +1,241 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use image::DynamicImage;
+use serde::{Deserialize, Serialize};
+use crate::checker::LineKind;
+use super::{GridGeometry, ParserConfig};
+
+/// Poignée saisie sur l'image pendant l'étalonnage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationHandle {
+    /// Coin haut gauche de la grille
+    TopLeft,
+    /// Coin bas droit de la grille
+    BottomRight,
+    /// Trait intérieur: `Column` pour un trait vertical, `Row` pour un trait horizontal
+    Line(LineKind, usize),
+}
+
+/// Étalonnage manuel de la grille, enregistré à côté de l'image (`grille.png` → `grille.calibration.json`)
+/// et repris par le parseur aux analyses suivantes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Calibration {
+    /// Taille de l'image étalonnée: l'étalonnage ne vaut que pour elle
+    pub image_width: u32,
+    pub image_height: u32,
+    pub geometry: GridGeometry,
+}
+
+impl Calibration {
+    /// Grille régulière entre deux coins (en pixels de l'image)
+    pub fn from_corners(image: &DynamicImage, top_left: (u32, u32), bottom_right: (u32, u32), width: usize, height: usize) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Err("La grille doit avoir au moins une case".to_string());
+        }
+        let geometry = GridGeometry::new(
+            Self::spread(top_left.0, bottom_right.0, width)?,
+            Self::spread(top_left.1, bottom_right.1, height)?,
+        )?;
+        Ok(Self { image_width: image.width(), image_height: image.height(), geometry })
+    }
+
+    /// Reprend une géométrie existante (détectée automatiquement, par exemple)
+    pub fn from_geometry(image: &DynamicImage, geometry: GridGeometry) -> Self {
+        Self { image_width: image.width(), image_height: image.height(), geometry }
+    }
+
+    /// Vrai si l'étalonnage a été fait sur une image de cette taille, pour une grille de ces dimensions
+    pub fn matches(&self, image: &DynamicImage, width: usize, height: usize) -> bool {
+        (self.image_width, self.image_height) == (image.width(), image.height())
+            && self.geometry.width() == width
+            && self.geometry.height() == height
+    }
+
+    /// Configuration du parseur qui lit les cases aux positions étalonnées
+    pub fn parser_config(&self) -> ParserConfig {
+        ParserConfig {
+            cell_size: self.geometry.average_cell_size(),
+            margin_left: self.geometry.columns[0],
+            margin_top: self.geometry.rows[0],
+            geometry: Some(self.geometry.clone()),
+            ..Default::default()
+        }
+    }
+
+    /// Poignée la plus proche du point (x, y), à moins de `tolerance` pixels; les coins d'abord
+    pub fn handle_at(&self, x: f32, y: f32, tolerance: f32) -> Option<CalibrationHandle> {
+        let area = self.geometry.grid_area();
+        let near = |a: f32, b: u32| (a - b as f32).abs() <= tolerance;
+        if near(x, area.x0) && near(y, area.y0) {
+            return Some(CalibrationHandle::TopLeft);
+        }
+        if near(x, area.x1) && near(y, area.y1) {
+            return Some(CalibrationHandle::BottomRight);
+        }
+
+        let inside = |a: f32, start: u32, end: u32| a >= start as f32 - tolerance && a <= end as f32 + tolerance;
+        let nearest = |lines: &[u32], value: f32| {
+            (1..lines.len() - 1)
+                .map(|index| (index, (value - lines[index] as f32).abs()))
+                .filter(|&(_, distance)| distance <= tolerance)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+        };
+        let column = nearest(&self.geometry.columns, x).filter(|_| inside(y, area.y0, area.y1));
+        let row = nearest(&self.geometry.rows, y).filter(|_| inside(x, area.x0, area.x1));
+        match (column, row) {
+            (Some(column), Some(row)) if row.1 < column.1 => Some(CalibrationHandle::Line(LineKind::Row, row.0)),
+            (Some(column), _) => Some(CalibrationHandle::Line(LineKind::Column, column.0)),
+            (None, Some(row)) => Some(CalibrationHandle::Line(LineKind::Row, row.0)),
+            (None, None) => None,
+        }
+    }
+
+    /// Déplace une poignée au point (x, y). Déplacer un coin étire la grille en gardant
+    /// les écarts relatifs des traits; un trait reste entre ses voisins.
+    pub fn drag(&mut self, handle: CalibrationHandle, x: u32, y: u32) {
+        let x = x.min(self.image_width.saturating_sub(1));
+        let y = y.min(self.image_height.saturating_sub(1));
+        let area = self.geometry.grid_area();
+        match handle {
+            CalibrationHandle::TopLeft => {
+                Self::rescale(&mut self.geometry.columns, x, area.x1);
+                Self::rescale(&mut self.geometry.rows, y, area.y1);
+            }
+            CalibrationHandle::BottomRight => {
+                Self::rescale(&mut self.geometry.columns, area.x0, x);
+                Self::rescale(&mut self.geometry.rows, area.y0, y);
+            }
+            CalibrationHandle::Line(kind, index) => {
+                let (lines, position) = match kind {
+                    LineKind::Column => (&mut self.geometry.columns, x),
+                    LineKind::Row => (&mut self.geometry.rows, y),
+                };
+                if index > 0 && index + 1 < lines.len() {
+                    lines[index] = position.clamp(lines[index - 1] + 1, lines[index + 1] - 1);
+                }
+            }
+        }
+    }
+
+    /// Chemin de l'étalonnage d'une image
+    pub fn path_for(image_path: &Path) -> PathBuf {
+        image_path.with_extension("calibration.json")
+    }
+
+    /// Étalonnage enregistré pour l'image, s'il y en a un
+    pub fn load_for(image_path: &Path) -> Result<Option<Self>, String> {
+        let path = Self::path_for(image_path);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Erreur lors de la lecture de l'étalonnage: {}", e))?;
+        let calibration: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("Étalonnage invalide ({}): {}", path.display(), e))?;
+        // Les positions des traits sont revérifiées comme à la création
+        GridGeometry::new(calibration.geometry.columns.clone(), calibration.geometry.rows.clone())
+            .map_err(|e| format!("Étalonnage invalide ({}): {}", path.display(), e))?;
+        Ok(Some(calibration))
+    }
+
+    pub fn save_for(&self, image_path: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Erreur de sérialisation: {}", e))?;
+        fs::write(Self::path_for(image_path), content)
+            .map_err(|e| format!("Erreur lors de l'enregistrement de l'étalonnage: {}", e))
+    }
+
+    /// Supprime l'étalonnage enregistré (aucune erreur s'il n'y en a pas)
+    pub fn remove_for(image_path: &Path) -> Result<(), String> {
+        match fs::remove_file(Self::path_for(image_path)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Erreur lors de la suppression de l'étalonnage: {}", e))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// `count + 1` traits régulièrement espacés de `start` à `end`
+    fn spread(start: u32, end: u32, count: usize) -> Result<Vec<u32>, String> {
+        if end < start + count as u32 {
+            return Err("Les coins sont trop proches pour le nombre de cases".to_string());
+        }
+        let step = (end - start) as f32 / count as f32;
+        Ok((0..=count).map(|index| start + (index as f32 * step).round() as u32).collect())
+    }
+
+    /// Étire les traits entre deux nouvelles extrémités en gardant leurs écarts relatifs
+    fn rescale(lines: &mut [u32], start: u32, end: u32) {
+        let count = lines.len() - 1;
+        // Au moins un pixel par case, sans dépasser le coin opposé
+        if end < start + count as u32 {
+            return;
+        }
+        let (old_start, old_end) = (lines[0] as f32, lines[count] as f32);
+        let ratio = (end - start) as f32 / (old_end - old_start);
+        for line in lines.iter_mut() {
+            *line = start + ((*line as f32 - old_start) * ratio).round() as u32;
+        }
+        // L'arrondi peut rapprocher deux traits: les garder strictement croissants
+        for index in 1..=count {
+            let minimum = lines[index - 1] + 1;
+            let maximum = end - (count - index) as u32;
+            lines[index] = lines[index].clamp(minimum, maximum);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbaImage;
+
+    fn image() -> DynamicImage {
+        DynamicImage::ImageRgba8(RgbaImage::new(200, 150))
+    }
+
+    #[test]
+    fn test_corners_and_lines_can_be_dragged() {
+        let mut calibration = Calibration::from_corners(&image(), (10, 20), (110, 70), 4, 2).unwrap();
+        assert_eq!(calibration.geometry.columns, vec![10, 35, 60, 85, 110]);
+        assert_eq!(calibration.geometry.rows, vec![20, 45, 70]);
+
+        // Un trait: saisi près de sa position, bloqué par ses voisins
+        assert_eq!(calibration.handle_at(62.0, 40.0, 4.0), Some(CalibrationHandle::Line(LineKind::Column, 2)));
+        calibration.drag(CalibrationHandle::Line(LineKind::Column, 2), 70, 40);
+        calibration.drag(CalibrationHandle::Line(LineKind::Column, 1), 100, 40);
+        assert_eq!(calibration.geometry.columns, vec![10, 69, 70, 85, 110]);
+
+        // Un coin: la grille s'étire (jusqu'au bord de l'image), les écarts relatifs sont conservés
+        calibration.drag(CalibrationHandle::Line(LineKind::Column, 1), 35, 0);
+        assert_eq!(calibration.handle_at(108.0, 72.0, 4.0), Some(CalibrationHandle::BottomRight));
+        calibration.drag(CalibrationHandle::BottomRight, 210, 120);
+        assert_eq!(calibration.geometry.columns, vec![10, 57, 123, 152, 199]);
+        assert_eq!(calibration.geometry.rows, vec![20, 70, 120]);
+        assert_eq!(calibration.handle_at(150.0, 140.0, 4.0), None);
+
+        let config = calibration.parser_config();
+        assert_eq!((config.margin_left, config.margin_top), (10, 20));
+        assert!(Calibration::from_corners(&image(), (10, 20), (12, 70), 4, 2).is_err());
+    }
+
+    #[test]
+    fn test_saved_next_to_image() {
+        let directory = std::env::temp_dir().join(format!("nonogram-calibration-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let image_path = directory.join("grille.png");
+        assert_eq!(Calibration::load_for(&image_path), Ok(None));
+
+        let calibration = Calibration::from_corners(&image(), (0, 0), (100, 100), 5, 5).unwrap();
+        calibration.save_for(&image_path).unwrap();
+        assert!(directory.join("grille.calibration.json").exists());
+        let loaded = Calibration::load_for(&image_path).unwrap().unwrap();
+        assert_eq!(loaded, calibration);
+        assert!(loaded.matches(&image(), 5, 5));
+        assert!(!loaded.matches(&image(), 5, 6));
+
+        Calibration::remove_for(&image_path).unwrap();
+        assert_eq!(Calibration::load_for(&image_path), Ok(None));
+        let _ = fs::remove_dir_all(directory);
+    }
+}
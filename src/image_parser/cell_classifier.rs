@@ -0,0 +1,364 @@
+use image::{DynamicImage, GrayImage, RgbaImage};
+use crate::grid::CellState;
+
+/// Configuration du classifieur de cases
+#[derive(Debug, Clone)]
+pub struct ClassifierConfig {
+    /// Proportion du bord de chaque case ignorée (traits de la grille)
+    pub interior_margin_ratio: f32,
+    /// Seuil calculé sur le voisinage de chaque case plutôt que sur toute la grille
+    pub use_local_threshold: bool,
+    /// Écart minimal de luminosité pour qu'un voisinage soit jugé contrasté
+    pub min_contrast: u8,
+    /// Taux d'encre au-delà duquel une case est noire
+    pub fill_coverage: f32,
+    /// Taux d'encre en dessous duquel une case est vierge
+    pub mark_coverage: f32,
+}
+
+impl Default for ClassifierConfig {
+    fn default() -> Self {
+        Self {
+            interior_margin_ratio: 0.15,
+            use_local_threshold: true,
+            min_contrast: 40,
+            fill_coverage: 0.55,
+            mark_coverage: 0.04,
+        }
+    }
+}
+
+/// Forme reconnue dans une case
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellMark {
+    /// Aucune encre
+    Blank,
+    /// Case noircie
+    Solid,
+    /// Croix en X
+    Cross,
+    /// Point central
+    Dot,
+    /// Trace non reconnue
+    Unknown,
+}
+
+/// Résultat de la classification d'une case
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellClassification {
+    pub state: CellState,
+    pub mark: CellMark,
+    /// Confiance entre 0 et 1
+    pub confidence: f32,
+    /// Proportion de pixels d'encre dans l'intérieur de la case
+    pub coverage: f32,
+}
+
+/// Rectangle d'une case dans l'image (bornes exclusives à droite et en bas)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellRect {
+    pub x0: u32,
+    pub y0: u32,
+    pub x1: u32,
+    pub y1: u32,
+}
+
+impl CellRect {
+    pub fn width(&self) -> u32 {
+        self.x1.saturating_sub(self.x0)
+    }
+
+    pub fn height(&self) -> u32 {
+        self.y1.saturating_sub(self.y0)
+    }
+
+    /// Rectangle réduit d'une proportion de sa taille de chaque côté
+    fn shrink(&self, ratio: f32) -> CellRect {
+        let dx = (self.width() as f32 * ratio).round() as u32;
+        let dy = (self.height() as f32 * ratio).round() as u32;
+        CellRect {
+            x0: self.x0 + dx,
+            y0: self.y0 + dy,
+            x1: self.x1.saturating_sub(dx).max(self.x0 + dx + 1),
+            y1: self.y1.saturating_sub(dy).max(self.y0 + dy + 1),
+        }
+    }
+
+    /// Rectangle agrandi d'une taille de case de chaque côté, borné à l'image
+    fn neighbourhood(&self, width: u32, height: u32) -> CellRect {
+        CellRect {
+            x0: self.x0.saturating_sub(self.width()),
+            y0: self.y0.saturating_sub(self.height()),
+            x1: (self.x1 + self.width()).min(width),
+            y1: (self.y1 + self.height()).min(height),
+        }
+    }
+}
+
+/// Classifie les cases à partir de seuils adaptatifs (Otsu) et de la forme de l'encre
+pub struct CellClassifier {
+    config: ClassifierConfig,
+    gray: GrayImage,
+    color: RgbaImage,
+    global_threshold: u8,
+}
+
+impl CellClassifier {
+    /// Prépare l'image; le seuil global est calculé sur la zone de la grille
+    /// (une zone uniforme est considérée comme du papier vierge)
+    pub fn new(image: &DynamicImage, grid_area: CellRect, config: ClassifierConfig) -> Self {
+        let gray = image.to_luma8();
+        let hist = histogram(&gray, grid_area);
+        let contrast = percentile(&hist, 0.995).saturating_sub(percentile(&hist, 0.005));
+        let global_threshold = if contrast < config.min_contrast { 0 } else { otsu_threshold(&hist) };
+        Self {
+            config,
+            gray,
+            color: image.to_rgba8(),
+            global_threshold,
+        }
+    }
+
+    /// Classifie une case
+    pub fn classify(&self, cell: CellRect) -> CellClassification {
+        let interior = self.clip(cell.shrink(self.config.interior_margin_ratio));
+        let threshold = self.threshold_for(cell);
+        let (w, h) = (interior.width() as usize, interior.height() as usize);
+
+        let mut ink = vec![false; w * h];
+        for y in 0..h {
+            for x in 0..w {
+                let (px, py) = (interior.x0 + x as u32, interior.y0 + y as u32);
+                ink[y * w + x] = self.is_ink(px, py, threshold);
+            }
+        }
+
+        let total = (w * h).max(1) as f32;
+        let coverage = ink.iter().filter(|&&i| i).count() as f32 / total;
+        let fill = self.config.fill_coverage;
+        let blank = self.config.mark_coverage;
+
+        if coverage >= fill {
+            return CellClassification {
+                state: CellState::Filled,
+                mark: CellMark::Solid,
+                confidence: 0.5 + 0.5 * ((coverage - fill) / (1.0 - fill).max(f32::EPSILON)).min(1.0),
+                coverage,
+            };
+        }
+        if coverage < blank {
+            return CellClassification {
+                state: CellState::Empty,
+                mark: CellMark::Blank,
+                confidence: 0.5 + 0.5 * (1.0 - coverage / blank.max(f32::EPSILON)),
+                coverage,
+            };
+        }
+
+        let cross = cross_score(&ink, w, h);
+        let dot = dot_score(&ink, w, h);
+        let (state, mark, confidence) = if cross >= 0.6 && cross >= dot {
+            (CellState::Crossed, CellMark::Cross, cross)
+        } else if dot >= 0.7 && coverage < 0.4 {
+            (CellState::Crossed, CellMark::Dot, dot)
+        } else if coverage >= fill / 2.0 {
+            // Remplissage partiel (hachures, feutre sec)
+            (CellState::Filled, CellMark::Unknown, coverage / fill)
+        } else {
+            (CellState::Empty, CellMark::Unknown, 1.0 - coverage / fill)
+        };
+
+        CellClassification {
+            state,
+            mark,
+            confidence: confidence.clamp(0.0, 1.0) * 0.9,
+            coverage,
+        }
+    }
+
+    /// Seuil d'encre: Otsu sur le voisinage de la case s'il est contrasté, seuil global sinon
+    fn threshold_for(&self, cell: CellRect) -> u8 {
+        if !self.config.use_local_threshold {
+            return self.global_threshold;
+        }
+        let area = cell.neighbourhood(self.gray.width(), self.gray.height());
+        let hist = histogram(&self.gray, area);
+        let (low, high) = (percentile(&hist, 0.005), percentile(&hist, 0.995));
+        if high.saturating_sub(low) < self.config.min_contrast {
+            return self.global_threshold;
+        }
+        otsu_threshold(&hist)
+    }
+
+    /// Un pixel est de l'encre s'il est sombre ou franchement coloré (croix en couleur)
+    fn is_ink(&self, x: u32, y: u32, threshold: u8) -> bool {
+        if self.gray.get_pixel(x, y)[0] <= threshold {
+            return true;
+        }
+        let pixel = self.color.get_pixel(x, y);
+        let (r, g, b) = (pixel[0] as i32, pixel[1] as i32, pixel[2] as i32);
+        (r - g).abs() + (g - b).abs() + (r - b).abs() > 150
+    }
+
+    fn clip(&self, rect: CellRect) -> CellRect {
+        let (width, height) = (self.gray.width(), self.gray.height());
+        let x0 = rect.x0.min(width.saturating_sub(1));
+        let y0 = rect.y0.min(height.saturating_sub(1));
+        CellRect {
+            x0,
+            y0,
+            x1: rect.x1.min(width).max(x0 + 1),
+            y1: rect.y1.min(height).max(y0 + 1),
+        }
+    }
+}
+
+/// Histogramme des niveaux de gris d'une zone
+pub fn histogram(gray: &GrayImage, area: CellRect) -> [u32; 256] {
+    let mut hist = [0u32; 256];
+    for y in area.y0..area.y1.min(gray.height()) {
+        for x in area.x0..area.x1.min(gray.width()) {
+            hist[gray.get_pixel(x, y)[0] as usize] += 1;
+        }
+    }
+    hist
+}
+
+/// Seuil d'Otsu: maximise la variance inter-classes
+pub fn otsu_threshold(hist: &[u32; 256]) -> u8 {
+    let total: u64 = hist.iter().map(|&c| c as u64).sum();
+    if total == 0 {
+        return 128;
+    }
+    let sum: f64 = hist.iter().enumerate().map(|(i, &c)| i as f64 * c as f64).sum();
+
+    let mut sum_background = 0.0;
+    let mut weight_background = 0u64;
+    let mut best = (0.0, 128u8);
+
+    for (level, &count) in hist.iter().enumerate() {
+        weight_background += count as u64;
+        if weight_background == 0 {
+            continue;
+        }
+        let weight_foreground = total - weight_background;
+        if weight_foreground == 0 {
+            break;
+        }
+        sum_background += level as f64 * count as f64;
+        let mean_background = sum_background / weight_background as f64;
+        let mean_foreground = (sum - sum_background) / weight_foreground as f64;
+        let variance = weight_background as f64 * weight_foreground as f64
+            * (mean_background - mean_foreground).powi(2);
+        if variance > best.0 {
+            best = (variance, level as u8);
+        }
+    }
+
+    best.1
+}
+
+/// Niveau de gris sous lequel se trouve la proportion donnée des pixels
+fn percentile(hist: &[u32; 256], fraction: f64) -> u8 {
+    let total: u64 = hist.iter().map(|&c| c as u64).sum();
+    let target = (total as f64 * fraction).ceil() as u64;
+    let mut cumulated = 0u64;
+    for (level, &count) in hist.iter().enumerate() {
+        cumulated += count as u64;
+        if cumulated >= target.max(1) {
+            return level as u8;
+        }
+    }
+    255
+}
+
+/// Proportion des deux diagonales couvertes par l'encre (la plus faible des deux)
+fn cross_score(ink: &[bool], w: usize, h: usize) -> f32 {
+    let samples = w.max(h).max(2);
+    let band = (w.min(h) / 8).max(1) as isize;
+    let near_ink = |x: isize, y: isize| {
+        (-band..=band).any(|d| {
+            let xx = x + d;
+            xx >= 0 && (xx as usize) < w && y >= 0 && (y as usize) < h && ink[y as usize * w + xx as usize]
+        })
+    };
+
+    let mut main = 0;
+    let mut anti = 0;
+    for i in 0..samples {
+        let t = i as f32 / (samples - 1) as f32;
+        let y = (t * (h - 1) as f32).round() as isize;
+        let x = (t * (w - 1) as f32).round() as isize;
+        if near_ink(x, y) {
+            main += 1;
+        }
+        if near_ink((w - 1) as isize - x, y) {
+            anti += 1;
+        }
+    }
+
+    main.min(anti) as f32 / samples as f32
+}
+
+/// Proportion de l'encre concentrée au centre de la case
+fn dot_score(ink: &[bool], w: usize, h: usize) -> f32 {
+    let (cx, cy) = ((w as f32 - 1.0) / 2.0, (h as f32 - 1.0) / 2.0);
+    let radius = w.min(h) as f32 * 0.3;
+    let mut inside = 0;
+    let mut total = 0;
+    for y in 0..h {
+        for x in 0..w {
+            if ink[y * w + x] {
+                total += 1;
+                if (x as f32 - cx).hypot(y as f32 - cy) <= radius {
+                    inside += 1;
+                }
+            }
+        }
+    }
+    if total == 0 { 0.0 } else { inside as f32 / total as f32 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    fn cell_image(draw: impl Fn(u32, u32) -> bool, background: u8) -> DynamicImage {
+        let gray = GrayImage::from_fn(30, 30, |x, y| if draw(x, y) { Luma([20]) } else { Luma([background]) });
+        DynamicImage::ImageLuma8(gray)
+    }
+
+    #[test]
+    fn test_otsu_separates_two_modes() {
+        let mut hist = [0u32; 256];
+        hist[30] = 100;
+        hist[220] = 300;
+        let threshold = otsu_threshold(&hist);
+        assert!((30..220).contains(&threshold));
+    }
+
+    #[test]
+    fn test_classifies_marks_on_dim_background() {
+        let rect = CellRect { x0: 0, y0: 0, x1: 30, y1: 30 };
+        let classify = |image: DynamicImage| {
+            CellClassifier::new(&image, rect, ClassifierConfig::default()).classify(rect)
+        };
+
+        // Fond grisâtre (ombre): le seuil fixe de 128 classerait mal une case claire à 120
+        let blank = classify(cell_image(|_, _| false, 120));
+        assert_eq!(blank.state, CellState::Empty);
+
+        let solid = classify(cell_image(|x, y| (3..27).contains(&x) && (3..27).contains(&y), 120));
+        assert_eq!(solid.mark, CellMark::Solid);
+        assert_eq!(solid.state, CellState::Filled);
+
+        let cross = classify(cell_image(|x, y| (x as i32 - y as i32).abs() <= 1 || (x as i32 + y as i32 - 29).abs() <= 1, 200));
+        assert_eq!(cross.mark, CellMark::Cross);
+        assert_eq!(cross.state, CellState::Crossed);
+
+        let dot = classify(cell_image(|x, y| (x as f32 - 14.5).hypot(y as f32 - 14.5) <= 3.0, 200));
+        assert_eq!(dot.mark, CellMark::Dot);
+        assert!(dot.confidence > 0.5);
+    }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use super::cell_classifier::CellRect;
+
+/// Géométrie de la grille: positions explicites des traits verticaux et horizontaux
+///
+/// Permet des cases non carrées et des traits de séparation plus épais (tous les 5 cases),
+/// sans cumul d'erreur vers le bord opposé comme avec `marge + i * taille`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GridGeometry {
+    /// Abscisses des traits verticaux (largeur + 1 valeurs croissantes)
+    pub columns: Vec<u32>,
+    /// Ordonnées des traits horizontaux (hauteur + 1 valeurs croissantes)
+    pub rows: Vec<u32>,
+}
+
+impl GridGeometry {
+    /// Crée une géométrie à partir des positions des traits
+    pub fn new(columns: Vec<u32>, rows: Vec<u32>) -> Result<Self, String> {
+        if columns.len() < 2 || rows.len() < 2 {
+            return Err("Au moins deux traits sont nécessaires dans chaque direction".to_string());
+        }
+        if columns.windows(2).any(|w| w[1] <= w[0]) || rows.windows(2).any(|w| w[1] <= w[0]) {
+            return Err("Les positions des traits doivent être strictement croissantes".to_string());
+        }
+        Ok(Self { columns, rows })
+    }
+
+    /// Géométrie régulière (cases carrées de même taille)
+    pub fn uniform(cell_size: u32, margin_left: u32, margin_top: u32, width: usize, height: usize) -> Self {
+        Self {
+            columns: (0..=width as u32).map(|i| margin_left + i * cell_size).collect(),
+            rows: (0..=height as u32).map(|i| margin_top + i * cell_size).collect(),
+        }
+    }
+
+    /// Nombre de colonnes de cases
+    pub fn width(&self) -> usize {
+        self.columns.len() - 1
+    }
+
+    /// Nombre de lignes de cases
+    pub fn height(&self) -> usize {
+        self.rows.len() - 1
+    }
+
+    /// Rectangle d'une case (None si hors de la grille)
+    pub fn cell_rect(&self, row: usize, col: usize) -> Option<CellRect> {
+        Some(CellRect {
+            x0: *self.columns.get(col)?,
+            y0: *self.rows.get(row)?,
+            x1: *self.columns.get(col + 1)?,
+            y1: *self.rows.get(row + 1)?,
+        })
+    }
+
+    /// Rectangle englobant toute la grille
+    pub fn grid_area(&self) -> CellRect {
+        CellRect {
+            x0: self.columns[0],
+            y0: self.rows[0],
+            x1: self.columns[self.columns.len() - 1],
+            y1: self.rows[self.rows.len() - 1],
+        }
+    }
+
+    /// Taille moyenne d'une case (pour dimensionner les marqueurs)
+    pub fn average_cell_size(&self) -> u32 {
+        let area = self.grid_area();
+        let width = area.width() as f32 / self.width() as f32;
+        let height = area.height() as f32 / self.height() as f32;
+        ((width + height) / 2.0).round() as u32
+    }
+
+    /// Choisit, parmi des traits détectés, `count + 1` traits consécutifs aux espacements
+    /// les plus réguliers (les traits en trop viennent des indices, titres ou cadres)
+    pub fn select_lines(lines: &[usize], count: usize) -> Option<Vec<u32>> {
+        if lines.len() < count + 1 || count == 0 {
+            return None;
+        }
+
+        (0..=lines.len() - count - 1)
+            .map(|start| &lines[start..=start + count])
+            .map(|window| {
+                let spacings: Vec<f32> = window.windows(2).map(|w| (w[1] - w[0]) as f32).collect();
+                let mean = spacings.iter().sum::<f32>() / spacings.len() as f32;
+                let variance = spacings.iter().map(|s| (s - mean).powi(2)).sum::<f32>() / spacings.len() as f32;
+                (window, variance.sqrt() / mean.max(1.0))
+            })
+            // En cas d'égalité, la dernière fenêtre (la grille suit les indices)
+            .reduce(|best, candidate| if candidate.1 <= best.1 { candidate } else { best })
+            .map(|(window, _)| window.iter().map(|&line| line as u32).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_uniform_cells() {
+        // Séparateur épais après la 2e colonne: la 3e case commence plus loin
+        let geometry = GridGeometry::new(vec![10, 30, 50, 74], vec![5, 25]).unwrap();
+        assert_eq!(geometry.width(), 3);
+        assert_eq!(geometry.height(), 1);
+        assert_eq!(geometry.cell_rect(0, 2), Some(CellRect { x0: 50, y0: 5, x1: 74, y1: 25 }));
+        assert!(geometry.cell_rect(1, 0).is_none());
+        assert!(GridGeometry::new(vec![10, 10], vec![0, 1]).is_err());
+    }
+
+    #[test]
+    fn test_select_lines_skips_clue_area() {
+        // Trait du cadre des indices à 5, puis grille régulière de 3 cases de ~20 px
+        let lines = vec![5, 60, 80, 101, 120];
+        assert_eq!(GridGeometry::select_lines(&lines, 3), Some(vec![60, 80, 101, 120]));
+        assert_eq!(GridGeometry::select_lines(&lines, 5), None);
+    }
+}
@@ -1,5 +1,10 @@
 use image::{DynamicImage, GrayImage};
 use crate::edge_detection::canny;
+use super::geometry::GridGeometry;
+
+/// Distance au bord de l'image en deçà de laquelle une ligne est ignorée
+/// (Canny laisse un cadre noir d'un pixel qui produit de faux contours)
+const BORDER_MARGIN: usize = 3;
 
 /// Détecteur de grille avancé pour l'analyse automatique
 pub struct GridDetector;
@@ -37,17 +42,34 @@ impl GridDetector {
         
         Ok((cell_size, margin_left, margin_top))
     }
+
+    /// Détecte la position de chaque trait de la grille (cases non carrées, séparateurs épais)
+    pub fn detect_geometry(image: &DynamicImage, expected_width: usize, expected_height: usize) -> Result<GridGeometry, String> {
+        let gray = image.to_luma8();
+        let (horizontal_lines, vertical_lines) = Self::detect_grid_lines(&gray)?;
+
+        let columns = GridGeometry::select_lines(&vertical_lines, expected_width).ok_or_else(|| format!(
+            "{} traits verticaux détectés, {} attendus", vertical_lines.len(), expected_width + 1
+        ))?;
+        let rows = GridGeometry::select_lines(&horizontal_lines, expected_height).ok_or_else(|| format!(
+            "{} traits horizontaux détectés, {} attendus", horizontal_lines.len(), expected_height + 1
+        ))?;
+
+        GridGeometry::new(columns, rows)
+    }
     
     /// Détecte les lignes horizontales et verticales de la grille
     fn detect_grid_lines(gray: &GrayImage) -> Result<(Vec<usize>, Vec<usize>), String> {
         // Appliquer la détection de contours Canny
         let edges = canny(gray, 50.0, 100.0);
         
+        let (width, height) = gray.dimensions();
+
         // Détecter les lignes horizontales
-        let horizontal_lines = Self::find_horizontal_lines(&edges);
+        let horizontal_lines = Self::merge_thick_lines(Self::without_border(Self::find_horizontal_lines(&edges), height as usize));
         
         // Détecter les lignes verticales
-        let vertical_lines = Self::find_vertical_lines(&edges);
+        let vertical_lines = Self::merge_thick_lines(Self::without_border(Self::find_vertical_lines(&edges), width as usize));
         
         Ok((horizontal_lines, vertical_lines))
     }
@@ -136,6 +158,33 @@ impl GridDetector {
         filtered
     }
     
+    /// Retire les lignes collées au bord de l'image
+    fn without_border(lines: Vec<usize>, size: usize) -> Vec<usize> {
+        lines.into_iter()
+            .filter(|&pos| pos >= BORDER_MARGIN && pos + BORDER_MARGIN < size)
+            .collect()
+    }
+
+    /// Fusionne les deux bords d'un trait épais (séparateur tous les 5 cases) en un seul trait
+    /// centré: deux lignes distantes de moins de la moitié de l'espacement médian
+    fn merge_thick_lines(lines: Vec<usize>) -> Vec<usize> {
+        let threshold = (Self::calculate_average_spacing(&lines) / 2.0) as usize;
+        let mut merged: Vec<usize> = Vec::new();
+        let mut group_start = None;
+
+        for &pos in &lines {
+            match (merged.last_mut(), group_start) {
+                (Some(last), Some(start)) if pos - start < threshold => *last = (start + pos) / 2,
+                _ => {
+                    merged.push(pos);
+                    group_start = Some(pos);
+                }
+            }
+        }
+
+        merged
+    }
+
     /// Calcule l'espacement moyen entre les lignes
     fn calculate_average_spacing(lines: &[usize]) -> f32 {
         if lines.len() < 2 {
@@ -169,6 +218,13 @@ mod tests {
         assert_eq!(filtered[2], 50);
     }
     
+    #[test]
+    fn test_merge_thick_lines() {
+        // Les deux bords du séparateur épais (96 et 104) deviennent un trait en 100
+        let lines = vec![0, 20, 40, 60, 80, 96, 104, 120, 140];
+        assert_eq!(GridDetector::merge_thick_lines(lines), vec![0, 20, 40, 60, 80, 100, 120, 140]);
+    }
+
     #[test]
     fn test_calculate_average_spacing() {
         let lines = vec![10, 30, 50, 70];
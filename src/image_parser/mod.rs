@@ -1,8 +1,14 @@
 mod grid_detector;
+mod cell_classifier;
+mod geometry;
+pub mod calibration;
 
 use image::{DynamicImage, GenericImageView, Rgba};
 use crate::grid::{Grid, CellState};
 use grid_detector::GridDetector;
+pub use cell_classifier::{CellClassifier, ClassifierConfig, CellClassification, CellRect, histogram, otsu_threshold};
+pub use geometry::GridGeometry;
+pub use calibration::Calibration;
 
 /// Configuration pour le parseur d'image
 #[derive(Debug, Clone)]
@@ -12,8 +18,23 @@ pub struct ParserConfig {
     /// Marge en pixels depuis le bord de l'image jusqu'à la grille
     pub margin_top: u32,
     pub margin_left: u32,
-    /// Seuil pour déterminer si une case est noire (0-255)
+    /// Seuil pour déterminer si une case est noire (0-255), utilisé par le classement par points
     pub black_threshold: u8,
+    /// Classer les cases par seuil adaptatif et forme de l'encre (sinon: cinq points par case)
+    pub adaptive: bool,
+    pub classifier: ClassifierConfig,
+    /// Positions détectées des traits de la grille (sinon: marges + i * taille de case)
+    pub geometry: Option<GridGeometry>,
+}
+
+impl ParserConfig {
+    /// Géométrie de la grille: celle détectée si elle a les bonnes dimensions, sinon régulière
+    pub fn geometry(&self, width: usize, height: usize) -> GridGeometry {
+        match &self.geometry {
+            Some(geometry) if geometry.width() == width && geometry.height() == height => geometry.clone(),
+            _ => GridGeometry::uniform(self.cell_size, self.margin_left, self.margin_top, width, height),
+        }
+    }
 }
 
 impl Default for ParserConfig {
@@ -23,6 +44,9 @@ impl Default for ParserConfig {
             margin_top: 50,
             margin_left: 50,
             black_threshold: 128,
+            adaptive: true,
+            classifier: ClassifierConfig::default(),
+            geometry: None,
         }
     }
 }
@@ -47,9 +71,19 @@ impl ImageParser {
     pub fn parse_image(&self, image: &DynamicImage, width: usize, height: usize) -> Result<Grid, String> {
         let mut grid = Grid::new(width, height);
 
+        if self.config.adaptive {
+            for (row, cells) in self.classify_cells(image, width, height)?.into_iter().enumerate() {
+                for (col, cell) in cells.into_iter().enumerate() {
+                    grid.set(row, col, cell.state)?;
+                }
+            }
+            return Ok(grid);
+        }
+
+        let geometry = self.config.geometry(width, height);
         for row in 0..height {
             for col in 0..width {
-                let cell_state = self.detect_cell_state(image, row, col)?;
+                let cell_state = self.detect_cell_state(image, &geometry, row, col)?;
                 grid.set(row, col, cell_state)?;
             }
         }
@@ -57,11 +91,35 @@ impl ImageParser {
         Ok(grid)
     }
 
+    /// Classe chaque case avec un indice de confiance (seuils adaptatifs, taux d'encre,
+    /// reconnaissance des croix et des points)
+    pub fn classify_cells(&self, image: &DynamicImage, width: usize, height: usize) -> Result<Vec<Vec<CellClassification>>, String> {
+        let geometry = self.config.geometry(width, height);
+        let grid_area = geometry.grid_area();
+        let last = geometry.cell_rect(height.saturating_sub(1), width.saturating_sub(1)).ok_or("Grille vide")?;
+        if (last.x0 + last.x1) / 2 >= image.width() || (last.y0 + last.y1) / 2 >= image.height() {
+            return Err(format!(
+                "Grille ({}x{} cases, {}x{} px) hors de l'image ({}x{})",
+                width, height, grid_area.width(), grid_area.height(), image.width(), image.height()
+            ));
+        }
+
+        let classifier = CellClassifier::new(image, grid_area, self.config.classifier.clone());
+        Ok((0..height)
+            .map(|row| (0..width)
+                .filter_map(|col| geometry.cell_rect(row, col))
+                .map(|rect| classifier.classify(rect))
+                .collect())
+            .collect())
+    }
+
     /// Détecte l'état d'une case spécifique dans l'image
-    fn detect_cell_state(&self, image: &DynamicImage, row: usize, col: usize) -> Result<CellState, String> {
+    fn detect_cell_state(&self, image: &DynamicImage, geometry: &GridGeometry, row: usize, col: usize) -> Result<CellState, String> {
         // Calculer le centre de la case
-        let center_x = self.config.margin_left + (col as u32 * self.config.cell_size) + (self.config.cell_size / 2);
-        let center_y = self.config.margin_top + (row as u32 * self.config.cell_size) + (self.config.cell_size / 2);
+        let rect = geometry.cell_rect(row, col).ok_or_else(|| format!("Case ({}, {}) hors de la grille", row, col))?;
+        let (center_x, center_y) = ((rect.x0 + rect.x1) / 2, (rect.y0 + rect.y1) / 2);
+        let quarter_x = rect.width() / 4;
+        let quarter_y = rect.height() / 4;
 
         // Vérifier que le point est dans l'image
         if center_x >= image.width() || center_y >= image.height() {
@@ -74,10 +132,10 @@ impl ImageParser {
         // Échantillonner plusieurs points dans la case pour plus de robustesse
         let sample_points = vec![
             (center_x, center_y),
-            (center_x - self.config.cell_size / 4, center_y),
-            (center_x + self.config.cell_size / 4, center_y),
-            (center_x, center_y - self.config.cell_size / 4),
-            (center_x, center_y + self.config.cell_size / 4),
+            (center_x - quarter_x, center_y),
+            (center_x + quarter_x, center_y),
+            (center_x, center_y - quarter_y),
+            (center_x, center_y + quarter_y),
         ];
 
         let mut black_count = 0;
@@ -138,16 +196,31 @@ impl ImageParser {
         image::open(path).map_err(|e| format!("Erreur lors du chargement de l'image: {}", e))
     }
 
+    /// Configuration de l'étalonnage enregistré pour l'image, s'il correspond à l'image et à la grille
+    pub fn calibrated_config(image_path: &str, image: &DynamicImage, width: usize, height: usize) -> Result<Option<ParserConfig>, String> {
+        let calibration = Calibration::load_for(std::path::Path::new(image_path))?;
+        Ok(calibration
+            .filter(|calibration| calibration.matches(image, width, height))
+            .map(|calibration| calibration.parser_config()))
+    }
+
     /// Détecte automatiquement les paramètres de la grille
     pub fn auto_detect_config(image: &DynamicImage, width: usize, height: usize) -> Result<ParserConfig, String> {
         // Utiliser le détecteur avancé de grille
         match GridDetector::detect_grid_params(image, width, height) {
             Ok((cell_size, margin_left, margin_top)) => {
+                // Positions exactes des traits quand leur nombre correspond aux dimensions
+                let geometry = GridDetector::detect_geometry(image, width, height).ok();
+                let (margin_left, margin_top) = geometry.as_ref()
+                    .map(|geometry| (geometry.columns[0], geometry.rows[0]))
+                    .unwrap_or((margin_left, margin_top));
+
                 Ok(ParserConfig {
-                    cell_size,
+                    cell_size: geometry.as_ref().map(GridGeometry::average_cell_size).unwrap_or(cell_size),
                     margin_top,
                     margin_left,
-                    black_threshold: 128,
+                    geometry,
+                    ..Default::default()
                 })
             }
             Err(_) => {
@@ -168,7 +241,7 @@ impl ImageParser {
                     cell_size,
                     margin_top,
                     margin_left,
-                    black_threshold: 128,
+                    ..Default::default()
                 })
             }
         }
@@ -186,6 +259,55 @@ mod tests {
         assert_eq!(config.black_threshold, 128);
     }
 
+    #[test]
+    fn test_adaptive_parsing_with_shadow() {
+        use image::{GrayImage, Luma};
+
+        // Grille 4x1 de cases de 20 px, éclairage décroissant vers la gauche:
+        // le papier de gauche est plus sombre que l'encre de droite
+        let image = GrayImage::from_fn(100, 40, |x, y| {
+            let paper = 95 + (x * 14 / 10) as u8;
+            let line = (x >= 10 && (x - 10) % 20 == 0) || y == 10 || y == 30;
+            let filled = (y > 12 && y < 28) && ((12..28).contains(&x) || (72..88).contains(&x));
+            Luma([if line || filled { paper - 90 } else { paper }])
+        });
+        let config = ParserConfig {
+            cell_size: 20,
+            margin_left: 10,
+            margin_top: 10,
+            ..Default::default()
+        };
+
+        let parser = ImageParser::new(config);
+        let grid = parser.parse_image(&DynamicImage::ImageLuma8(image), 4, 1).unwrap();
+        assert_eq!(grid.get_row(0).unwrap(), vec![
+            CellState::Filled, CellState::Empty, CellState::Empty, CellState::Filled,
+        ]);
+    }
+
+    #[test]
+    fn test_parsing_with_thick_separator() {
+        use image::{GrayImage, Luma};
+
+        // Grille 6x1: cases de 20 px, trait épais de 6 px après la 5e case. Avec
+        // marge + i * taille, la dernière case lue serait à cheval sur le séparateur.
+        let columns = vec![10, 30, 50, 70, 90, 110, 136];
+        let image = GrayImage::from_fn(150, 40, |x, y| {
+            let thin = columns.contains(&x) || y == 10 || y == 30;
+            let thick = (110..116).contains(&x) && (10..=30).contains(&y);
+            let filled = (y > 12 && y < 28) && (119..133).contains(&x);
+            Luma([if thin || thick || filled { 0 } else { 255 }])
+        });
+
+        let config = ParserConfig {
+            geometry: Some(GridGeometry::new(columns.clone(), vec![10, 30]).unwrap()),
+            ..Default::default()
+        };
+        let grid = ImageParser::new(config).parse_image(&DynamicImage::ImageLuma8(image), 6, 1).unwrap();
+        assert_eq!(grid.get(0, 5), Some(CellState::Filled));
+        assert_eq!(grid.get(0, 4), Some(CellState::Empty));
+    }
+
     #[test]
     fn test_classify_pixel() {
         let parser = ImageParser::with_default_config();
@@ -0,0 +1,605 @@
+//! Analyse de la mise en page d'un nonogramme: grille, panneaux d'indices et titre
+//! Traits longs → regroupement en grille → panneaux d'indices de part et d'autre
+
+use image::{DynamicImage, GrayImage};
+use crate::image_parser::{histogram, otsu_threshold, CellRect, GridGeometry, ParserConfig};
+
+/// Configuration de l'analyse de mise en page
+#[derive(Debug, Clone)]
+pub struct LayoutConfig {
+    /// Longueur minimale d'un trait, en proportion du plus petit côté de l'image
+    pub min_line_ratio: f32,
+    /// Longueur minimale absolue d'un trait en pixels
+    pub min_line_length: u32,
+    /// Nombre minimal de cases dans chaque direction
+    pub min_cells: usize,
+    /// Espace vide (en cases) au-delà duquel un panneau d'indices s'arrête
+    pub max_panel_gap: f32,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            min_line_ratio: 0.1,
+            min_line_length: 20,
+            min_cells: 2,
+            max_panel_gap: 1.5,
+        }
+    }
+}
+
+/// Côté de la grille où se trouve un panneau d'indices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// Panneau d'indices
+#[derive(Debug, Clone, PartialEq)]
+pub struct CluePanel {
+    pub side: Side,
+    pub area: CellRect,
+}
+
+/// Description structurée d'un nonogramme dans l'image
+#[derive(Debug, Clone)]
+pub struct PuzzleLayout {
+    pub geometry: GridGeometry,
+    /// Indices des lignes (à gauche ou à droite de la grille)
+    pub row_clues: CluePanel,
+    /// Indices des colonnes (au-dessus ou au-dessous de la grille)
+    pub column_clues: CluePanel,
+    /// Texte étranger aux indices au-dessus du nonogramme (titre, numéro)
+    pub title: Option<CellRect>,
+}
+
+impl PuzzleLayout {
+    pub fn width(&self) -> usize {
+        self.geometry.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.geometry.height()
+    }
+
+    /// Zone `(x, y, largeur, hauteur)` des indices d'une ligne, traits de la grille exclus
+    pub fn row_clue_region(&self, row: usize) -> Option<(u32, u32, u32, u32)> {
+        let (top, bottom) = (*self.geometry.rows.get(row)?, *self.geometry.rows.get(row + 1)?);
+        let area = &self.row_clues.area;
+        Some((area.x0, top + 2, area.width(), (bottom - top).saturating_sub(3)))
+    }
+
+    /// Zone `(x, y, largeur, hauteur)` des indices d'une colonne, traits de la grille exclus
+    pub fn column_clue_region(&self, col: usize) -> Option<(u32, u32, u32, u32)> {
+        let (left, right) = (*self.geometry.columns.get(col)?, *self.geometry.columns.get(col + 1)?);
+        let area = &self.column_clues.area;
+        Some((left + 2, area.y0, (right - left).saturating_sub(3), area.height()))
+    }
+
+    /// Rectangle englobant le nonogramme: grille, panneaux d'indices et titre
+    pub fn bounds(&self) -> CellRect {
+        [self.row_clues.area, self.column_clues.area].iter()
+            .chain(self.title.as_ref())
+            .fold(self.geometry.grid_area(), |bounds, area| CellRect {
+                x0: bounds.x0.min(area.x0),
+                y0: bounds.y0.min(area.y0),
+                x1: bounds.x1.max(area.x1),
+                y1: bounds.y1.max(area.y1),
+            })
+    }
+
+    /// Configuration du parseur de cases correspondant à la grille
+    pub fn parser_config(&self) -> ParserConfig {
+        ParserConfig {
+            cell_size: self.geometry.average_cell_size(),
+            margin_left: self.geometry.columns[0],
+            margin_top: self.geometry.rows[0],
+            geometry: Some(self.geometry.clone()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Trait détecté: position du centre, étendue le long du trait, épaisseur
+#[derive(Debug, Clone, Copy)]
+struct Stroke {
+    start: u32,
+    end: u32,
+    first: u32,
+    last: u32,
+}
+
+impl Stroke {
+    fn center(&self) -> u32 {
+        (self.first + self.last) / 2
+    }
+
+    fn thickness(&self) -> u32 {
+        self.last - self.first + 1
+    }
+
+    fn same_extent(&self, other: &Stroke, tolerance: u32) -> bool {
+        self.start.abs_diff(other.start) <= tolerance && self.end.abs_diff(other.end) <= tolerance
+    }
+}
+
+/// Résultat du parcours d'un côté de la grille
+struct PanelScan {
+    panel: Option<CluePanel>,
+    /// Pixels d'encre du panneau
+    ink: u32,
+    /// Bande de texte qui a arrêté le parcours (titre éventuel)
+    stopped: Option<CellRect>,
+}
+
+/// Image binarisée (encre / papier)
+struct InkMap {
+    width: u32,
+    height: u32,
+    dark: Vec<bool>,
+}
+
+impl InkMap {
+    fn new(gray: &GrayImage) -> Self {
+        let (width, height) = gray.dimensions();
+        let threshold = otsu_threshold(&histogram(gray, CellRect { x0: 0, y0: 0, x1: width, y1: height }));
+        let dark = gray.pixels().map(|pixel| pixel[0] <= threshold).collect();
+        Self { width, height, dark }
+    }
+
+    fn at(&self, x: u32, y: u32) -> bool {
+        x < self.width && y < self.height && self.dark[(y * self.width + x) as usize]
+    }
+
+    /// Pixel d'encre en (along, across): `horizontal` lit les lignes de pixels
+    fn oriented(&self, horizontal: bool, along: u32, across: u32) -> bool {
+        if horizontal { self.at(along, across) } else { self.at(across, along) }
+    }
+}
+
+/// Localise la grille, les panneaux d'indices et le titre d'un nonogramme
+pub struct LayoutAnalyzer {
+    config: LayoutConfig,
+}
+
+impl LayoutAnalyzer {
+    pub fn new() -> Self {
+        Self::with_config(LayoutConfig::default())
+    }
+
+    pub fn with_config(config: LayoutConfig) -> Self {
+        Self { config }
+    }
+
+    /// Analyse la mise en page (la plus grande grille de l'image)
+    pub fn analyze(&self, image: &DynamicImage) -> Result<PuzzleLayout, String> {
+        let ink = InkMap::new(&image.to_luma8());
+        let geometry = self.find_grids(&ink)
+            .into_iter()
+            .max_by_key(|geometry| geometry.grid_area().width() * geometry.grid_area().height())
+            .ok_or("Aucune grille détectée dans l'image")?;
+        self.layout_around(&ink, geometry)
+    }
+
+    /// Analyse tous les nonogrammes d'une page, dans l'ordre de lecture (rangées de
+    /// haut en bas, puis de gauche à droite). Les grilles sans indices sont ignorées.
+    pub fn analyze_all(&self, image: &DynamicImage) -> Vec<PuzzleLayout> {
+        let ink = InkMap::new(&image.to_luma8());
+        let mut layouts: Vec<PuzzleLayout> = self.find_grids(&ink)
+            .into_iter()
+            .filter_map(|geometry| self.layout_around(&ink, geometry).ok())
+            .collect();
+
+        // Une rangée regroupe les nonogrammes qui chevauchent verticalement le premier
+        layouts.sort_by_key(|layout| layout.bounds().y0);
+        let mut ordered = Vec::with_capacity(layouts.len());
+        while !layouts.is_empty() {
+            let row_end = layouts[0].bounds().y1;
+            let (mut row, rest): (Vec<PuzzleLayout>, Vec<PuzzleLayout>) = layouts.into_iter()
+                .partition(|layout| layout.bounds().y0 < row_end);
+            row.sort_by_key(|layout| layout.bounds().x0);
+            ordered.extend(row);
+            layouts = rest;
+        }
+        ordered
+    }
+
+    /// Panneaux d'indices et titre autour d'une grille
+    fn layout_around(&self, ink: &InkMap, geometry: GridGeometry) -> Result<PuzzleLayout, String> {
+        let grid = geometry.grid_area();
+        let cell = geometry.average_cell_size().max(1);
+        let max_gap = (self.config.max_panel_gap * cell as f32) as u32;
+
+        // Indices des lignes: à gauche, sinon à droite
+        let left = self.scan_panel(ink, &geometry, Side::Left, max_gap);
+        let right = self.scan_panel(ink, &geometry, Side::Right, max_gap);
+        let row_clues = Self::pick(&left, &right)
+            .ok_or("Aucun indice de ligne trouvé à gauche ou à droite de la grille")?;
+
+        // Indices des colonnes: au-dessus, sinon au-dessous
+        let top = self.scan_panel(ink, &geometry, Side::Top, max_gap);
+        let bottom = self.scan_panel(ink, &geometry, Side::Bottom, max_gap);
+        let column_clues = Self::pick(&top, &bottom)
+            .ok_or("Aucun indice de colonne trouvé au-dessus ou au-dessous de la grille")?;
+
+        // Le titre est le premier texte au-dessus qui n'est pas un indice
+        let title = top.stopped.filter(|area| area.y1 <= grid.y0);
+
+        Ok(PuzzleLayout { geometry, row_clues, column_clues, title })
+    }
+
+    /// Garde le panneau le plus fourni des deux côtés
+    fn pick(first: &PanelScan, second: &PanelScan) -> Option<CluePanel> {
+        match (&first.panel, &second.panel) {
+            (Some(_), Some(b)) if second.ink > first.ink => Some(b.clone()),
+            (Some(a), _) => Some(a.clone()),
+            (None, b) => b.clone(),
+        }
+    }
+
+    /// Parcourt les bandes de texte depuis la grille vers l'extérieur d'un côté donné.
+    ///
+    /// Une bande d'indices reste entre les traits prolongés de la grille; la première bande
+    /// qui déborde ou chevauche un trait (titre, texte courant) arrête le panneau, de même
+    /// qu'un espace vide de plus de `max_gap` pixels.
+    fn scan_panel(&self, ink: &InkMap, geometry: &GridGeometry, side: Side, max_gap: u32) -> PanelScan {
+        let grid = geometry.grid_area();
+        // Le long du panneau: ordonnées pour les indices de lignes, abscisses pour les colonnes
+        let (lines, span_start, span_end) = match side {
+            Side::Left | Side::Right => (&geometry.rows, grid.y0, grid.y1),
+            Side::Top | Side::Bottom => (&geometry.columns, grid.x0, grid.x1),
+        };
+        let vertical_scan = matches!(side, Side::Top | Side::Bottom);
+        let limit = if vertical_scan { ink.height } else { ink.width };
+        let margin = 3;
+
+        // Positions successives en s'éloignant de la grille
+        let outward: Vec<u32> = match side {
+            Side::Left => (0..grid.x0.saturating_sub(margin)).rev().collect(),
+            Side::Top => (0..grid.y0.saturating_sub(margin)).rev().collect(),
+            Side::Right => (grid.x1 + margin..limit).collect(),
+            Side::Bottom => (grid.y1 + margin..limit).collect(),
+        };
+
+        let slice_has_ink = |position: u32, from: u32, to: u32| {
+            (from..to).any(|along| ink.oriented(vertical_scan, along, position))
+        };
+        // Encre sur un trait intérieur prolongé, ou débordant de la grille (titre au-dessus
+        // ou au-dessous; à gauche et à droite, les indices des colonnes ne débordent pas)
+        let overflow = span_start.saturating_sub(2)..span_end + 2;
+        let crosses = |position: u32| {
+            let outside = vertical_scan && (
+                slice_has_ink(position, span_start.saturating_sub(max_gap), span_start.saturating_sub(2))
+                    || slice_has_ink(position, span_end + 2, (span_end + max_gap).min(ink.width))
+            );
+            let on_line = lines[1..lines.len() - 1].iter().any(|&line| ink.oriented(vertical_scan, line, position));
+            outside || on_line
+        };
+
+        let mut extent: Option<(u32, u32)> = None;
+        let mut ink_count = 0;
+        let mut blank = 0;
+        let mut band: Vec<u32> = Vec::new();
+        let mut stopped_band: Option<Vec<u32>> = None;
+
+        let flush = |band: &mut Vec<u32>, extent: &mut Option<(u32, u32)>, ink_count: &mut u32| -> bool {
+            if band.is_empty() {
+                return true;
+            }
+            if band.iter().any(|&position| crosses(position)) {
+                return false;
+            }
+            for &position in band.iter() {
+                *ink_count += (overflow.clone()).filter(|&along| ink.oriented(vertical_scan, along, position)).count() as u32;
+                *extent = Some(match *extent {
+                    Some((lo, hi)) => (lo.min(position), hi.max(position)),
+                    None => (position, position),
+                });
+            }
+            band.clear();
+            true
+        };
+
+        for position in outward {
+            if slice_has_ink(position, overflow.start, overflow.end) || crosses(position) {
+                band.push(position);
+                blank = 0;
+                continue;
+            }
+            if !band.is_empty() && !flush(&mut band, &mut extent, &mut ink_count) {
+                stopped_band = Some(std::mem::take(&mut band));
+                break;
+            }
+            blank += 1;
+            if blank > max_gap && (extent.is_some() || blank > 3 * max_gap) {
+                break;
+            }
+        }
+        if !band.is_empty() && !flush(&mut band, &mut extent, &mut ink_count) {
+            stopped_band = Some(band);
+        }
+
+        let panel = extent.map(|(lo, hi)| CluePanel {
+            side,
+            area: match side {
+                Side::Left | Side::Right => CellRect { x0: lo, y0: grid.y0, x1: hi + 1, y1: grid.y1 },
+                Side::Top | Side::Bottom => CellRect { x0: grid.x0, y0: lo, x1: grid.x1, y1: hi + 1 },
+            },
+        });
+        PanelScan {
+            panel,
+            ink: ink_count,
+            stopped: stopped_band.and_then(|band| Self::band_bounds(ink, &band, vertical_scan)),
+        }
+    }
+
+    /// Boîte englobante de l'encre d'une bande de texte
+    fn band_bounds(ink: &InkMap, band: &[u32], vertical_scan: bool) -> Option<CellRect> {
+        let (lo, hi) = (*band.iter().min()?, *band.iter().max()?);
+        let along_limit = if vertical_scan { ink.width } else { ink.height };
+        let marked: Vec<u32> = (0..along_limit)
+            .filter(|&along| (lo..=hi).any(|position| ink.oriented(vertical_scan, along, position)))
+            .collect();
+        let (first, last) = (*marked.first()?, *marked.last()?);
+        Some(if vertical_scan {
+            CellRect { x0: first, y0: lo, x1: last + 1, y1: hi + 1 }
+        } else {
+            CellRect { x0: lo, y0: first, x1: hi + 1, y1: last + 1 }
+        })
+    }
+
+    /// Toutes les grilles de l'image: familles de traits horizontaux de même étendue,
+    /// fermées par des traits verticaux couvrant la même hauteur
+    fn find_grids(&self, ink: &InkMap) -> Vec<GridGeometry> {
+        let min_length = ((ink.width.min(ink.height) as f32 * self.config.min_line_ratio) as u32)
+            .max(self.config.min_line_length);
+        let horizontal = Self::strokes(ink, true, min_length);
+        let vertical = Self::strokes(ink, false, min_length);
+
+        let mut grids = Vec::new();
+        for family in Self::families(&horizontal) {
+            let (start, end) = (family[0].start, family[0].end);
+            let tolerance = Self::tolerance(end - start);
+            let rows = Self::positions(&family);
+            let (top, bottom) = (rows[0], rows[rows.len() - 1]);
+
+            let columns_strokes: Vec<Stroke> = vertical.iter()
+                .filter(|stroke| stroke.center() + tolerance >= start && stroke.center() <= end + tolerance)
+                .filter(|stroke| stroke.start.abs_diff(top) <= tolerance && stroke.end.abs_diff(bottom) <= tolerance)
+                .copied()
+                .collect();
+            if columns_strokes.len() < 2 {
+                continue;
+            }
+            let columns = Self::positions(&columns_strokes);
+            if columns.len() < self.config.min_cells + 1 || rows.len() < self.config.min_cells + 1 {
+                continue;
+            }
+            if let Ok(geometry) = GridGeometry::new(columns, rows) {
+                grids.push(geometry);
+            }
+        }
+        grids
+    }
+
+    /// Traits de longueur suffisante, regroupés sur des lignes de pixels consécutives de même étendue
+    fn strokes(ink: &InkMap, horizontal: bool, min_length: u32) -> Vec<Stroke> {
+        let (along_limit, across_limit) = if horizontal { (ink.width, ink.height) } else { (ink.height, ink.width) };
+        let mut strokes: Vec<Stroke> = Vec::new();
+
+        for across in 0..across_limit {
+            let mut run_start = None;
+            let mut last_dark = 0;
+            for along in 0..=along_limit {
+                let dark = along < along_limit && ink.oriented(horizontal, along, across);
+                if dark {
+                    if run_start.is_none() {
+                        run_start = Some(along);
+                    }
+                    last_dark = along;
+                } else if let Some(start) = run_start {
+                    // Tolère un pixel manquant dans un trait
+                    if along < along_limit && along == last_dark + 1 && ink.oriented(horizontal, along + 1, across) {
+                        continue;
+                    }
+                    if last_dark + 1 - start >= min_length {
+                        let run = Stroke { start, end: last_dark, first: across, last: across };
+                        let tolerance = Self::tolerance(run.end - run.start);
+                        match strokes.iter_mut().rev().find(|stroke| stroke.last + 1 == across && stroke.same_extent(&run, tolerance)) {
+                            Some(stroke) => stroke.last = across,
+                            None => strokes.push(run),
+                        }
+                    }
+                    run_start = None;
+                }
+            }
+        }
+        strokes
+    }
+
+    /// Regroupe les traits parallèles de même étendue (au moins trois). Des grilles
+    /// alignées l'une sous l'autre partagent leur étendue: une famille est coupée là où
+    /// l'espace entre deux traits dépasse le double de l'espace médian.
+    fn families(strokes: &[Stroke]) -> Vec<Vec<Stroke>> {
+        let mut aligned: Vec<Vec<Stroke>> = Vec::new();
+        for stroke in strokes {
+            let tolerance = Self::tolerance(stroke.end - stroke.start);
+            match aligned.iter_mut().find(|family| family[0].same_extent(stroke, tolerance)) {
+                Some(family) => family.push(*stroke),
+                None => aligned.push(vec![*stroke]),
+            }
+        }
+
+        let mut families = Vec::new();
+        for family in aligned {
+            let gaps: Vec<u32> = family.windows(2).map(|w| w[1].first.saturating_sub(w[0].last)).collect();
+            let mut sorted = gaps.clone();
+            sorted.sort_unstable();
+            let limit = 2 * sorted.get(sorted.len() / 2).copied().unwrap_or(0).max(1);
+
+            let mut current = vec![family[0]];
+            for (stroke, &gap) in family[1..].iter().zip(&gaps) {
+                if gap > limit {
+                    families.push(std::mem::take(&mut current));
+                }
+                current.push(*stroke);
+            }
+            families.push(current);
+        }
+        families.retain(|family| family.len() >= 3);
+        families
+    }
+
+    /// Positions des traits d'une famille: une bande épaisse (rangée de cases noires collée
+    /// à ses traits) donne ses deux bords, et les traits manquants sont interpolés
+    fn positions(family: &[Stroke]) -> Vec<u32> {
+        let mut thicknesses: Vec<u32> = family.iter().map(Stroke::thickness).collect();
+        thicknesses.sort_unstable();
+        let thickness = thicknesses[thicknesses.len() / 2];
+
+        let mut positions: Vec<u32> = Vec::new();
+        for stroke in family {
+            if stroke.thickness() > 3 * thickness + 2 {
+                positions.push(stroke.first + thickness / 2);
+                positions.push(stroke.last - thickness / 2);
+            } else {
+                positions.push(stroke.center());
+            }
+        }
+        positions.sort_unstable();
+        positions.dedup();
+
+        let mut spacings: Vec<u32> = positions.windows(2).map(|w| w[1] - w[0]).collect();
+        spacings.sort_unstable();
+        let Some(&spacing) = spacings.get(spacings.len() / 2) else {
+            return positions;
+        };
+
+        let mut filled = vec![positions[0]];
+        for window in positions.windows(2) {
+            let gap = window[1] - window[0];
+            let missing = ((gap as f32 / spacing.max(1) as f32).round() as u32).max(1);
+            for k in 1..missing {
+                filled.push(window[0] + gap * k / missing);
+            }
+            filled.push(window[1]);
+        }
+        filled
+    }
+
+    /// Tolérance d'alignement des extrémités d'un trait
+    fn tolerance(length: u32) -> u32 {
+        (length / 30).max(4)
+    }
+}
+
+impl Default for LayoutAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    /// Grille 4x3 de cases de 20 px en (x0, y0), traits de 2 px
+    fn draw_grid(image: &mut GrayImage, x0: u32, y0: u32) {
+        for i in 0..=4 {
+            for y in y0..=y0 + 60 {
+                for t in 0..2 {
+                    image.put_pixel(x0 + i * 20 + t, y, Luma([0]));
+                }
+            }
+        }
+        for j in 0..=3 {
+            for x in x0..=x0 + 81 {
+                for t in 0..2 {
+                    image.put_pixel(x, y0 + j * 20 + t, Luma([0]));
+                }
+            }
+        }
+    }
+
+    fn fill(image: &mut GrayImage, x0: u32, y0: u32, w: u32, h: u32) {
+        for y in y0..y0 + h {
+            for x in x0..x0 + w {
+                image.put_pixel(x, y, Luma([0]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_clues_on_the_left_and_top_with_title() {
+        let mut image = GrayImage::from_pixel(200, 160, Luma([255]));
+        draw_grid(&mut image, 80, 70);
+        // Titre large au-dessus de tout
+        fill(&mut image, 20, 30, 150, 10);
+        // Indices des colonnes (un par colonne) et des lignes
+        for col in 0..4 {
+            fill(&mut image, 88 + col * 20, 50, 4, 8);
+        }
+        for row in 0..3 {
+            fill(&mut image, 55, 76 + row * 20, 4, 8);
+            fill(&mut image, 65, 76 + row * 20, 4, 8);
+        }
+
+        let layout = LayoutAnalyzer::new().analyze(&DynamicImage::ImageLuma8(image)).unwrap();
+        assert_eq!((layout.width(), layout.height()), (4, 3));
+        assert_eq!(layout.row_clues.side, Side::Left);
+        assert_eq!(layout.column_clues.side, Side::Top);
+        assert!(layout.row_clues.area.x0 <= 55 && layout.row_clues.area.x1 >= 69);
+        assert!(layout.column_clues.area.y0 <= 50 && layout.column_clues.area.y0 >= 40);
+        assert!(layout.title.is_some_and(|title| title.y0 == 30 && title.x0 == 20));
+
+        let (x, y, w, h) = layout.row_clue_region(1).unwrap();
+        assert!(x <= 55 && x + w >= 69 && y > 90 && y + h < 110);
+    }
+
+    #[test]
+    fn test_clues_on_the_right_and_bottom() {
+        let mut image = GrayImage::from_pixel(200, 160, Luma([255]));
+        draw_grid(&mut image, 30, 30);
+        for col in 0..4 {
+            fill(&mut image, 38 + col * 20, 100, 4, 8);
+        }
+        for row in 0..3 {
+            fill(&mut image, 125, 36 + row * 20, 4, 8);
+        }
+
+        let layout = LayoutAnalyzer::new().analyze(&DynamicImage::ImageLuma8(image)).unwrap();
+        assert_eq!(layout.geometry.columns.len(), 5);
+        assert_eq!(layout.row_clues.side, Side::Right);
+        assert_eq!(layout.column_clues.side, Side::Bottom);
+        assert!(layout.title.is_none());
+        assert_eq!(layout.parser_config().margin_left, layout.geometry.columns[0]);
+    }
+
+    #[test]
+    fn test_several_puzzles_in_reading_order() {
+        // Trois nonogrammes: deux côte à côte en haut, un en bas à gauche
+        let mut image = GrayImage::from_pixel(400, 320, Luma([255]));
+        for &(x0, y0) in &[(250, 50), (60, 50), (60, 210)] {
+            draw_grid(&mut image, x0, y0);
+            for col in 0..4 {
+                fill(&mut image, x0 + 8 + col * 20, y0 - 20, 4, 8);
+            }
+            for row in 0..3 {
+                fill(&mut image, x0 - 20, y0 + 6 + row * 20, 4, 8);
+            }
+        }
+
+        let layouts = LayoutAnalyzer::new().analyze_all(&DynamicImage::ImageLuma8(image));
+        let origins: Vec<(u32, u32)> = layouts.iter()
+            .map(|layout| (layout.geometry.columns[0], layout.geometry.rows[0]))
+            .collect();
+        assert_eq!(origins, vec![(60, 50), (250, 50), (60, 210)]);
+        // Les indices des lignes de la grille de droite ne débordent pas sur sa voisine
+        assert!(layouts[1].row_clues.area.x0 > layouts[0].geometry.grid_area().x1);
+        assert!(layouts[1].bounds().x0 <= 230 && layouts[1].bounds().y0 <= 30);
+    }
+}
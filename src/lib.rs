@@ -0,0 +1,18 @@
+//! Solveur de nonogrammes: grille, solveurs, analyse d'image, vérification, jeu, éditeur et API
+//! Bibliothèque partagée par la ligne de commande (`nonogram-solver`) et la GUI (`nonogram-solver-gui`)
+
+pub mod grid;
+pub mod solver;
+pub mod image_parser;
+pub mod image_generator;
+pub mod ocr;
+pub mod drawing;
+pub mod edge_detection;
+pub mod benchmark;
+pub mod checker;
+pub mod perspective;
+pub mod layout;
+pub mod play;
+pub mod animation;
+pub mod editor;
+pub mod server;
@@ -1,33 +1,40 @@
-mod grid;
-mod solver;
-mod image_parser;
-mod image_generator;
-mod ocr;
-mod drawing;
-mod edge_detection;
-
-use clap::Parser;
+use nonogram_solver::{
+    animation, benchmark, checker, grid, image_generator, image_parser, layout, ocr, perspective, server, solver,
+};
+
+use clap::{Parser, Subcommand};
 use anyhow::Result;
 
-use grid::Constraints;
-use solver::{NonogramSolver, AdvancedSolver, AdvancedSolverConfig, UltimateSolver, UltimateSolverConfig};
+use grid::{Grid, Constraints};
+use solver::{NonogramSolver, AdvancedSolver, AdvancedSolverConfig, UltimateSolver, UltimateSolverConfig, PortfolioSolver, PortfolioConfig, SatEncoding, BranchingStrategy, HeatMap, SolveStep, SolveReport};
+#[cfg(feature = "sat")]
+use solver::{SatSolver, SatSolverConfig};
 use image_parser::ImageParser;
 use image_generator::ImageGenerator;
 use ocr::AdvancedConstraintExtractor;
+use benchmark::{BranchingBenchmark, BenchmarkConfig};
+use checker::{Checker, CheckerConfig, Diagnoser};
+use perspective::PerspectiveCorrector;
+use layout::LayoutAnalyzer;
+use animation::SolveAnimation;
+use server::{ApiServer, ServerConfig};
 
 /// Solveur de nonogramme (logimage/hanjie) par déduction logique
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Chemin vers l'image d'entrée du nonogramme
-    #[arg(short, long)]
-    input: String,
+    #[arg(short, long, required = true)]
+    input: Option<String>,
 
-    /// Chemin vers le fichier JSON contenant les contraintes (optionnel si --auto est activé)
+    /// Fichier de contraintes: JSON, XML webpbn (`.xml`, `.pbn`) ou `.non` (optionnel si --auto est activé)
     #[arg(short, long)]
     constraints: Option<String>,
 
-    /// Extraction automatique des contraintes depuis l'image (sans OCR, par détection de grille)
+    /// Extraction automatique des contraintes depuis l'image (lecteur de chiffres intégré, sans Tesseract)
     #[arg(long)]
     auto: bool,
 
@@ -36,8 +43,8 @@ struct Args {
     use_ocr: bool,
 
     /// Chemin vers l'image de sortie avec les déductions marquées
-    #[arg(short, long)]
-    output: String,
+    #[arg(short, long, required = true)]
+    output: Option<String>,
 
     /// Taille d'une case en pixels (optionnel, détection automatique si non spécifié)
     #[arg(long)]
@@ -62,12 +69,404 @@ struct Args {
     /// Utiliser le solveur ultime (toutes les techniques + backtracking + parallélisation)
     #[arg(long)]
     ultimate: bool,
+
+    /// Utiliser le solveur SAT intégré (nécessite --features sat)
+    #[arg(long)]
+    sat: bool,
+
+    /// Lancer plusieurs recherches en parallèle et garder la première solution trouvée
+    #[arg(long)]
+    portfolio: bool,
+
+    /// Vérifier la grille obtenue et l'unicité de la solution avec le solveur SAT (nécessite --features sat)
+    #[arg(long)]
+    sat_check: bool,
+
+    /// Exporter les contraintes au format DIMACS (CNF) vers ce fichier
+    #[arg(long)]
+    export_dimacs: Option<String>,
+
+    /// Enregistrer une carte de chaleur des probabilités de remplissage (indice doux) vers ce fichier
+    #[arg(long)]
+    heatmap: Option<String>,
+
+    /// Enregistrer la résolution étape par étape dans une animation (.gif, ou .png/.apng pour un APNG)
+    #[arg(long)]
+    animation: Option<String>,
+
+    /// Redresser la photo (rotation et perspective) avant l'analyse de la grille
+    #[arg(long)]
+    rectify: bool,
+
+    /// Diagnostiquer les erreurs du joueur: effacer le plus petit ensemble de marques
+    /// incompatibles et les entourer sur l'image de sortie
+    #[arg(long)]
+    diagnose: bool,
+
+    /// Stratégie de branchement du backtracking
+    #[arg(long, value_enum, default_value_t)]
+    branching: BranchingStrategy,
+
+    /// Afficher le rapport de résolution au format JSON (même schéma que l'API `serve`)
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Compare les stratégies de branchement des solveurs par backtracking
+    Benchmark {
+        /// Fichiers de contraintes (JSON, XML webpbn, `.non`) à inclure dans le banc d'essai
+        #[arg(short, long)]
+        constraints: Vec<String>,
+
+        /// Nombre de grilles aléatoires à générer
+        #[arg(long, default_value_t = 5)]
+        random: usize,
+
+        /// Taille des grilles aléatoires
+        #[arg(long, default_value_t = 10)]
+        size: usize,
+
+        /// Graine du générateur aléatoire
+        #[arg(long, default_value_t = 42)]
+        seed: u64,
+
+        /// Nombre maximal d'états explorés par résolution
+        #[arg(long, default_value_t = 2000)]
+        max_states: usize,
+
+        /// Mode verbeux
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
+    /// Vérifie une grille soumise par un joueur
+    Check {
+        /// Fichier de contraintes (JSON, XML webpbn, `.non`)
+        #[arg(short, long)]
+        constraints: String,
+
+        /// Grille au format texte (`#` noire, `x` barrée, `.` vide)
+        #[arg(short, long, conflicts_with = "image", required_unless_present = "image")]
+        grid: Option<String>,
+
+        /// Image de la grille à analyser
+        #[arg(short, long)]
+        image: Option<String>,
+
+        /// Taille d'une case en pixels (détection automatique si non spécifiée)
+        #[arg(long)]
+        cell_size: Option<u32>,
+
+        /// Marge gauche en pixels
+        #[arg(long)]
+        margin_left: Option<u32>,
+
+        /// Marge haute en pixels
+        #[arg(long)]
+        margin_top: Option<u32>,
+
+        /// Redresser la photo (rotation et perspective) avant l'analyse
+        #[arg(long)]
+        rectify: bool,
+
+        /// Soumission terminée: les cases non marquées sont blanches
+        #[arg(long)]
+        complete: bool,
+
+        /// Afficher le rapport au format JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Extrait et résout tous les nonogrammes d'une page (magazine scanné)
+    Page {
+        /// Image de la page
+        #[arg(short, long)]
+        input: String,
+
+        /// Page annotée, ou modèle des noms de fichiers avec --split
+        #[arg(short, long)]
+        output: String,
+
+        /// Une image annotée par nonogramme (`sortie_1.png`, `sortie_2.png`, ...)
+        #[arg(long)]
+        split: bool,
+
+        /// Mode verbeux
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
+    /// Lance l'API JSON du solveur sur 127.0.0.1 (résolution, indice, vérification, difficulté, génération)
+    Serve {
+        /// Port d'écoute
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+
+        /// Taille maximale d'une requête en kilo-octets
+        #[arg(long, default_value_t = 10240)]
+        max_body_kb: usize,
+
+        /// Durée maximale de traitement d'une requête en secondes
+        #[arg(long, default_value_t = 30)]
+        timeout: u64,
+
+        /// Nombre de requêtes traitées en parallèle (par défaut: nombre de cœurs)
+        #[arg(long)]
+        workers: Option<usize>,
+
+        /// Afficher chaque requête reçue
+        #[arg(short, long)]
+        verbose: bool,
+    },
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    match args.command {
+        Some(Command::Benchmark { constraints, random, size, seed, max_states, verbose }) => {
+            run_benchmark(constraints, random, size, seed, max_states, verbose)
+        }
+        Some(Command::Check { constraints, grid, image, cell_size, margin_left, margin_top, rectify, complete, json }) => {
+            let margins = match (cell_size, margin_left, margin_top) {
+                (Some(cell_size), Some(margin_left), Some(margin_top)) => Some((cell_size, margin_left, margin_top)),
+                _ => None,
+            };
+            run_check(&constraints, grid, image, margins, rectify, complete, json)
+        }
+        Some(Command::Page { input, output, split, verbose }) => run_page(&input, &output, split, verbose),
+        Some(Command::Serve { port, max_body_kb, timeout, workers, verbose }) => {
+            let defaults = ServerConfig::default();
+            let config = ServerConfig {
+                port,
+                max_body_bytes: max_body_kb * 1024,
+                timeout: std::time::Duration::from_secs(timeout),
+                workers: workers.unwrap_or(defaults.workers),
+                verbose,
+            };
+            ApiServer::with_config(config).run().map_err(|e| anyhow::anyhow!(e))
+        }
+        None => solve_image(args),
+    }
+}
+
+/// Vérifie une grille de joueur (fichier texte ou image) par rapport aux contraintes
+fn run_check(
+    constraints_file: &str,
+    grid_file: Option<String>,
+    image_file: Option<String>,
+    margins: Option<(u32, u32, u32)>,
+    rectify: bool,
+    complete: bool,
+    json: bool,
+) -> Result<()> {
+    let constraints = Constraints::from_file(constraints_file)
+        .map_err(|e| anyhow::anyhow!("Erreur lors du chargement des contraintes: {}", e))?;
+
+    let grid = match (grid_file, image_file) {
+        (Some(grid_file), _) => Grid::from_text_file(&grid_file)
+            .map_err(|e| anyhow::anyhow!("Erreur lors du chargement de la grille: {}", e))?,
+        (None, Some(image_file)) => {
+            let image = load_image(&image_file, rectify, false)?;
+            let calibrated = calibrated_config(&image_file, &image, &constraints, rectify)?;
+            let parser_config = match (margins, calibrated) {
+                (Some((cell_size, margin_left, margin_top)), _) => image_parser::ParserConfig {
+                    cell_size,
+                    margin_left,
+                    margin_top,
+                    ..Default::default()
+                },
+                (None, Some(config)) => config,
+                (None, None) => ImageParser::auto_detect_config(&image, constraints.width, constraints.height)
+                    .map_err(|e| anyhow::anyhow!("Erreur lors de la détection automatique de la configuration: {}", e))?,
+            };
+            ImageParser::new(parser_config).parse_image(&image, constraints.width, constraints.height)
+                .map_err(|e| anyhow::anyhow!("Erreur lors du parsing de l'image: {}", e))?
+        }
+        (None, None) => return Err(anyhow::anyhow!("Vous devez spécifier --grid ou --image")),
+    };
+
+    let checker = Checker::with_config(CheckerConfig {
+        treat_empty_as_crossed: complete,
+        ..Default::default()
+    });
+    let report = checker.check(&grid, &constraints)
+        .map_err(|e| anyhow::anyhow!("Erreur lors de la vérification: {}", e))?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        report.print();
+    }
+
+    Ok(())
+}
+
+/// Extrait, résout et annote chaque nonogramme d'une page
+fn run_page(input: &str, output: &str, split: bool, verbose: bool) -> Result<()> {
+    let image = load_image(input, false, verbose)?;
+    let layouts = LayoutAnalyzer::new().analyze_all(&image);
+    if layouts.is_empty() {
+        return Err(anyhow::anyhow!("Aucun nonogramme détecté dans la page"));
+    }
+    println!("🗞️  {} nonogramme(s) détecté(s)", layouts.len());
+
+    let mut page = image.clone();
+    let mut solved = 0;
+    for (n, layout) in layouts.iter().enumerate() {
+        let bounds = layout.bounds();
+        println!("🧩 Nonogramme {} ({}x{}, en ({}, {}))", n + 1, layout.width(), layout.height(), bounds.x0, bounds.y0);
+
+        let (deductions, complete) = match solve_layout(&image, layout, verbose) {
+            Ok(result) => result,
+            Err(e) => {
+                println!("⚠️  Nonogramme {} ignoré: {}", n + 1, e);
+                continue;
+            }
+        };
+        if complete {
+            solved += 1;
+        } else {
+            println!("ℹ️  Nonogramme {}: la logique ligne par ligne ne suffit pas à le terminer", n + 1);
+        }
+        if verbose {
+            println!("✓ {} déductions", deductions.len());
+        }
+
+        let generator = ImageGenerator::new(ImageGenerator::from_parser(&layout.parser_config()));
+        if split {
+            let annotated = generator.generate_output_image(&image, &deductions)
+                .map_err(|e| anyhow::anyhow!("Erreur lors de la génération de l'image de sortie: {}", e))?
+                .crop_imm(bounds.x0, bounds.y0, bounds.width(), bounds.height());
+            let path = numbered_path(output, n + 1);
+            ImageGenerator::save_image(&annotated, &path)
+                .map_err(|e| anyhow::anyhow!("Erreur lors de la sauvegarde de l'image: {}", e))?;
+            println!("💾 {}", path);
+        } else {
+            page = generator.generate_output_image(&page, &deductions)
+                .map_err(|e| anyhow::anyhow!("Erreur lors de la génération de l'image de sortie: {}", e))?;
+        }
+    }
+
+    if !split {
+        ImageGenerator::save_image(&page, output)
+            .map_err(|e| anyhow::anyhow!("Erreur lors de la sauvegarde de l'image: {}", e))?;
+    }
+    println!("✅ Terminé! {}/{} nonogramme(s) résolu(s)", solved, layouts.len());
+    if !split {
+        println!("   Page annotée sauvegardée: {}", output);
+    }
+
+    Ok(())
+}
+
+/// Lit les indices d'un nonogramme de la page, puis le résout par déduction logique;
+/// le booléen indique si la grille obtenue est complète et respecte les indices
+fn solve_layout(image: &image::DynamicImage, layout: &layout::PuzzleLayout, verbose: bool) -> Result<(Vec<solver::Deduction>, bool)> {
+    let reconciliation = AdvancedConstraintExtractor::extract_with_recognizer(image, layout);
+    reconciliation.print();
+    let constraints = reconciliation.constraints()
+        .map_err(|e| anyhow::anyhow!("indices illisibles: {}", e))?;
+    if verbose {
+        println!("   - Lignes: {:?}", constraints.rows);
+        println!("   - Colonnes: {:?}", constraints.columns);
+    }
+
+    let mut grid = ImageParser::new(layout.parser_config()).parse_image(image, constraints.width, constraints.height)
+        .map_err(|e| anyhow::anyhow!("erreur lors du parsing de la grille: {}", e))?;
+    let deductions = NonogramSolver::new().solve(&mut grid, &constraints)
+        .map_err(|e| anyhow::anyhow!("erreur lors de la résolution: {}", e))?;
+    let complete = grid.count_empty_cells() == 0 && constraints.is_satisfied_by(&grid);
+    Ok((deductions, complete))
+}
+
+/// `page.png` → `page_2.png`
+fn numbered_path(path: &str, number: usize) -> String {
+    let path = std::path::Path::new(path);
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("nonogramme");
+    let name = match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) => format!("{}_{}.{}", stem, number, extension),
+        None => format!("{}_{}.png", stem, number),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+/// Lance le banc d'essai des stratégies de branchement
+fn run_benchmark(files: Vec<String>, random: usize, size: usize, seed: u64, max_states: usize, verbose: bool) -> Result<()> {
+    let config = BenchmarkConfig {
+        random_puzzles: random,
+        size,
+        seed,
+        max_states,
+        verbose,
+        ..Default::default()
+    };
+    let benchmark = BranchingBenchmark::with_config(config);
+
+    let mut puzzles = Vec::new();
+    for file in files {
+        let constraints = Constraints::from_file(&file)
+            .map_err(|e| anyhow::anyhow!("Erreur lors du chargement des contraintes {}: {}", file, e))?;
+        puzzles.push((file, constraints));
+    }
+    puzzles.extend(benchmark.random_puzzles());
+
+    println!("🏁 Banc d'essai: {} grilles", puzzles.len());
+    let results = benchmark.run(&puzzles);
+    benchmark.print_report(&results);
+
+    Ok(())
+}
+
+/// Charge une image, redressée si demandé (la grille devient alignée sur les axes)
+fn load_image(path: &str, rectify: bool, verbose: bool) -> Result<image::DynamicImage> {
+    let image = ImageParser::load_image(path)
+        .map_err(|e| anyhow::anyhow!("Erreur lors du chargement de l'image: {}", e))?;
+    if !rectify {
+        return Ok(image);
+    }
+
+    let rectification = PerspectiveCorrector::new().rectify(&image)
+        .map_err(|e| anyhow::anyhow!("Erreur lors du redressement de l'image: {}", e))?;
+    if verbose {
+        if rectification.corrected {
+            let [tl, tr, br, bl] = rectification.corners;
+            println!("📐 Image redressée (coins: ({:.0}, {:.0}) ({:.0}, {:.0}) ({:.0}, {:.0}) ({:.0}, {:.0}))",
+                     tl.0, tl.1, tr.0, tr.1, br.0, br.1, bl.0, bl.1);
+        } else {
+            println!("📐 Grille déjà droite, aucun redressement");
+        }
+    }
+    Ok(rectification.image)
+}
+
+/// Étalonnage enregistré pour l'image; ignoré si elle a été redressée, car les traits
+/// ont été placés sur l'image d'origine (le redressement garde la taille de l'image)
+fn calibrated_config(path: &str, image: &image::DynamicImage, constraints: &Constraints, rectify: bool) -> Result<Option<image_parser::ParserConfig>> {
+    if rectify {
+        return Ok(None);
+    }
+    ImageParser::calibrated_config(path, image, constraints.width, constraints.height)
+        .map_err(|e| anyhow::anyhow!(e))
+}
+
+/// Résout le nonogramme d'une image et génère l'image annotée
+fn solve_image(args: Args) -> Result<()> {
+    let input = args.input.clone()
+        .ok_or_else(|| anyhow::anyhow!("Vous devez spécifier --input"))?;
+    let output = args.output.clone()
+        .ok_or_else(|| anyhow::anyhow!("Vous devez spécifier --output"))?;
+    let branching = args.branching;
+    // Extension de l'animation vérifiée avant la résolution plutôt qu'à l'export
+    if let Some(animation_path) = &args.animation {
+        animation::AnimationFormat::from_path(animation_path).map_err(|e| anyhow::anyhow!(e))?;
+    }
+
     // Charger ou extraire les contraintes
+    let mut layout = None;
     let constraints = if args.auto || args.use_ocr {
         if args.verbose {
             if args.use_ocr {
@@ -78,8 +477,7 @@ fn main() -> Result<()> {
         }
         
         // Charger l'image d'abord
-        let input_image = ImageParser::load_image(&args.input)
-            .map_err(|e| anyhow::anyhow!("Erreur lors du chargement de l'image: {}", e))?;
+        let input_image = load_image(&input, args.rectify, false)?;
         
         // Extraire automatiquement les contraintes
         if args.use_ocr {
@@ -93,17 +491,25 @@ fn main() -> Result<()> {
                 return Err(anyhow::anyhow!("La fonctionnalité OCR n'est pas activée. Recompilez avec --features ocr"));
             }
         } else {
-            // Mode auto: détecter la grille sans OCR
-            #[cfg(feature = "ocr")]
-            {
-                AdvancedConstraintExtractor::extract_auto(&input_image)
-                    .map_err(|e| anyhow::anyhow!("Erreur lors de l'extraction automatique: {}. Essayez avec --constraints", e))?
+            // Mode auto: mise en page puis lecteur de chiffres intégré, sans Tesseract
+            let puzzle_layout = LayoutAnalyzer::new().analyze(&input_image)
+                .map_err(|e| anyhow::anyhow!("Erreur lors de l'analyse de la mise en page: {}. Essayez avec --constraints", e))?;
+            if args.verbose {
+                println!("✓ Grille {}x{}, indices des lignes {:?}, des colonnes {:?}{}",
+                         puzzle_layout.width(), puzzle_layout.height(),
+                         puzzle_layout.row_clues.side, puzzle_layout.column_clues.side,
+                         if puzzle_layout.title.is_some() { ", titre ignoré" } else { "" });
             }
-            #[cfg(not(feature = "ocr"))]
-            {
-                AdvancedConstraintExtractor::extract_from_image_heuristic(&input_image)
-                    .map_err(|e| anyhow::anyhow!("Erreur lors de la détection de grille: {}. Utilisez --constraints", e))?
+            let reconciliation = AdvancedConstraintExtractor::extract_with_recognizer(&input_image, &puzzle_layout);
+            reconciliation.print();
+            layout = Some(puzzle_layout);
+            let constraints = reconciliation.constraints()
+                .map_err(|e| anyhow::anyhow!("Indices illisibles: {}. Essayez avec --constraints", e))?;
+            if args.verbose {
+                println!("   - Lignes: {:?}", constraints.rows);
+                println!("   - Colonnes: {:?}", constraints.columns);
             }
+            constraints
         }
     } else {
         let constraints_file = args.constraints
@@ -113,7 +519,7 @@ fn main() -> Result<()> {
             println!("🔍 Chargement des contraintes depuis: {}", constraints_file);
         }
         
-        Constraints::from_json_file(&constraints_file)
+        Constraints::from_file(&constraints_file)
             .map_err(|e| anyhow::anyhow!("Erreur lors du chargement des contraintes: {}", e))?
     };
 
@@ -121,19 +527,33 @@ fn main() -> Result<()> {
         println!("✓ Contraintes chargées: {}x{}", constraints.width, constraints.height);
     }
 
+    if let Some(dimacs_path) = &args.export_dimacs {
+        let encoding = SatEncoding::encode(&constraints, None)
+            .map_err(|e| anyhow::anyhow!("Erreur lors de l'encodage CNF: {}", e))?;
+        std::fs::write(dimacs_path, encoding.cnf.to_dimacs())
+            .map_err(|e| anyhow::anyhow!("Erreur lors de l'export DIMACS: {}", e))?;
+        if args.verbose {
+            println!("✓ CNF exportée: {} ({} variables, {} clauses)",
+                     dimacs_path, encoding.cnf.num_vars, encoding.cnf.clauses.len());
+        }
+    }
+
     // Charger l'image
     if args.verbose {
-        println!("🔍 Chargement de l'image depuis: {}", args.input);
+        println!("🔍 Chargement de l'image depuis: {}", input);
     }
 
-    let input_image = ImageParser::load_image(&args.input)
-        .map_err(|e| anyhow::anyhow!("Erreur lors du chargement de l'image: {}", e))?;
+    let input_image = load_image(&input, args.rectify, args.verbose)?;
 
     if args.verbose {
         println!("✓ Image chargée: {}x{} pixels", input_image.width(), input_image.height());
     }
 
     // Configurer le parseur
+    let calibrated = calibrated_config(&input, &input_image, &constraints, args.rectify)?;
+    if args.rectify && args.verbose && image_parser::Calibration::path_for(std::path::Path::new(&input)).exists() {
+        println!("📐 Étalonnage enregistré ignoré: il a été fait sur l'image d'origine, pas sur l'image redressée");
+    }
     let parser_config = if let (Some(cell_size), Some(margin_left), Some(margin_top)) = 
         (args.cell_size, args.margin_left, args.margin_top) {
         if args.verbose {
@@ -148,6 +568,15 @@ fn main() -> Result<()> {
             margin_top,
             ..Default::default()
         }
+    } else if let Some(config) = calibrated {
+        // Étalonnage fait dans la GUI et enregistré à côté de l'image
+        if args.verbose {
+            println!("📐 Utilisation de l'étalonnage enregistré: {}", image_parser::Calibration::path_for(std::path::Path::new(&input)).display());
+        }
+        config
+    } else if let Some(layout) = &layout {
+        // La mise en page analysée pour les indices donne déjà les traits de la grille
+        layout.parser_config()
     } else {
         if args.verbose {
             println!("🤖 Détection automatique de la configuration...");
@@ -159,6 +588,9 @@ fn main() -> Result<()> {
             println!("   - Taille de case: {} px", config.cell_size);
             println!("   - Marge gauche: {} px", config.margin_left);
             println!("   - Marge haute: {} px", config.margin_top);
+            if let Some(geometry) = &config.geometry {
+                println!("   - Traits détectés: {} verticaux, {} horizontaux", geometry.columns.len(), geometry.rows.len());
+            }
         }
         config
     };
@@ -176,8 +608,84 @@ fn main() -> Result<()> {
         println!("✓ Grille extraite");
     }
 
-    // Choisir le solveur en fonction des options
-    let deductions = if args.ultimate {
+    let mut mistakes = Vec::new();
+    if args.diagnose {
+        if args.verbose {
+            println!("🩺 Recherche des erreurs du joueur...");
+        }
+        let diagnosis = Diagnoser::new().diagnose(&grid, &constraints)
+            .map_err(|e| anyhow::anyhow!("Erreur lors du diagnostic: {}", e))?;
+
+        if diagnosis.mistakes.is_empty() {
+            println!("✓ Aucune erreur détectée dans les cases marquées");
+        } else {
+            println!(
+                "🩺 {} erreur(s) probable(s){}:",
+                diagnosis.mistakes.len(),
+                if diagnosis.minimal { "" } else { " (ensemble non garanti minimal)" }
+            );
+            for cell in &diagnosis.mistakes {
+                println!("   - Ligne {}, colonne {}: marquée {:?}, attendu {:?}", cell.row + 1, cell.col + 1, cell.actual, cell.expected);
+            }
+        }
+
+        grid = diagnosis.cleaned_grid(&grid)
+            .map_err(|e| anyhow::anyhow!("Erreur lors du nettoyage de la grille: {}", e))?;
+        mistakes = diagnosis.positions();
+    }
+
+    if let Some(heatmap_path) = &args.heatmap {
+        let heat_map = HeatMap::compute(&grid, &constraints)
+            .map_err(|e| anyhow::anyhow!("Erreur lors du calcul des probabilités: {}", e))?;
+        let generator = ImageGenerator::new(ImageGenerator::from_parser(&parser_config));
+        let heatmap_image = generator.generate_heatmap_image(&input_image, &grid, &heat_map)
+            .map_err(|e| anyhow::anyhow!("Erreur lors de la génération de la carte de chaleur: {}", e))?;
+        ImageGenerator::save_image(&heatmap_image, heatmap_path)
+            .map_err(|e| anyhow::anyhow!("Erreur lors de la sauvegarde de la carte de chaleur: {}", e))?;
+        println!("🌡️  Carte de chaleur sauvegardée: {}", heatmap_path);
+    }
+
+    // Grille avant résolution, pour rejouer les étapes dans l'animation
+    let start_grid = grid.clone();
+
+    // Choisir le solveur en fonction des options; les solveurs qui détaillent leurs
+    // étapes les fournissent pour l'animation
+    let started = std::time::Instant::now();
+    let (deductions, solver_steps) = if args.sat {
+        #[cfg(feature = "sat")]
+        {
+            let config = SatSolverConfig {
+                verbose: args.verbose,
+                ..Default::default()
+            };
+
+            let mut sat_solver = SatSolver::with_config(config);
+            let deductions = sat_solver.solve(&mut grid, &constraints)
+                .map_err(|e| anyhow::anyhow!("Erreur lors de la résolution SAT: {}", e))?;
+            (deductions, None)
+        }
+        #[cfg(not(feature = "sat"))]
+        {
+            return Err(anyhow::anyhow!("Le solveur SAT n'est pas activé. Recompilez avec --features sat"));
+        }
+    } else if args.portfolio {
+        if args.verbose {
+            println!("🏁 Résolution avec le solveur portfolio...");
+        }
+
+        let config = PortfolioConfig {
+            verbose: args.verbose,
+            ..Default::default()
+        };
+
+        let mut portfolio_solver = PortfolioSolver::with_config(config);
+        let deductions = portfolio_solver.solve(&mut grid, &constraints)
+            .map_err(|e| anyhow::anyhow!("Erreur lors de la résolution portfolio: {}", e))?;
+        if let Some(winner) = portfolio_solver.winner() {
+            println!("🏁 Solution trouvée par la recherche {}", winner.name());
+        }
+        (deductions, None)
+    } else if args.ultimate {
         if args.verbose {
             println!("🌟 Résolution avec le solveur ultime...");
         }
@@ -186,12 +694,14 @@ fn main() -> Result<()> {
             use_parallel: true,
             use_backtracking: true,
             backtracking_depth: 10,
+            branching,
             verbose: args.verbose,
         };
 
         let mut ultimate_solver = UltimateSolver::with_config(config);
-        ultimate_solver.solve(&mut grid, &constraints)
-            .map_err(|e| anyhow::anyhow!("Erreur lors de la résolution: {}", e))?
+        let steps = ultimate_solver.solve_steps(&mut grid, &constraints)
+            .map_err(|e| anyhow::anyhow!("Erreur lors de la résolution: {}", e))?;
+        (SolveStep::deductions_until(&steps, steps.len()), Some(steps))
     } else if args.advanced {
         if args.verbose {
             println!("🚀 Résolution avec le solveur avancé...");
@@ -205,16 +715,18 @@ fn main() -> Result<()> {
         };
 
         let mut advanced_solver = AdvancedSolver::with_config(config);
-        advanced_solver.solve(&mut grid, &constraints)
-            .map_err(|e| anyhow::anyhow!("Erreur lors de la résolution: {}", e))?
+        let steps = advanced_solver.solve_steps(&mut grid, &constraints)
+            .map_err(|e| anyhow::anyhow!("Erreur lors de la résolution: {}", e))?;
+        (SolveStep::deductions_until(&steps, steps.len()), Some(steps))
     } else {
         if args.verbose {
             println!("🧩 Résolution de la grille par déduction logique...");
         }
 
         let mut solver = NonogramSolver::new();
-        let deductions = solver.solve(&mut grid, &constraints)
+        let steps = solver.solve_steps(&mut grid, &constraints)
             .map_err(|e| anyhow::anyhow!("Erreur lors de la résolution: {}", e))?;
+        let deductions = SolveStep::deductions_until(&steps, steps.len());
 
         if args.verbose {
             println!("✓ Résolution terminée: {} déductions trouvées", deductions.len());
@@ -224,10 +736,38 @@ fn main() -> Result<()> {
             println!("   - Cases barrées déduites: {}", crossed_count);
         }
 
-        deductions
+        (deductions, Some(steps))
     };
 
-    if deductions.is_empty() {
+    if args.sat_check {
+        #[cfg(feature = "sat")]
+        {
+            let sat_solver = SatSolver::new();
+            let consistent = sat_solver.cross_check(&grid, &constraints)
+                .map_err(|e| anyhow::anyhow!("Erreur lors de la vérification SAT: {}", e))?;
+            if consistent {
+                println!("✓ Vérification SAT: grille cohérente avec les contraintes");
+            } else {
+                println!("⚠️  Vérification SAT: la grille obtenue ne mène à aucune solution");
+            }
+
+            let blank = Grid::new(constraints.width, constraints.height);
+            match sat_solver.count_solutions(&blank, &constraints, 2)
+                .map_err(|e| anyhow::anyhow!("Erreur lors du test d'unicité SAT: {}", e))?
+            {
+                0 => println!("⚠️  Vérification SAT: les contraintes n'admettent aucune solution"),
+                1 => println!("✓ Vérification SAT: solution unique"),
+                _ => println!("⚠️  Vérification SAT: plusieurs solutions possibles"),
+            }
+        }
+        #[cfg(not(feature = "sat"))]
+        {
+            return Err(anyhow::anyhow!("Le solveur SAT n'est pas activé. Recompilez avec --features sat"));
+        }
+    }
+
+    let solve_duration = started.elapsed();
+    if deductions.is_empty() && !args.json {
         println!("ℹ️  Aucune nouvelle déduction possible avec la logique actuelle.");
         println!("   La grille est soit complète, soit nécessite des techniques avancées.");
     }
@@ -237,24 +777,50 @@ fn main() -> Result<()> {
         println!("🎨 Génération de l'image de sortie...");
     }
 
-    let generator_config = ImageGenerator::from_parser_config(
-        parser_config.cell_size,
-        parser_config.margin_top,
-        parser_config.margin_left,
-    );
+    let generator_config = ImageGenerator::from_parser(&parser_config);
     let generator = ImageGenerator::new(generator_config);
-    let output_image = generator.generate_output_image(&input_image, &deductions)
+    let output_image = generator.generate_diagnosis_image(&input_image, &deductions, &mistakes)
         .map_err(|e| anyhow::anyhow!("Erreur lors de la génération de l'image de sortie: {}", e))?;
 
     // Sauvegarder l'image
     if args.verbose {
-        println!("💾 Sauvegarde de l'image vers: {}", args.output);
+        println!("💾 Sauvegarde de l'image vers: {}", output);
     }
 
-    ImageGenerator::save_image(&output_image, &args.output)
+    ImageGenerator::save_image(&output_image, &output)
         .map_err(|e| anyhow::anyhow!("Erreur lors de la sauvegarde de l'image: {}", e))?;
 
-    println!("✅ Terminé! Image sauvegardée: {}", args.output);
+    if let Some(animation_path) = &args.animation {
+        if args.verbose {
+            println!("🎞️  Génération de l'animation...");
+        }
+        let steps = match solver_steps {
+            Some(steps) => steps,
+            None => SolveStep::trace(&start_grid, &constraints, &grid)
+                .map_err(|e| anyhow::anyhow!("Erreur lors du découpage de la résolution en étapes: {}", e))?,
+        };
+        let animation = SolveAnimation::new();
+        let frames = animation.frames(&input_image, &generator, &steps)
+            .map_err(|e| anyhow::anyhow!("Erreur lors de la génération des images de l'animation: {}", e))?;
+        animation.export(&frames, animation_path)
+            .map_err(|e| anyhow::anyhow!("Erreur lors de la sauvegarde de l'animation: {}", e))?;
+        println!("🎞️  Animation sauvegardée: {} ({} étapes)", animation_path, steps.len());
+    }
+
+    if args.json {
+        let solver = match (args.sat, args.portfolio, args.ultimate, args.advanced) {
+            (true, ..) => "sat",
+            (_, true, ..) => "portfolio",
+            (_, _, true, _) => "ultimate",
+            (_, _, _, true) => "advanced",
+            _ => "basic",
+        };
+        let report = SolveReport::new(solver, &grid, &constraints, deductions, solve_duration);
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("✅ Terminé! Image sauvegardée: {}", output);
     if !deductions.is_empty() {
         println!("   {} cases ont été marquées en rouge", deductions.len());
     }
@@ -1,6 +1,10 @@
-use image::{DynamicImage, GenericImageView, ImageBuffer, Luma};
+use image::{DynamicImage, ImageBuffer, Luma};
+#[cfg(feature = "ocr")]
 use regex::Regex;
 use crate::grid::Constraints;
+use super::digit_recognizer::{ClueReading, DigitRecognizer};
+use super::reconcile::{ClueReconciler, Reconciliation};
+use crate::layout::{LayoutAnalyzer, PuzzleLayout};
 
 #[cfg(feature = "ocr")]
 use tesseract::Tesseract;
@@ -12,62 +16,21 @@ impl AdvancedConstraintExtractor {
     /// Extrait automatiquement les contraintes d'une image de nonogramme
     /// Détecte automatiquement les régions de contraintes et la taille de la grille
     pub fn extract_auto(image: &DynamicImage) -> Result<Constraints, String> {
-        // Étape 1: Détecter la grille
-        let (grid_x, grid_y, cell_size, grid_width, grid_height) = Self::detect_grid(image)?;
+        // Étape 1: Analyser la mise en page (grille, panneaux d'indices, titre)
+        let layout = LayoutAnalyzer::new().analyze(image)?;
         
-        // Étape 2: Détecter les régions de contraintes
-        let row_region = (0, grid_y, grid_x, (grid_height as u32) * cell_size);
-        let col_region = (grid_x, 0, (grid_width as u32) * cell_size, grid_y);
+        // Étape 2: Régions des panneaux d'indices
+        let rows = &layout.row_clues.area;
+        let columns = &layout.column_clues.area;
+        let row_region = (rows.x0, rows.y0, rows.width(), rows.height());
+        let col_region = (columns.x0, columns.y0, columns.width(), columns.height());
         
         // Étape 3: Extraire les contraintes
-        Self::extract_from_regions(image, row_region, col_region, grid_width as usize, grid_height as usize)
-    }
-    
-    /// Détecte automatiquement la grille dans l'image
-    fn detect_grid(image: &DynamicImage) -> Result<(u32, u32, u32, usize, usize), String> {
-        let width = image.width();
-        let height = image.height();
-        
-        // Convertir en niveaux de gris
-        let gray = image.to_luma8();
-        
-        // Détecter les lignes horizontales et verticales
-        let horizontal_lines = Self::detect_lines(&gray, true);
-        let vertical_lines = Self::detect_lines(&gray, false);
-        
-        if horizontal_lines.len() < 2 || vertical_lines.len() < 2 {
-            return Err("Impossible de détecter la grille".to_string());
-        }
-        
-        // Calculer la taille des cellules
-        let cell_size_h = if horizontal_lines.len() > 1 {
-            (horizontal_lines[1] - horizontal_lines[0]) as u32
-        } else {
-            return Err("Pas assez de lignes horizontales détectées".to_string());
-        };
-        
-        let cell_size_v = if vertical_lines.len() > 1 {
-            (vertical_lines[1] - vertical_lines[0]) as u32
-        } else {
-            return Err("Pas assez de lignes verticales détectées".to_string());
-        };
-        
-        // Utiliser la moyenne des deux
-        let cell_size = (cell_size_h + cell_size_v) / 2;
-        
-        // Position de départ de la grille
-        let grid_x = vertical_lines[0] as u32;
-        let grid_y = horizontal_lines[0] as u32;
-        
-        // Dimensions de la grille
-        let grid_width = vertical_lines.len() - 1;
-        let grid_height = horizontal_lines.len() - 1;
-        
-        Ok((grid_x, grid_y, cell_size, grid_width, grid_height))
+        Self::extract_from_regions(image, row_region, col_region, layout.width(), layout.height())
     }
     
     /// Détecte les lignes dans l'image
-    fn detect_lines(image: &ImageBuffer<Luma<u8>, Vec<u8>>, horizontal: bool) -> Vec<usize> {
+    pub fn detect_lines(image: &ImageBuffer<Luma<u8>, Vec<u8>>, horizontal: bool) -> Vec<usize> {
         let (width, height) = image.dimensions();
         let mut lines = Vec::new();
         
@@ -101,10 +64,10 @@ impl AdvancedConstraintExtractor {
                     }
                 }
                 
-                if dark_pixels as f32 / height as f32 > 0.5 {
-                    if lines.is_empty() || x as usize - lines[lines.len() - 1] > 5 {
-                        lines.push(x as usize);
-                    }
+                if dark_pixels as f32 / height as f32 > 0.5
+                    && (lines.is_empty() || x as usize - lines[lines.len() - 1] > 5)
+                {
+                    lines.push(x as usize);
                 }
             }
         }
@@ -149,6 +112,7 @@ impl AdvancedConstraintExtractor {
     }
     
     /// Prétraite l'image pour améliorer la reconnaissance OCR
+    #[cfg(feature = "ocr")]
     fn preprocess_for_ocr(image: &DynamicImage) -> DynamicImage {
         // Convertir en niveaux de gris
         let gray = image.to_luma8();
@@ -202,7 +166,7 @@ impl AdvancedConstraintExtractor {
     
     #[cfg(feature = "ocr")]
     fn parse_constraint_list(text: &str, expected_count: usize) -> Result<Vec<Vec<usize>>, String> {
-        let re = Regex::new(r"(\d+(?:\s+\d+)*)").unwrap();
+        let re = Regex::new(r"(\d+(?:[ \t]+\d+)*)").unwrap();
         let mut constraints = Vec::new();
         
         for cap in re.captures_iter(text) {
@@ -232,17 +196,36 @@ impl AdvancedConstraintExtractor {
         Ok(constraints)
     }
     
-    /// Méthode simplifiée sans OCR qui utilise une heuristique basée sur l'image
-    #[cfg(not(feature = "ocr"))]
-    pub fn extract_from_image_heuristic(image: &DynamicImage) -> Result<Constraints, String> {
-        // Détecter la grille
-        let (grid_x, grid_y, cell_size, grid_width, grid_height) = Self::detect_grid(image)?;
-        
-        // Créer des contraintes vides (l'utilisateur devra les fournir)
-        let rows = vec![vec![]; grid_height];
-        let columns = vec![vec![]; grid_width];
-        
-        Constraints::new(grid_width, grid_height, rows, columns)
+    /// Lit les indices avec le lecteur de chiffres intégré (sans Tesseract)
+    ///
+    /// Chaque ligne et chaque colonne est lue dans sa part du panneau d'indices décrit par
+    /// la mise en page. Chaque lecture garde sa confiance et ses lectures alternatives.
+    pub fn read_clues(image: &DynamicImage, layout: &PuzzleLayout) -> (Vec<ClueReading>, Vec<ClueReading>) {
+        let gray = image.to_luma8();
+        let recognizer = DigitRecognizer::new();
+        let rows = (0..layout.height())
+            .map(|row| layout.row_clue_region(row)
+                .map(|region| recognizer.read_clue(&gray, region, false))
+                .unwrap_or_default())
+            .collect();
+        let columns = (0..layout.width())
+            .map(|col| layout.column_clue_region(col)
+                .map(|region| recognizer.read_clue(&gray, region, true))
+                .unwrap_or_default())
+            .collect();
+
+        (rows, columns)
+    }
+
+    /// Lit les indices puis corrige les erreurs de lecture probables avec le solveur
+    pub fn extract_with_recognizer(image: &DynamicImage, layout: &PuzzleLayout) -> Reconciliation {
+        Self::extract_with_reconciler(image, layout, &ClueReconciler::new())
+    }
+
+    /// Comme `extract_with_recognizer`, avec un réconciliateur configuré (budget, annulation)
+    pub fn extract_with_reconciler(image: &DynamicImage, layout: &PuzzleLayout, reconciler: &ClueReconciler) -> Reconciliation {
+        let (rows, columns) = Self::read_clues(image, layout);
+        reconciler.reconcile(&rows, &columns)
     }
 }
 
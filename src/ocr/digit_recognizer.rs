@@ -0,0 +1,484 @@
+use image::GrayImage;
+
+/// Largeur et hauteur de la grille normalisée sur laquelle les glyphes sont comparés
+const NORM_WIDTH: usize = 6;
+const NORM_HEIGHT: usize = 9;
+/// Sous-échantillonnage par case normalisée
+const SUPERSAMPLING: usize = 4;
+
+/// Gabarits intégrés: police matricielle 5x7, puis variantes sur 8 lignes des petites
+/// polices sans empattement (boucles ouvertes, « 1 » sans pied)
+const BUILTIN_TEMPLATES: &[(u8, &[&str])] = &[
+    (0, &["01110", "10001", "10011", "10101", "11001", "10001", "01110"]),
+    (1, &["010", "110", "010", "010", "010", "010", "111"]),
+    (2, &["01110", "10001", "00001", "00010", "00100", "01000", "11111"]),
+    (3, &["11111", "00010", "00100", "00010", "00001", "10001", "01110"]),
+    (4, &["00010", "00110", "01010", "10010", "11111", "00010", "00010"]),
+    (5, &["11111", "10000", "11110", "00001", "00001", "10001", "01110"]),
+    (6, &["00110", "01000", "10000", "11110", "10001", "10001", "01110"]),
+    (7, &["11111", "00001", "00010", "00100", "01000", "01000", "01000"]),
+    (8, &["01110", "10001", "10001", "01110", "10001", "10001", "01110"]),
+    (9, &["01110", "10001", "10001", "01111", "00001", "00010", "01100"]),
+    (0, &["01110", "10001", "10001", "10001", "10001", "10001", "10001", "01110"]),
+    (1, &["011", "111", "001", "001", "001", "001", "001", "001"]),
+    (2, &["0110", "1001", "0001", "0001", "0010", "0100", "1000", "1111"]),
+    (3, &["01110", "10001", "00001", "00110", "00001", "00001", "10001", "01110"]),
+    (4, &["000110", "000110", "001010", "010010", "010010", "111111", "000010", "000010"]),
+    (5, &["11111", "10000", "10000", "11110", "00001", "00001", "10001", "01110"]),
+    (6, &["01110", "10001", "10000", "11110", "10001", "10001", "10001", "01110"]),
+    (7, &["11111", "00001", "00010", "00010", "00100", "00100", "01000", "01000"]),
+    (8, &["01110", "10001", "10001", "01110", "10001", "10001", "10001", "01110"]),
+    (9, &["01110", "10001", "10001", "10001", "01111", "00001", "10001", "01110"]),
+];
+
+/// Dessin d'un chiffre de la police des gabarits (lignes de `0` et `1`),
+/// pour écrire des indices que le lecteur saura relire
+pub fn digit_bitmap(digit: u8) -> Option<&'static [&'static str]> {
+    BUILTIN_TEMPLATES.iter().find(|(d, _)| *d == digit).map(|(_, rows)| *rows)
+}
+
+/// Configuration du lecteur de chiffres
+#[derive(Debug, Clone)]
+pub struct RecognizerConfig {
+    /// Écart minimal entre papier et encre pour considérer qu'une zone contient du texte
+    pub min_contrast: u8,
+    /// Espace (en proportion de la hauteur de ligne) au-delà duquel deux chiffres
+    /// appartiennent à deux nombres différents
+    pub number_gap_ratio: f32,
+    /// Rapport largeur/hauteur au-delà duquel un glyphe est découpé en chiffres collés
+    pub max_digit_aspect: f32,
+    /// Hauteur minimale d'un glyphe en pixels (en dessous: bruit)
+    pub min_glyph_height: u32,
+}
+
+impl Default for RecognizerConfig {
+    fn default() -> Self {
+        Self {
+            min_contrast: 60,
+            number_gap_ratio: 0.35,
+            max_digit_aspect: 1.0,
+            min_glyph_height: 4,
+        }
+    }
+}
+
+/// Glyphe isolé: taux d'encre (0 = papier, 1 = encre) de chaque pixel de sa boîte englobante
+#[derive(Debug, Clone)]
+pub struct Glyph {
+    pub width: usize,
+    pub height: usize,
+    pub ink: Vec<f32>,
+}
+
+impl Glyph {
+    /// Glyphe à partir de lignes de '0' et '1'
+    pub fn from_rows(rows: &[&str]) -> Self {
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let ink = rows.iter()
+            .flat_map(|row| (0..width).map(move |x| if row.as_bytes().get(x) == Some(&b'1') { 1.0 } else { 0.0 }))
+            .collect();
+        Self { width, height: rows.len(), ink }
+    }
+
+    /// Rapport largeur / hauteur
+    pub fn aspect(&self) -> f32 {
+        self.width as f32 / self.height.max(1) as f32
+    }
+
+    /// Taux d'encre rééchantillonné sur la grille normalisée
+    fn normalized(&self) -> Vec<f32> {
+        let samples = (SUPERSAMPLING * SUPERSAMPLING) as f32;
+        let mut normalized = Vec::with_capacity(NORM_WIDTH * NORM_HEIGHT);
+        for ny in 0..NORM_HEIGHT {
+            for nx in 0..NORM_WIDTH {
+                let mut total = 0.0;
+                for sy in 0..SUPERSAMPLING {
+                    for sx in 0..SUPERSAMPLING {
+                        let fx = (nx * SUPERSAMPLING + sx) as f32 + 0.5;
+                        let fy = (ny * SUPERSAMPLING + sy) as f32 + 0.5;
+                        let x = (fx * self.width as f32 / (NORM_WIDTH * SUPERSAMPLING) as f32) as usize;
+                        let y = (fy * self.height as f32 / (NORM_HEIGHT * SUPERSAMPLING) as f32) as usize;
+                        total += self.ink[y.min(self.height - 1) * self.width + x.min(self.width - 1)];
+                    }
+                }
+                normalized.push(total / samples);
+            }
+        }
+        normalized
+    }
+}
+
+/// Gabarit d'un chiffre
+#[derive(Debug, Clone)]
+pub struct DigitTemplate {
+    pub digit: u8,
+    aspect: f32,
+    normalized: Vec<f32>,
+}
+
+impl DigitTemplate {
+    /// Apprend un gabarit à partir d'un glyphe (par exemple extrait d'une image dans la police du magazine)
+    pub fn from_glyph(digit: u8, glyph: &Glyph) -> Self {
+        Self { digit, aspect: glyph.aspect(), normalized: glyph.normalized() }
+    }
+}
+
+/// Nombre lu dans une zone d'indices
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecognizedNumber {
+    pub value: usize,
+    /// Confiance du chiffre le moins sûr (0 à 1)
+    pub confidence: f32,
+    /// Chiffres candidats de chaque position, du plus au moins probable, avec leur distance
+    pub digits: Vec<Vec<(u8, f32)>>,
+    /// Espaces entre chiffres consécutifs, en proportion de la hauteur de ligne
+    pub inner_gaps: Vec<f32>,
+    /// Espace avec le nombre précédent sur la même ligne de texte (None en début de ligne)
+    pub gap_before: Option<f32>,
+}
+
+impl RecognizedNumber {
+    fn from_digits(digits: Vec<Vec<(u8, f32)>>, inner_gaps: Vec<f32>, gap_before: Option<f32>) -> Option<Self> {
+        let best: Vec<(u8, f32)> = digits.iter().map(|candidates| candidates.first().copied()).collect::<Option<_>>()?;
+        if best.is_empty() {
+            return None;
+        }
+        let value = best.iter().fold(0usize, |value, &(digit, _)| value * 10 + digit as usize);
+        let worst = best.iter().map(|&(_, distance)| distance).fold(0.0f32, f32::max);
+        Some(Self { value, confidence: (1.0 - worst).clamp(0.0, 1.0), digits, inner_gaps, gap_before })
+    }
+}
+
+/// Lecture complète de l'indice d'une ligne ou d'une colonne
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ClueReading {
+    pub numbers: Vec<RecognizedNumber>,
+    /// Seuil d'espace (en proportion de la hauteur de ligne) séparant deux nombres
+    pub gap_threshold: f32,
+}
+
+impl ClueReading {
+    /// Valeurs lues (les zéros, indice d'une ligne vide, sont omis)
+    pub fn values(&self) -> Vec<usize> {
+        self.numbers.iter().map(|number| number.value).filter(|&value| value > 0).collect()
+    }
+
+    /// Confiance du nombre le moins sûr (1 pour un indice vide)
+    pub fn confidence(&self) -> f32 {
+        self.numbers.iter().map(|number| number.confidence).fold(1.0, f32::min)
+    }
+
+    /// Lectures alternatives classées par coût croissant (écart de distance au gabarit,
+    /// ou proximité de l'espace avec le seuil pour les nombres fusionnés ou séparés à tort)
+    pub fn alternatives(&self, limit: usize) -> Vec<(Vec<usize>, f32)> {
+        let values: Vec<usize> = self.numbers.iter().map(|number| number.value).collect();
+        let threshold = self.gap_threshold.max(f32::EPSILON);
+        let mut alternatives: Vec<(Vec<usize>, f32)> = Vec::new();
+
+        for (index, number) in self.numbers.iter().enumerate() {
+            // Autre chiffre à une position (un 8 lu comme un 3)
+            for (position, candidates) in number.digits.iter().enumerate() {
+                let Some(&(_, best)) = candidates.first() else {
+                    continue;
+                };
+                for &(digit, distance) in candidates.iter().skip(1).take(2) {
+                    let mut digits: Vec<u8> = number.digits.iter().filter_map(|c| c.first().map(|&(d, _)| d)).collect();
+                    digits[position] = digit;
+                    let mut candidate = values.clone();
+                    candidate[index] = digits.iter().fold(0, |value, &d| value * 10 + d as usize);
+                    alternatives.push((candidate, distance - best));
+                }
+            }
+
+            // Nombre à plusieurs chiffres lu d'un bloc alors qu'il s'agit de plusieurs indices (« 1 1 » lu « 11 »)
+            for (position, &gap) in number.inner_gaps.iter().enumerate() {
+                let digits: Vec<usize> = number.digits.iter().filter_map(|c| c.first().map(|&(d, _)| d as usize)).collect();
+                let left = digits[..=position].iter().fold(0, |value, &d| value * 10 + d);
+                let right = digits[position + 1..].iter().fold(0, |value, &d| value * 10 + d);
+                let mut candidate = values[..index].to_vec();
+                candidate.extend([left, right]);
+                candidate.extend(&values[index + 1..]);
+                alternatives.push((candidate, (threshold - gap).max(0.0) / threshold));
+            }
+
+            // Deux nombres trop proches fusionnés (« 1 2 » pour « 12 »)
+            if let (Some(gap), Some(previous)) = (number.gap_before, index.checked_sub(1)) {
+                let merged = format!("{}{}", values[previous], values[index]).parse().unwrap_or(usize::MAX);
+                let mut candidate = values[..previous].to_vec();
+                candidate.push(merged);
+                candidate.extend(&values[index + 1..]);
+                alternatives.push((candidate, (gap - threshold).max(0.0) / threshold));
+            }
+        }
+
+        alternatives.retain(|(candidate, _)| candidate != &values);
+        alternatives.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let mut unique: Vec<(Vec<usize>, f32)> = Vec::new();
+        for (candidate, cost) in alternatives {
+            let candidate: Vec<usize> = candidate.into_iter().filter(|&value| value > 0).collect();
+            if !unique.iter().any(|(existing, _)| existing == &candidate) {
+                unique.push((candidate, cost));
+            }
+        }
+        unique.truncate(limit);
+        unique
+    }
+}
+
+/// Lecteur de chiffres par comparaison de gabarits, sans dépendance système
+///
+/// Les zones d'indices sont découpées par projections (lignes de texte, puis glyphes),
+/// chaque glyphe est normalisé sur une grille fixe et comparé aux gabarits; les glyphes
+/// proches forment un nombre à plusieurs chiffres.
+pub struct DigitRecognizer {
+    config: RecognizerConfig,
+    templates: Vec<DigitTemplate>,
+}
+
+impl DigitRecognizer {
+    pub fn new() -> Self {
+        Self::with_config(RecognizerConfig::default())
+    }
+
+    pub fn with_config(config: RecognizerConfig) -> Self {
+        let templates = BUILTIN_TEMPLATES.iter()
+            .map(|(digit, rows)| DigitTemplate::from_glyph(*digit, &Glyph::from_rows(rows)))
+            .collect();
+        Self { config, templates }
+    }
+
+    /// Chiffres classés du plus au moins probable, avec leur distance (0 = identique)
+    pub fn classify(&self, glyph: &Glyph) -> Vec<(u8, f32)> {
+        let normalized = glyph.normalized();
+        let mut best = [f32::INFINITY; 10];
+
+        for template in &self.templates {
+            let pixels = normalized.iter()
+                .zip(&template.normalized)
+                .map(|(a, b)| (a - b).abs())
+                .sum::<f32>() / normalized.len() as f32;
+            // Un « 1 » étroit étiré sur la grille normalisée ressemble à un pavé: la forme
+            // de la boîte englobante départage
+            let shape = 0.3 * (glyph.aspect() / template.aspect).ln().abs();
+            let distance = pixels + shape;
+            let slot = &mut best[template.digit as usize];
+            *slot = slot.min(distance);
+        }
+
+        let mut ranked: Vec<(u8, f32)> = best.iter()
+            .enumerate()
+            .filter(|(_, distance)| distance.is_finite())
+            .map(|(digit, &distance)| (digit as u8, distance))
+            .collect();
+        ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+        ranked
+    }
+
+    /// Lit l'indice d'une zone `(x, y, largeur, hauteur)` avec les candidats de chaque chiffre et
+    /// les espaces mesurés. Les indices d'une colonne sont empilés (`stacked`): une ligne de texte
+    /// par nombre ou groupe de nombres, lues de haut en bas.
+    pub fn read_clue(&self, gray: &GrayImage, region: (u32, u32, u32, u32), stacked: bool) -> ClueReading {
+        let mut reading = ClueReading { numbers: Vec::new(), gap_threshold: self.config.number_gap_ratio };
+        let Some((ink, width, height)) = self.ink_map(gray, region) else {
+            return reading;
+        };
+        let dark = |x: usize, y: usize| ink[y * width + x] >= 0.5;
+
+        // Lignes de texte: plages de lignes de pixels contenant de l'encre
+        let rows_with_ink: Vec<bool> = (0..height).map(|y| (0..width).any(|x| dark(x, y))).collect();
+        let mut lines = Self::runs(&rows_with_ink, 1);
+        if !stacked && !lines.is_empty() {
+            lines = vec![(lines[0].0, lines[lines.len() - 1].1)];
+        }
+
+        for (top, bottom) in lines {
+            let line_height = (bottom - top) as f32;
+            let columns_with_ink: Vec<bool> = (0..width).map(|x| (top..bottom).any(|y| dark(x, y))).collect();
+            let mut previous_end = None;
+            let mut gap_before = None;
+            let mut digits: Vec<Vec<(u8, f32)>> = Vec::new();
+            let mut inner_gaps = Vec::new();
+
+            for (left, right) in Self::runs(&columns_with_ink, 0) {
+                let Some(glyph) = Self::crop(&ink, width, left, right, top, bottom) else {
+                    continue;
+                };
+                if (glyph.height as u32) < self.config.min_glyph_height || glyph.height as f32 * 10.0 < line_height * 3.0 {
+                    continue;
+                }
+
+                if let Some(end) = previous_end {
+                    let gap = (left - end) as f32 / line_height;
+                    if gap > self.config.number_gap_ratio {
+                        reading.numbers.extend(RecognizedNumber::from_digits(
+                            std::mem::take(&mut digits), std::mem::take(&mut inner_gaps), gap_before,
+                        ));
+                        gap_before = Some(gap);
+                    } else {
+                        inner_gaps.push(gap);
+                    }
+                }
+                previous_end = Some(right);
+
+                for (i, part) in self.split_touching(&glyph).into_iter().enumerate() {
+                    if i > 0 {
+                        inner_gaps.push(0.0);
+                    }
+                    digits.push(self.classify(&part));
+                }
+            }
+            reading.numbers.extend(RecognizedNumber::from_digits(digits, inner_gaps, gap_before));
+        }
+
+        reading
+    }
+
+    /// Taux d'encre de chaque pixel de la zone, avec ses dimensions effectives
+    /// (None si la zone est vide ou sans contraste)
+    fn ink_map(&self, gray: &GrayImage, region: (u32, u32, u32, u32)) -> Option<(Vec<f32>, usize, usize)> {
+        let (x0, y0) = (region.0, region.1);
+        let x1 = (region.0 + region.2).min(gray.width());
+        let y1 = (region.1 + region.3).min(gray.height());
+        if x1 <= x0 || y1 <= y0 {
+            return None;
+        }
+
+        let values: Vec<u8> = (y0..y1)
+            .flat_map(|y| (x0..x1).map(move |x| gray.get_pixel(x, y)[0]))
+            .collect();
+        let black = *values.iter().min()?;
+        let white = *values.iter().max()?;
+        if white.saturating_sub(black) < self.config.min_contrast {
+            return None;
+        }
+
+        let range = (white - black) as f32;
+        let ink = values.iter().map(|&v| (white - v) as f32 / range).collect();
+        Some((ink, (x1 - x0) as usize, (y1 - y0) as usize))
+    }
+
+    /// Plages consécutives de valeurs vraies, en fusionnant les trous de `tolerance` pixels au plus
+    fn runs(mask: &[bool], tolerance: usize) -> Vec<(usize, usize)> {
+        let mut runs: Vec<(usize, usize)> = Vec::new();
+        for (i, _) in mask.iter().enumerate().filter(|(_, &set)| set) {
+            match runs.last_mut() {
+                Some((_, end)) if i <= *end + tolerance => *end = i + 1,
+                _ => runs.push((i, i + 1)),
+            }
+        }
+        runs
+    }
+
+    /// Glyphe réduit à sa boîte englobante
+    fn crop(ink: &[f32], width: usize, left: usize, right: usize, top: usize, bottom: usize) -> Option<Glyph> {
+        let dark_rows: Vec<usize> = (top..bottom)
+            .filter(|&y| (left..right).any(|x| ink[y * width + x] >= 0.5))
+            .collect();
+        let (&glyph_top, &glyph_bottom) = (dark_rows.first()?, dark_rows.last()?);
+
+        let glyph_width = right - left;
+        let glyph_height = glyph_bottom + 1 - glyph_top;
+        let pixels = (glyph_top..=glyph_bottom)
+            .flat_map(|y| (left..right).map(move |x| ink[y * width + x]))
+            .collect();
+        Some(Glyph { width: glyph_width, height: glyph_height, ink: pixels })
+    }
+
+    /// Découpe un glyphe trop large (chiffres qui se touchent) aux colonnes les moins encrées
+    fn split_touching(&self, glyph: &Glyph) -> Vec<Glyph> {
+        if glyph.aspect() <= self.config.max_digit_aspect {
+            return vec![glyph.clone()];
+        }
+
+        let parts = (glyph.aspect() / (self.config.max_digit_aspect * 0.6)).round().max(2.0) as usize;
+        let column_ink = |x: usize| (0..glyph.height).map(|y| glyph.ink[y * glyph.width + x]).sum::<f32>();
+        let window = (glyph.width / (4 * parts)).max(1);
+
+        let mut cuts = vec![0];
+        for i in 1..parts {
+            let center = i * glyph.width / parts;
+            let start = center.saturating_sub(window).max(cuts[cuts.len() - 1] + 1);
+            let end = (center + window).min(glyph.width - 1);
+            let cut = (start..=end.max(start))
+                .min_by(|&a, &b| column_ink(a).total_cmp(&column_ink(b)))
+                .unwrap_or(center);
+            cuts.push(cut);
+        }
+        cuts.push(glyph.width);
+
+        cuts.windows(2)
+            .filter(|cut| cut[1] > cut[0])
+            .map(|cut| Glyph {
+                width: cut[1] - cut[0],
+                height: glyph.height,
+                ink: (0..glyph.height)
+                    .flat_map(|y| (cut[0]..cut[1]).map(move |x| glyph.ink[y * glyph.width + x]))
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+impl Default for DigitRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    /// Dessine des chiffres de la police intégrée, agrandis, séparés par `spacing` pixels
+    /// (un espace dans le texte sépare deux nombres)
+    fn render(text: &str, scale: u32, spacing: u32) -> GrayImage {
+        let mut image = GrayImage::from_pixel(200, 40, Luma([255]));
+        let mut x = 5;
+        for c in text.chars() {
+            if c == ' ' {
+                x += 4 * scale;
+                continue;
+            }
+            let digit = c.to_digit(10).unwrap() as u8;
+            let (_, rows) = BUILTIN_TEMPLATES.iter().find(|(d, _)| *d == digit).unwrap();
+            let width = rows[0].len() as u32;
+            for (row, line) in rows.iter().enumerate() {
+                for (col, bit) in line.bytes().enumerate() {
+                    if bit == b'1' {
+                        for dy in 0..scale {
+                            for dx in 0..scale {
+                                image.put_pixel(x + col as u32 * scale + dx, 8 + row as u32 * scale + dy, Luma([20]));
+                            }
+                        }
+                    }
+                }
+            }
+            x += width * scale + spacing;
+        }
+        image
+    }
+
+    #[test]
+    fn test_reads_every_digit() {
+        let recognizer = DigitRecognizer::new();
+        let image = render("0 1 2 3 4 5 6 7 8 9", 2, 2);
+        let values: Vec<usize> = recognizer.read_clue(&image, (0, 0, 200, 40), false).numbers
+            .into_iter()
+            .map(|number| number.value)
+            .collect();
+        assert_eq!(values, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_reads_multi_digit_clues() {
+        let recognizer = DigitRecognizer::new();
+        let image = render("12 3 10", 3, 2);
+        let numbers = recognizer.read_clue(&image, (0, 0, 200, 40), false).numbers;
+        assert_eq!(numbers.iter().map(|number| number.value).collect::<Vec<_>>(), vec![12, 3, 10]);
+        assert!(numbers.iter().all(|number| number.confidence > 0.8));
+
+        // Zone blanche: aucun indice
+        assert!(recognizer.read_clue(&GrayImage::from_pixel(20, 20, Luma([250])), (0, 0, 20, 20), true).numbers.is_empty());
+    }
+}
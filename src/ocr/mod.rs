@@ -3,6 +3,8 @@ use regex::Regex;
 use crate::grid::Constraints;
 
 pub mod advanced_extractor;
+pub mod digit_recognizer;
+pub mod reconcile;
 pub use advanced_extractor::AdvancedConstraintExtractor;
 
 #[cfg(feature = "ocr")]
@@ -44,9 +46,9 @@ impl ConstraintExtractor {
     }
     
     /// Parse le texte extrait pour obtenir les contraintes
-    fn parse_constraints_from_text(text: &str, width: usize, height: usize) -> Result<Constraints, String> {
+    pub fn parse_constraints_from_text(text: &str, width: usize, height: usize) -> Result<Constraints, String> {
         // Expression régulière pour trouver les séquences de nombres
-        let re = Regex::new(r"(\d+(?:\s+\d+)*)").unwrap();
+        let re = Regex::new(r"(\d+(?:[ \t]+\d+)*)").unwrap();
         
         let mut all_constraints: Vec<Vec<usize>> = Vec::new();
         
@@ -130,7 +132,7 @@ impl ConstraintExtractor {
     
     #[cfg(feature = "ocr")]
     fn parse_constraint_list(text: &str, expected_count: usize) -> Result<Vec<Vec<usize>>, String> {
-        let re = Regex::new(r"(\d+(?:\s+\d+)*)").unwrap();
+        let re = Regex::new(r"(\d+(?:[ \t]+\d+)*)").unwrap();
         let mut constraints = Vec::new();
         
         for cap in re.captures_iter(text) {
@@ -165,7 +167,7 @@ mod tests {
     
     #[test]
     fn test_parse_constraints_from_text() {
-        let text = "1 2\n3\n4 5\n6\n7\n8";
+        let text = "3\n1\n2\n1 1\n3\n1";
         let result = ConstraintExtractor::parse_constraints_from_text(text, 3, 3);
         
         assert!(result.is_ok());
@@ -0,0 +1,379 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::checker::LineKind;
+use crate::grid::{Constraints, Grid};
+use crate::solver::{SolutionCount, SolutionSearch};
+use super::digit_recognizer::ClueReading;
+
+/// Alternative `j` de l'indice `i`
+type AlternativeRef = (usize, usize);
+
+/// Correction essayée: indices modifiés avec leur nouvelle valeur, et son coût
+type Candidate = (Vec<(usize, Vec<usize>)>, f32);
+
+/// Configuration de la réconciliation des indices lus
+#[derive(Debug, Clone)]
+pub struct ReconcilerConfig {
+    /// Lectures alternatives essayées par indice
+    pub alternatives_per_clue: usize,
+    /// Indices les moins sûrs pris en compte pour les corrections doubles
+    pub suspects: usize,
+    /// Limite de nœuds de la recherche de solution pour chaque essai
+    pub max_search_nodes: usize,
+    /// Limite de nœuds pour l'ensemble des essais: une fois atteinte, la meilleure
+    /// correction non vérifiée est retenue
+    pub max_total_nodes: usize,
+}
+
+impl Default for ReconcilerConfig {
+    fn default() -> Self {
+        Self {
+            alternatives_per_clue: 4,
+            suspects: 8,
+            max_search_nodes: 5000,
+            max_total_nodes: 100_000,
+        }
+    }
+}
+
+/// Indice corrigé lors de la réconciliation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClueCorrection {
+    pub kind: LineKind,
+    pub index: usize,
+    pub read: Vec<usize>,
+    pub corrected: Vec<usize>,
+}
+
+/// Résultat de la réconciliation
+#[derive(Debug, Clone)]
+pub struct Reconciliation {
+    /// Indices lus, éventuellement corrigés
+    pub rows: Vec<Vec<usize>>,
+    pub columns: Vec<Vec<usize>>,
+    pub corrections: Vec<ClueCorrection>,
+    /// Les indices retenus admettent une solution (None si la recherche n'a pas abouti:
+    /// les corrections éventuelles ne sont alors pas vérifiées)
+    pub consistent: Option<bool>,
+}
+
+impl Reconciliation {
+    /// Contraintes correspondant aux indices retenus
+    pub fn constraints(&self) -> Result<Constraints, String> {
+        Constraints::new(self.columns.len(), self.rows.len(), self.rows.clone(), self.columns.clone())
+    }
+
+    /// Des indices ont été corrigés sans que le solveur ait pu prouver l'existence d'une solution
+    pub fn unverified(&self) -> bool {
+        !self.corrections.is_empty() && self.consistent.is_none()
+    }
+
+    /// Affiche les corrections effectuées
+    pub fn print(&self) {
+        for correction in &self.corrections {
+            println!(
+                "🔧 {} {}: indice lu {:?}, corrigé en {:?}",
+                correction.kind.label(), correction.index + 1, correction.read, correction.corrected
+            );
+        }
+        if self.consistent == Some(false) {
+            println!("⚠️  Les indices lus restent contradictoires: vérifiez l'image ou fournissez --constraints");
+        } else if self.unverified() {
+            println!("⚠️  Corrections non vérifiées (recherche de solution non aboutie): vérifiez les indices corrigés");
+        }
+    }
+}
+
+/// Corrige les erreurs de lecture probables en s'appuyant sur le solveur
+///
+/// Les sommes des lignes et des colonnes doivent être égales et chaque indice doit tenir
+/// dans sa ligne; tant que ce n'est pas le cas, ou que le solveur prouve l'absence de
+/// solution, les lectures alternatives les plus probables des indices les moins sûrs sont
+/// essayées, une puis deux à la fois. La moins coûteuse dont le solveur prouve la cohérence
+/// est retenue; à défaut, la moins coûteuse dont la recherche n'a pas abouti, signalée
+/// comme non vérifiée. Les essais s'arrêtent quand le budget total de nœuds est épuisé
+/// ou que la réconciliation est annulée.
+pub struct ClueReconciler {
+    config: ReconcilerConfig,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+/// État des essais en cours: meilleure correction non vérifiée et nœuds restants
+struct Attempts {
+    inconclusive: Option<Candidate>,
+    remaining_nodes: usize,
+}
+
+impl ClueReconciler {
+    pub fn new() -> Self {
+        Self::with_config(ReconcilerConfig::default())
+    }
+
+    pub fn with_config(config: ReconcilerConfig) -> Self {
+        Self { config, cancel: None }
+    }
+
+    /// Drapeau d'annulation: une fois levé, plus aucune correction n'est essayée
+    pub fn set_cancel_flag(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
+    }
+
+    pub fn reconcile(&self, rows: &[ClueReading], columns: &[ClueReading]) -> Reconciliation {
+        let mut clues: Vec<Vec<usize>> = rows.iter().chain(columns).map(ClueReading::values).collect();
+        let readings: Vec<&ClueReading> = rows.iter().chain(columns).collect();
+        let (width, height) = (columns.len(), rows.len());
+
+        let mut attempts = Attempts { inconclusive: None, remaining_nodes: self.config.max_total_nodes };
+        let initial = self.check(&clues, width, height, &mut attempts.remaining_nodes);
+        if initial != Some(false) {
+            return Self::result(clues, rows.len(), Vec::new(), initial);
+        }
+
+        // Suspects: indices qui ne tiennent pas dans leur ligne, puis confiance croissante
+        let mut suspects: Vec<usize> = (0..clues.len()).collect();
+        suspects.sort_by(|&a, &b| {
+            let fits = |i: usize| Self::fits(&clues[i], if i < height { width } else { height });
+            fits(a).cmp(&fits(b)).then(readings[a].confidence().total_cmp(&readings[b].confidence()))
+        });
+        suspects.truncate(self.config.suspects.max(1));
+
+        let alternatives: Vec<Vec<(Vec<usize>, f32)>> = (0..clues.len())
+            .map(|i| readings[i].alternatives(self.config.alternatives_per_clue))
+            .collect();
+
+        // Corrections simples, de la moins coûteuse à la plus coûteuse
+        let mut singles: Vec<(usize, &Vec<usize>, f32)> = (0..clues.len())
+            .flat_map(|i| alternatives[i].iter().map(move |(candidate, cost)| (i, candidate, *cost)))
+            .collect();
+        singles.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+        for &(i, candidate, cost) in &singles {
+            if self.exhausted(&attempts) {
+                break;
+            }
+            let changes = vec![(i, candidate.clone())];
+            if let Some(result) = self.try_candidate(&mut clues, changes, cost, height, &mut attempts) {
+                return result;
+            }
+        }
+
+        // Corrections doubles parmi les suspects (indices des alternatives essayées)
+        let mut pairs: Vec<(AlternativeRef, AlternativeRef, f32)> = Vec::new();
+        for (n, &a) in suspects.iter().enumerate() {
+            for &b in &suspects[n + 1..] {
+                for (i, (_, cost_a)) in alternatives[a].iter().enumerate() {
+                    for (j, (_, cost_b)) in alternatives[b].iter().enumerate() {
+                        pairs.push(((a, i), (b, j), cost_a + cost_b));
+                    }
+                }
+            }
+        }
+        pairs.sort_by(|x, y| x.2.total_cmp(&y.2));
+
+        for ((a, i), (b, j), cost) in pairs {
+            if self.exhausted(&attempts) {
+                break;
+            }
+            let changes = vec![(a, alternatives[a][i].0.clone()), (b, alternatives[b][j].0.clone())];
+            if let Some(result) = self.try_candidate(&mut clues, changes, cost, height, &mut attempts) {
+                return result;
+            }
+        }
+
+        match attempts.inconclusive {
+            Some((changes, _)) => Self::apply(clues, changes, height, None),
+            None => Self::result(clues, height, Vec::new(), Some(false)),
+        }
+    }
+
+    /// Essaie une correction: le résultat si le solveur la prouve cohérente, sinon les indices
+    /// sont restaurés et la correction est gardée dans `attempts` si la recherche n'a pas
+    /// abouti et qu'elle est la moins coûteuse dans ce cas
+    fn try_candidate(
+        &self,
+        clues: &mut [Vec<usize>],
+        changes: Vec<(usize, Vec<usize>)>,
+        cost: f32,
+        height: usize,
+        attempts: &mut Attempts,
+    ) -> Option<Reconciliation> {
+        let width = clues.len() - height;
+        let originals: Vec<Vec<usize>> = changes.iter()
+            .map(|(i, candidate)| std::mem::replace(&mut clues[*i], candidate.clone()))
+            .collect();
+        let consistent = self.check(clues, width, height, &mut attempts.remaining_nodes);
+        for ((i, _), original) in changes.iter().zip(originals) {
+            clues[*i] = original;
+        }
+
+        match consistent {
+            Some(true) => Some(Self::apply(clues.to_vec(), changes, height, Some(true))),
+            None => {
+                if attempts.inconclusive.as_ref().is_none_or(|(_, best)| cost < *best) {
+                    attempts.inconclusive = Some((changes, cost));
+                }
+                None
+            }
+            Some(false) => None,
+        }
+    }
+
+    /// Applique une correction aux indices lus et en dresse la liste
+    fn apply(mut clues: Vec<Vec<usize>>, changes: Vec<(usize, Vec<usize>)>, height: usize, consistent: Option<bool>) -> Reconciliation {
+        let corrections = changes.into_iter()
+            .map(|(i, candidate)| {
+                let original = std::mem::replace(&mut clues[i], candidate.clone());
+                Self::correction(i, height, &original, &candidate)
+            })
+            .collect();
+        Self::result(clues, height, corrections, consistent)
+    }
+
+    /// Budget de nœuds épuisé ou réconciliation annulée
+    fn exhausted(&self, attempts: &Attempts) -> bool {
+        attempts.remaining_nodes == 0 || self.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+
+    /// Vérifie la cohérence des indices: sommes, place dans chaque ligne, puis existence d'une
+    /// solution; les nœuds explorés sont décomptés de `remaining_nodes`
+    fn check(&self, clues: &[Vec<usize>], width: usize, height: usize, remaining_nodes: &mut usize) -> Option<bool> {
+        let (rows, columns) = clues.split_at(height);
+        let row_sum: usize = rows.iter().flatten().sum();
+        let column_sum: usize = columns.iter().flatten().sum();
+        if row_sum != column_sum
+            || !rows.iter().all(|clue| Self::fits(clue, width))
+            || !columns.iter().all(|clue| Self::fits(clue, height))
+        {
+            return Some(false);
+        }
+
+        let Ok(constraints) = Constraints::new(width, height, rows.to_vec(), columns.to_vec()) else {
+            return Some(false);
+        };
+        let mut search = SolutionSearch::with_max_nodes(self.config.max_search_nodes.min(*remaining_nodes));
+        if let Some(cancel) = &self.cancel {
+            search.set_cancel_flag(Arc::clone(cancel));
+        }
+        let count = search.count(&Grid::new(width, height), &constraints);
+        *remaining_nodes = remaining_nodes.saturating_sub(search.nodes());
+        match count {
+            Ok(SolutionCount::None) | Err(_) => Some(false),
+            Ok(SolutionCount::Unknown) => None,
+            Ok(_) => Some(true),
+        }
+    }
+
+    /// L'indice tient dans une ligne de `length` cases
+    fn fits(clue: &[usize], length: usize) -> bool {
+        clue.iter().sum::<usize>() + clue.len().saturating_sub(1) <= length
+    }
+
+    fn correction(i: usize, height: usize, read: &[usize], corrected: &[usize]) -> ClueCorrection {
+        let (kind, index) = if i < height { (LineKind::Row, i) } else { (LineKind::Column, i - height) };
+        ClueCorrection { kind, index, read: read.to_vec(), corrected: corrected.to_vec() }
+    }
+
+    fn result(mut clues: Vec<Vec<usize>>, height: usize, corrections: Vec<ClueCorrection>, consistent: Option<bool>) -> Reconciliation {
+        let columns = clues.split_off(height);
+        Reconciliation { rows: clues, columns, corrections, consistent }
+    }
+}
+
+impl Default for ClueReconciler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocr::digit_recognizer::RecognizedNumber;
+
+    /// Lecture d'un indice à un seul chiffre, avec un second candidat éventuel
+    fn reading(digit: u8, confidence: f32, second: Option<u8>) -> ClueReading {
+        let mut candidates = vec![(digit, 1.0 - confidence)];
+        candidates.extend(second.map(|other| (other, 1.0 - confidence + 0.02)));
+        let number = RecognizedNumber {
+            value: digit as usize,
+            confidence,
+            digits: vec![candidates],
+            inner_gaps: Vec::new(),
+            gap_before: None,
+        };
+        ClueReading { numbers: vec![number], gap_threshold: 0.35 }
+    }
+
+    #[test]
+    fn test_corrects_misread_digit() {
+        // Croix 3x3: l'indice « 3 » de la 2e ligne a été lu « 8 », avec « 3 » en second choix
+        let rows = vec![reading(1, 0.95, None), reading(8, 0.7, Some(3)), reading(1, 0.95, None)];
+        let columns = vec![reading(1, 0.95, None), reading(3, 0.95, None), reading(1, 0.95, None)];
+
+        let reconciliation = ClueReconciler::new().reconcile(&rows, &columns);
+        assert_eq!(reconciliation.consistent, Some(true));
+        assert_eq!(reconciliation.rows, vec![vec![1], vec![3], vec![1]]);
+        assert_eq!(reconciliation.corrections, vec![ClueCorrection {
+            kind: LineKind::Row,
+            index: 1,
+            read: vec![8],
+            corrected: vec![3],
+        }]);
+    }
+
+    #[test]
+    fn test_prefers_proven_correction_over_inconclusive() {
+        // Croix 3x3 lue [1] [2] [1]: corriger la 1re ligne en « 2 » coûte le moins, mais admet
+        // plusieurs solutions que la recherche limitée à un nœud ne tranche pas; corriger
+        // la 2e ligne en « 3 » est prouvé cohérent par la seule logique des lignes
+        let mut middle = reading(2, 0.9, Some(3));
+        middle.numbers[0].digits[0][1].1 += 0.1;
+        let rows = vec![reading(1, 0.9, Some(2)), middle, reading(1, 0.95, None)];
+        let columns = vec![reading(1, 0.95, None), reading(3, 0.95, None), reading(1, 0.95, None)];
+
+        let config = ReconcilerConfig { max_search_nodes: 1, ..Default::default() };
+        let reconciliation = ClueReconciler::with_config(config).reconcile(&rows, &columns);
+        assert_eq!(reconciliation.consistent, Some(true));
+        assert_eq!(reconciliation.rows, vec![vec![1], vec![3], vec![1]]);
+        assert!(!reconciliation.unverified());
+
+        // Aucune recherche n'aboutit: la correction la moins coûteuse est gardée, non vérifiée
+        let config = ReconcilerConfig { max_search_nodes: 0, ..Default::default() };
+        let reconciliation = ClueReconciler::with_config(config).reconcile(&rows, &columns);
+        assert_eq!(reconciliation.consistent, None);
+        assert_eq!(reconciliation.rows, vec![vec![2], vec![2], vec![1]]);
+        assert!(reconciliation.unverified());
+    }
+
+    #[test]
+    fn test_stops_when_budget_is_spent_or_cancelled() {
+        // Croix 3x3 dont l'indice « 3 » a été lu « 8 »: sans budget, ou une fois annulée,
+        // la réconciliation n'essaie aucune correction et garde les indices lus
+        let rows = vec![reading(1, 0.95, None), reading(8, 0.7, Some(3)), reading(1, 0.95, None)];
+        let columns = vec![reading(1, 0.95, None), reading(3, 0.95, None), reading(1, 0.95, None)];
+
+        let config = ReconcilerConfig { max_total_nodes: 0, ..Default::default() };
+        let mut cancelled = ClueReconciler::new();
+        cancelled.set_cancel_flag(Arc::new(AtomicBool::new(true)));
+        for reconciler in [ClueReconciler::with_config(config), cancelled] {
+            let reconciliation = reconciler.reconcile(&rows, &columns);
+            assert_eq!(reconciliation.consistent, Some(false));
+            assert_eq!(reconciliation.rows, vec![vec![1], vec![8], vec![1]]);
+            assert!(reconciliation.corrections.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_splits_merged_numbers() {
+        // « 1 1 » lu « 11 »: l'espace entre les deux chiffres était juste sous le seuil
+        let merged = RecognizedNumber {
+            value: 11,
+            confidence: 0.95,
+            digits: vec![vec![(1, 0.05)], vec![(1, 0.05)]],
+            inner_gaps: vec![0.3],
+            gap_before: None,
+        };
+        let reading = ClueReading { numbers: vec![merged], gap_threshold: 0.35 };
+        assert_eq!(reading.alternatives(3).first().map(|(values, _)| values.clone()), Some(vec![1, 1]));
+    }
+}
@@ -0,0 +1,438 @@
+//! Module de redressement des photos de grilles (rotation et perspective)
+//! Contours de Canny → transformée de Hough → quadrilatère de la grille → homographie
+
+use image::{DynamicImage, GrayImage, Rgba, RgbaImage};
+use crate::edge_detection::canny;
+
+/// Point (x, y) en pixels
+pub type Point = (f64, f64);
+
+/// Configuration du redressement
+#[derive(Debug, Clone)]
+pub struct PerspectiveConfig {
+    /// Seuils de Canny
+    pub canny_low: f32,
+    pub canny_high: f32,
+    /// Nombre de pas d'angle de la transformée de Hough sur 180°
+    pub theta_steps: usize,
+    /// Votes minimaux d'une droite, en proportion de la meilleure droite de sa famille
+    pub min_votes_ratio: f32,
+    /// Écart angulaire maximal (degrés) entre une droite et l'orientation de sa famille
+    pub angle_tolerance: f64,
+    /// En dessous de ce défaut (pixels), la grille est considérée comme déjà droite
+    pub min_correction: f64,
+}
+
+impl Default for PerspectiveConfig {
+    fn default() -> Self {
+        Self {
+            canny_low: 50.0,
+            canny_high: 100.0,
+            theta_steps: 360,
+            min_votes_ratio: 0.5,
+            angle_tolerance: 20.0,
+            min_correction: 1.5,
+        }
+    }
+}
+
+/// Droite de Hough: x·cos(θ) + y·sin(θ) = ρ
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoughLine {
+    pub rho: f64,
+    /// Angle en radians dans [0, π)
+    pub theta: f64,
+    pub votes: u32,
+}
+
+impl HoughLine {
+    /// Intersection de deux droites (None si elles sont parallèles)
+    pub fn intersection(&self, other: &HoughLine) -> Option<Point> {
+        let (a1, b1) = (self.theta.cos(), self.theta.sin());
+        let (a2, b2) = (other.theta.cos(), other.theta.sin());
+        let det = a1 * b2 - a2 * b1;
+        if det.abs() < 1e-9 {
+            return None;
+        }
+        Some((
+            (self.rho * b2 - other.rho * b1) / det,
+            (a1 * other.rho - a2 * self.rho) / det,
+        ))
+    }
+}
+
+/// Transformation projective 3x3 (h33 = 1)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Homography {
+    pub matrix: [[f64; 3]; 3],
+}
+
+impl Homography {
+    /// Homographie envoyant les quatre points `from` sur les quatre points `to`
+    pub fn from_points(from: &[Point; 4], to: &[Point; 4]) -> Result<Self, String> {
+        let mut system = [[0.0f64; 9]; 8];
+        for (i, (&(x, y), &(u, v))) in from.iter().zip(to.iter()).enumerate() {
+            system[2 * i] = [x, y, 1.0, 0.0, 0.0, 0.0, -u * x, -u * y, u];
+            system[2 * i + 1] = [0.0, 0.0, 0.0, x, y, 1.0, -v * x, -v * y, v];
+        }
+
+        let h = solve_linear_system(system)
+            .ok_or_else(|| "Points dégénérés: homographie impossible".to_string())?;
+        Ok(Self {
+            matrix: [[h[0], h[1], h[2]], [h[3], h[4], h[5]], [h[6], h[7], 1.0]],
+        })
+    }
+
+    /// Applique la transformation à un point
+    pub fn apply(&self, (x, y): Point) -> Point {
+        let m = &self.matrix;
+        let w = m[2][0] * x + m[2][1] * y + m[2][2];
+        (
+            (m[0][0] * x + m[0][1] * y + m[0][2]) / w,
+            (m[1][0] * x + m[1][1] * y + m[1][2]) / w,
+        )
+    }
+
+    /// Transformation inverse (None si la matrice est singulière)
+    pub fn inverse(&self) -> Option<Self> {
+        let m = &self.matrix;
+        let cofactor = |r1: usize, c1: usize, r2: usize, c2: usize| m[r1][c1] * m[r2][c2] - m[r1][c2] * m[r2][c1];
+        let det = m[0][0] * cofactor(1, 1, 2, 2) - m[0][1] * cofactor(1, 0, 2, 2) + m[0][2] * cofactor(1, 0, 2, 1);
+        if det.abs() < 1e-12 {
+            return None;
+        }
+
+        let adjugate = [
+            [cofactor(1, 1, 2, 2), -cofactor(0, 1, 2, 2), cofactor(0, 1, 1, 2)],
+            [-cofactor(1, 0, 2, 2), cofactor(0, 0, 2, 2), -cofactor(0, 0, 1, 2)],
+            [cofactor(1, 0, 2, 1), -cofactor(0, 0, 2, 1), cofactor(0, 0, 1, 1)],
+        ];
+        let scale = adjugate[2][2] / det;
+        if scale.abs() < 1e-12 {
+            return None;
+        }
+
+        let mut matrix = [[0.0; 3]; 3];
+        for r in 0..3 {
+            for c in 0..3 {
+                matrix[r][c] = adjugate[r][c] / det / scale;
+            }
+        }
+        Some(Self { matrix })
+    }
+}
+
+/// Résultat du redressement
+#[derive(Debug, Clone)]
+pub struct Rectification {
+    /// Image redressée (identique à l'entrée si aucune correction n'était nécessaire)
+    pub image: DynamicImage,
+    /// Coins de la grille dans l'image d'origine: haut-gauche, haut-droit, bas-droit, bas-gauche
+    pub corners: [Point; 4],
+    /// Vrai si l'image a été transformée
+    pub corrected: bool,
+}
+
+/// Redresse les photos de grilles prises de biais
+pub struct PerspectiveCorrector {
+    config: PerspectiveConfig,
+}
+
+impl PerspectiveCorrector {
+    pub fn new() -> Self {
+        Self::with_config(PerspectiveConfig::default())
+    }
+
+    pub fn with_config(config: PerspectiveConfig) -> Self {
+        Self { config }
+    }
+
+    /// Détecte le quadrilatère de la grille et redresse l'image pour que la grille
+    /// soit alignée sur les axes; le reste de l'image (indices) suit la même transformation
+    pub fn rectify(&self, image: &DynamicImage) -> Result<Rectification, String> {
+        let corners = self.find_grid_corners(image)?;
+        let [tl, tr, br, bl] = corners;
+
+        let width = (distance(tl, tr) + distance(bl, br)) / 2.0;
+        let height = (distance(tl, bl) + distance(tr, br)) / 2.0;
+        let target = [tl, (tl.0 + width, tl.1), (tl.0 + width, tl.1 + height), (tl.0, tl.1 + height)];
+
+        let defect = corners.iter().zip(target.iter())
+            .map(|(&a, &b)| distance(a, b))
+            .fold(0.0, f64::max);
+        if defect < self.config.min_correction {
+            return Ok(Rectification {
+                image: image.clone(),
+                corners,
+                corrected: false,
+            });
+        }
+
+        let homography = Homography::from_points(&corners, &target)?;
+        let inverse = homography.inverse()
+            .ok_or_else(|| "Homographie non inversible".to_string())?;
+        let warped = warp_perspective(image, &inverse, image.width(), image.height());
+
+        Ok(Rectification {
+            image: warped,
+            corners,
+            corrected: true,
+        })
+    }
+
+    /// Coins de la grille (haut-gauche, haut-droit, bas-droit, bas-gauche)
+    pub fn find_grid_corners(&self, image: &DynamicImage) -> Result<[Point; 4], String> {
+        let edges = canny(&image.to_luma8(), self.config.canny_low, self.config.canny_high);
+        let lines = hough_lines(&edges, self.config.theta_steps);
+        let strongest = lines.first()
+            .ok_or_else(|| "Aucune droite détectée dans l'image".to_string())?;
+
+        let first = self.family(&lines, strongest.theta);
+        let second = self.family(&lines, strongest.theta + std::f64::consts::FRAC_PI_2);
+        if first.len() < 2 || second.len() < 2 {
+            return Err("Impossible de trouver les bords de la grille".to_string());
+        }
+
+        let (first_min, first_max) = (first[0], first[first.len() - 1]);
+        let (second_min, second_max) = (second[0], second[second.len() - 1]);
+        let mut points = Vec::with_capacity(4);
+        for a in [first_min, first_max] {
+            for b in [second_min, second_max] {
+                points.push(a.intersection(&b)
+                    .ok_or_else(|| "Bords de la grille parallèles".to_string())?);
+            }
+        }
+
+        Ok(order_corners(&points))
+    }
+
+    /// Droites fortes proches d'une orientation, ramenées à cette orientation et triées par ρ
+    fn family(&self, lines: &[HoughLine], theta: f64) -> Vec<HoughLine> {
+        let pi = std::f64::consts::PI;
+        let reference = theta.rem_euclid(pi);
+        let tolerance = self.config.angle_tolerance.to_radians();
+
+        let mut family: Vec<HoughLine> = lines.iter()
+            .filter_map(|line| {
+                let mut delta = line.theta - reference;
+                let mut rho = line.rho;
+                // θ et θ ± π décrivent la même droite avec ρ opposé
+                if delta > pi / 2.0 {
+                    delta -= pi;
+                    rho = -rho;
+                } else if delta < -pi / 2.0 {
+                    delta += pi;
+                    rho = -rho;
+                }
+                (delta.abs() <= tolerance).then_some(HoughLine { rho, theta: reference + delta, votes: line.votes })
+            })
+            .collect();
+
+        let best = family.iter().map(|line| line.votes).max().unwrap_or(0);
+        let min_votes = (best as f32 * self.config.min_votes_ratio) as u32;
+        family.retain(|line| line.votes >= min_votes);
+        family.sort_by(|a, b| a.rho.total_cmp(&b.rho));
+        family
+    }
+}
+
+impl Default for PerspectiveCorrector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Transformée de Hough: droites triées par votes décroissants (maxima locaux)
+pub fn hough_lines(edges: &GrayImage, theta_steps: usize) -> Vec<HoughLine> {
+    let (width, height) = edges.dimensions();
+    let max_rho = ((width as f64).hypot(height as f64)).ceil() as usize;
+    let rho_bins = 2 * max_rho + 1;
+    let thetas: Vec<(f64, f64)> = (0..theta_steps)
+        .map(|i| {
+            let theta = i as f64 * std::f64::consts::PI / theta_steps as f64;
+            (theta.cos(), theta.sin())
+        })
+        .collect();
+
+    // Le flou de `canny` laisse un cadre noir d'un pixel: les bords de l'image sont ignorés
+    let border = 3;
+    let mut accumulator = vec![0u32; theta_steps * rho_bins];
+    for y in border..height.saturating_sub(border) {
+        for x in border..width.saturating_sub(border) {
+            if edges.get_pixel(x, y)[0] <= 128 {
+                continue;
+            }
+            for (t, &(cos, sin)) in thetas.iter().enumerate() {
+                let rho = (x as f64 * cos + y as f64 * sin).round() as isize + max_rho as isize;
+                accumulator[t * rho_bins + rho as usize] += 1;
+            }
+        }
+    }
+
+    let best = accumulator.iter().copied().max().unwrap_or(0);
+    let min_votes = (best / 4).max(10);
+    let mut lines = Vec::new();
+
+    for t in 0..theta_steps {
+        for r in 0..rho_bins {
+            let votes = accumulator[t * rho_bins + r];
+            if votes < min_votes {
+                continue;
+            }
+            // Maximum local sur un voisinage (angle circulaire)
+            let is_peak = (-2isize..=2).all(|dt| (-3isize..=3).all(|dr| {
+                if dt == 0 && dr == 0 {
+                    return true;
+                }
+                let shifted = t as isize + dt;
+                let tt = shifted.rem_euclid(theta_steps as isize) as usize;
+                // Au-delà de π, la même droite a un ρ opposé
+                let base = if shifted < 0 || shifted >= theta_steps as isize { (rho_bins - 1 - r) as isize } else { r as isize };
+                let rr = base + dr;
+                if rr < 0 || rr >= rho_bins as isize {
+                    return true;
+                }
+                let neighbour = accumulator[tt * rho_bins + rr as usize];
+                neighbour < votes || (neighbour == votes && (dt, dr) > (0, 0))
+            }));
+            if is_peak {
+                lines.push(HoughLine {
+                    rho: r as f64 - max_rho as f64,
+                    theta: t as f64 * std::f64::consts::PI / theta_steps as f64,
+                    votes,
+                });
+            }
+        }
+    }
+
+    lines.sort_by_key(|line| std::cmp::Reverse(line.votes));
+    lines
+}
+
+/// Applique une transformation inverse (image de sortie → image source) avec interpolation bilinéaire
+pub fn warp_perspective(image: &DynamicImage, inverse: &Homography, width: u32, height: u32) -> DynamicImage {
+    let source = image.to_rgba8();
+    let white = Rgba([255, 255, 255, 255]);
+    let output = RgbaImage::from_fn(width, height, |x, y| {
+        let (sx, sy) = inverse.apply((x as f64 + 0.5, y as f64 + 0.5));
+        bilinear(&source, sx - 0.5, sy - 0.5).unwrap_or(white)
+    });
+    DynamicImage::ImageRgba8(output)
+}
+
+fn bilinear(image: &RgbaImage, x: f64, y: f64) -> Option<Rgba<u8>> {
+    if !x.is_finite() || !y.is_finite() || x < 0.0 || y < 0.0 {
+        return None;
+    }
+    let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+    if x0 + 1 >= image.width() || y0 + 1 >= image.height() {
+        return None;
+    }
+    let (fx, fy) = (x - x0 as f64, y - y0 as f64);
+    let p = |dx: u32, dy: u32| image.get_pixel(x0 + dx, y0 + dy);
+
+    let mut pixel = [0u8; 4];
+    for (c, value) in pixel.iter_mut().enumerate() {
+        let top = p(0, 0)[c] as f64 * (1.0 - fx) + p(1, 0)[c] as f64 * fx;
+        let bottom = p(0, 1)[c] as f64 * (1.0 - fx) + p(1, 1)[c] as f64 * fx;
+        *value = (top * (1.0 - fy) + bottom * fy).round().clamp(0.0, 255.0) as u8;
+    }
+    Some(Rgba(pixel))
+}
+
+fn distance(a: Point, b: Point) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+/// Ordonne quatre points: haut-gauche, haut-droit, bas-droit, bas-gauche
+fn order_corners(points: &[Point]) -> [Point; 4] {
+    let by = |key: &dyn Fn(&Point) -> f64, largest: bool| {
+        *points.iter()
+            .max_by(|a, b| {
+                let ordering = key(a).total_cmp(&key(b));
+                if largest { ordering } else { ordering.reverse() }
+            })
+            .expect("quatre points attendus")
+    };
+    [
+        by(&|p| p.0 + p.1, false),
+        by(&|p| p.0 - p.1, true),
+        by(&|p| p.0 + p.1, true),
+        by(&|p| p.0 - p.1, false),
+    ]
+}
+
+/// Élimination de Gauss avec pivot partiel sur un système 8x8 augmenté
+fn solve_linear_system(mut system: [[f64; 9]; 8]) -> Option<[f64; 8]> {
+    for col in 0..8 {
+        let pivot = (col..8).max_by(|&a, &b| system[a][col].abs().total_cmp(&system[b][col].abs()))?;
+        if system[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        system.swap(col, pivot);
+        let pivot_row = system[col];
+        for (row, equation) in system.iter_mut().enumerate() {
+            if row != col {
+                let factor = equation[col] / pivot_row[col];
+                for (value, pivot_value) in equation.iter_mut().zip(pivot_row.iter()).skip(col) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+    }
+
+    let mut solution = [0.0; 8];
+    for (i, value) in solution.iter_mut().enumerate() {
+        *value = system[i][8] / system[i][i];
+    }
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_homography_maps_points_and_inverts() {
+        let from = [(10.0, 20.0), (110.0, 15.0), (120.0, 130.0), (5.0, 110.0)];
+        let to = [(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)];
+        let homography = Homography::from_points(&from, &to).unwrap();
+        let inverse = homography.inverse().unwrap();
+
+        for (&a, &b) in from.iter().zip(to.iter()) {
+            assert!(distance(homography.apply(a), b) < 1e-6);
+            assert!(distance(inverse.apply(b), a) < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_rectify_tilted_grid() {
+        // Grille 5x5 de 30 px, tournée de 8° autour du centre de l'image et vue de biais
+        let grid = RgbaImage::from_fn(260, 260, |x, y| {
+            let on_line = |v: u32| (50..=200).contains(&v) && (v - 50) % 30 <= 1;
+            let inside = |v: u32| (50..=201).contains(&v);
+            if (on_line(x) && inside(y)) || (on_line(y) && inside(x)) {
+                Rgba([0, 0, 0, 255])
+            } else {
+                Rgba([255, 255, 255, 255])
+            }
+        });
+        let angle = 8f64.to_radians();
+        let (c, s) = (angle.cos(), angle.sin());
+        let rotation = Homography {
+            matrix: [[c, -s, 130.0 - 130.0 * c + 130.0 * s], [s, c, 130.0 - 130.0 * s - 130.0 * c], [0.0005, 0.0, 0.95]],
+        };
+        let rotated = warp_perspective(&DynamicImage::ImageRgba8(grid), &rotation.inverse().unwrap(), 260, 260);
+
+        let corrector = PerspectiveCorrector::new();
+        let corners = corrector.find_grid_corners(&rotated).unwrap();
+        let expected = rotation.apply((50.5, 50.5));
+        assert!(distance(corners[0], expected) < 4.0, "{:?} vs {:?}", corners[0], expected);
+
+        let rectified = corrector.rectify(&rotated).unwrap();
+        assert!(rectified.corrected);
+        let straightened = corrector.find_grid_corners(&rectified.image).unwrap();
+        assert!((straightened[0].1 - straightened[1].1).abs() < 2.0);
+        assert!((straightened[0].0 - straightened[3].0).abs() < 2.0);
+    }
+}
@@ -0,0 +1,328 @@
+//! Module de jeu: le joueur remplit la grille, aidé d'indices fournis par le solveur
+//! Coups (clic, glisser) → état des lignes → indice → détection de la fin de partie
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use crate::checker::LineKind;
+use crate::grid::{CellState, Constraints, Grid, MoveLog, MoveSource};
+use crate::solver::{NonogramSolver, OptimizedLineSolver, SolutionCount, SolutionSearch};
+
+/// Limite de nœuds de la recherche de la solution unique
+const MAX_SEARCH_NODES: usize = 20000;
+
+/// Outil du joueur
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Fill,
+    Cross,
+}
+
+impl Tool {
+    fn state(&self) -> CellState {
+        match self {
+            Tool::Fill => CellState::Filled,
+            Tool::Cross => CellState::Crossed,
+        }
+    }
+}
+
+/// État d'une ligne ou d'une colonne pendant la partie
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineState {
+    /// Encore à compléter
+    Open,
+    /// Les blocs noirs correspondent exactement à l'indice
+    Satisfied,
+    /// Les cases marquées ne peuvent plus respecter l'indice
+    Error,
+}
+
+/// Raison d'un indice
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HintReason {
+    /// Case marquée à tort (d'après la solution unique), effacée
+    Mistake,
+    /// Case déduite par la logique des lignes
+    Deduction,
+    /// Aucune déduction possible: case révélée depuis la solution
+    Reveal,
+}
+
+/// Indice appliqué à la grille
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Hint {
+    pub row: usize,
+    pub col: usize,
+    pub state: CellState,
+    pub reason: HintReason,
+}
+
+/// Partie en cours
+#[derive(Debug, Clone)]
+pub struct PlaySession {
+    constraints: Constraints,
+    /// Coups joués depuis la grille de départ
+    log: MoveLog,
+    /// Solution, si elle est unique
+    solution: Option<Grid>,
+    started: Instant,
+    /// Durée de la partie une fois la grille terminée
+    finished: Option<Duration>,
+    /// État appliqué aux cases parcourues pendant un glisser
+    stroke: Option<CellState>,
+}
+
+impl PlaySession {
+    /// Démarre une partie à partir de la grille lue (cases déjà marquées conservées)
+    pub fn new(constraints: Constraints, start: Grid) -> Result<Self, String> {
+        Self::with_cancel_flag(constraints, start, Arc::new(AtomicBool::new(false)))
+    }
+
+    /// Comme `new`; une fois le drapeau levé, la recherche de la solution unique s'arrête
+    /// et la partie démarre sans solution connue
+    pub fn with_cancel_flag(constraints: Constraints, start: Grid, cancel: Arc<AtomicBool>) -> Result<Self, String> {
+        if start.width() != constraints.width || start.height() != constraints.height {
+            return Err(format!(
+                "Dimensions incompatibles: grille {}x{}, contraintes {}x{}",
+                start.width(), start.height(), constraints.width, constraints.height
+            ));
+        }
+
+        let blank = Grid::new(constraints.width, constraints.height);
+        let mut search = SolutionSearch::with_max_nodes(MAX_SEARCH_NODES);
+        search.set_cancel_flag(cancel);
+        let solution = match search.count(&blank, &constraints)? {
+            SolutionCount::Unique(solution) => Some(solution),
+            _ => None,
+        };
+
+        let mut session = Self {
+            constraints,
+            log: MoveLog::new(start),
+            solution,
+            started: Instant::now(),
+            finished: None,
+            stroke: None,
+        };
+        session.update_finished();
+        Ok(session)
+    }
+
+    pub fn grid(&self) -> &Grid {
+        self.log.grid()
+    }
+
+    /// Journal des coups (pour sauvegarder ou rejouer la partie)
+    pub fn log(&self) -> &MoveLog {
+        &self.log
+    }
+
+    pub fn constraints(&self) -> &Constraints {
+        &self.constraints
+    }
+
+    /// La fin de partie est vérifiée par rapport à une solution unique
+    pub fn has_unique_solution(&self) -> bool {
+        self.solution.is_some()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.finished.is_some()
+    }
+
+    /// Temps de jeu (figé une fois la grille terminée)
+    pub fn elapsed(&self) -> Duration {
+        self.finished.unwrap_or_else(|| self.started.elapsed())
+    }
+
+    /// Clic sur une case: applique l'outil, ou efface si la case porte déjà cette marque.
+    /// Le même état est ensuite appliqué aux cases parcourues par `drag`.
+    pub fn press(&mut self, row: usize, col: usize, tool: Tool) -> bool {
+        let Some(current) = self.grid().get(row, col) else {
+            return false;
+        };
+        let target = if current == tool.state() { CellState::Empty } else { tool.state() };
+        let changed = self.apply(row, col, target, MoveSource::Player);
+        self.stroke = changed.then_some(target);
+        changed
+    }
+
+    /// Glisser sur une case pendant un clic (fait partie du même coup que le clic)
+    pub fn drag(&mut self, row: usize, col: usize) -> bool {
+        let Some(target) = self.stroke else {
+            return false;
+        };
+        if self.is_complete() || !self.log.extend(row, col, target).unwrap_or(false) {
+            return false;
+        }
+        self.update_finished();
+        true
+    }
+
+    /// Fin du glisser
+    pub fn release(&mut self) {
+        self.stroke = None;
+    }
+
+    /// Annule le dernier coup (un glisser entier ou un indice)
+    pub fn undo(&mut self) -> bool {
+        self.stroke = None;
+        !self.is_complete() && self.log.undo().is_some()
+    }
+
+    /// Rétablit le dernier coup annulé; peut terminer la grille
+    pub fn redo(&mut self) -> bool {
+        self.stroke = None;
+        if self.is_complete() || self.log.redo().is_none() {
+            return false;
+        }
+        self.update_finished();
+        true
+    }
+
+    /// État d'une ligne ou d'une colonne
+    pub fn line_state(&self, kind: LineKind, index: usize) -> LineState {
+        let (line, clue) = match kind {
+            LineKind::Row => (self.grid().get_row(index), self.constraints.get_row_constraint(index)),
+            LineKind::Column => (self.grid().get_column(index), self.constraints.get_column_constraint(index)),
+        };
+        let (Some(line), Some(clue)) = (line, clue) else {
+            return LineState::Open;
+        };
+        let clue: Vec<usize> = clue.iter().copied().filter(|&block| block > 0).collect();
+
+        if OptimizedLineSolver::new().fill_probabilities(&line, &clue).is_err() {
+            LineState::Error
+        } else if Constraints::line_clue(&line) == clue {
+            LineState::Satisfied
+        } else {
+            LineState::Open
+        }
+    }
+
+    /// Donne et applique un indice: d'abord une erreur à effacer, puis la prochaine
+    /// déduction logique, et en dernier recours une case de la solution
+    pub fn hint(&mut self) -> Option<Hint> {
+        if self.is_complete() {
+            return None;
+        }
+        let hint = self.find_hint()?;
+        self.apply(hint.row, hint.col, hint.state, MoveSource::Hint);
+        Some(hint)
+    }
+
+    fn find_hint(&self) -> Option<Hint> {
+        let grid = self.grid();
+        let cells = || (0..grid.height()).flat_map(|row| (0..grid.width()).map(move |col| (row, col)));
+
+        if let Some(solution) = &self.solution {
+            let mistake = cells().find(|&(row, col)| {
+                let actual = grid.get(row, col);
+                actual != Some(CellState::Empty) && actual != solution.get(row, col)
+            });
+            if let Some((row, col)) = mistake {
+                return Some(Hint { row, col, state: CellState::Empty, reason: HintReason::Mistake });
+            }
+        }
+
+        if let Ok(deductions) = NonogramSolver::new().find_next_deductions(grid, &self.constraints) {
+            if let Some(deduction) = deductions.first() {
+                return Some(Hint { row: deduction.row, col: deduction.col, state: deduction.state, reason: HintReason::Deduction });
+            }
+        }
+
+        let solution = self.solution.as_ref()?;
+        cells()
+            .find(|&(row, col)| grid.get(row, col) == Some(CellState::Empty) && solution.get(row, col) == Some(CellState::Filled))
+            .map(|(row, col)| Hint { row, col, state: CellState::Filled, reason: HintReason::Reveal })
+    }
+
+    /// Joue un coup sur une case (sauf en fin de partie); renvoie vrai si elle a changé
+    fn apply(&mut self, row: usize, col: usize, state: CellState, source: MoveSource) -> bool {
+        if self.is_complete() || !self.log.play(row, col, state, source).unwrap_or(false) {
+            return false;
+        }
+        self.update_finished();
+        true
+    }
+
+    /// Grille terminée: cases noires identiques à la solution unique, sinon tous les indices respectés
+    fn update_finished(&mut self) {
+        let grid = self.grid();
+        let complete = match &self.solution {
+            Some(solution) => (0..grid.height()).all(|row| (0..grid.width()).all(|col| {
+                (grid.get(row, col) == Some(CellState::Filled)) == (solution.get(row, col) == Some(CellState::Filled))
+            })),
+            None => (0..grid.height()).all(|row| self.line_state(LineKind::Row, row) == LineState::Satisfied)
+                && (0..grid.width()).all(|col| self.line_state(LineKind::Column, col) == LineState::Satisfied),
+        };
+        if complete && self.finished.is_none() {
+            self.finished = Some(self.started.elapsed());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cross() -> PlaySession {
+        let constraints = Constraints::new(3, 3, vec![vec![1], vec![3], vec![1]], vec![vec![1], vec![3], vec![1]]).unwrap();
+        PlaySession::new(constraints, Grid::new(3, 3)).unwrap()
+    }
+
+    #[test]
+    fn test_drag_and_line_states() {
+        let mut session = cross();
+        assert!(session.has_unique_solution());
+
+        // Glisser sur la ligne du milieu: les trois cases sont noircies
+        assert!(session.press(1, 0, Tool::Fill));
+        assert!(session.drag(1, 1));
+        assert!(session.drag(1, 2));
+        session.release();
+        assert!(!session.drag(0, 0));
+        assert_eq!(session.line_state(LineKind::Row, 1), LineState::Satisfied);
+        assert_eq!(session.line_state(LineKind::Column, 1), LineState::Open);
+
+        // Le glisser entier s'annule et se rétablit d'un coup
+        assert!(session.undo());
+        assert_eq!(session.grid().count_filled_cells(), 0);
+        assert!(session.redo());
+        assert_eq!(session.log().moves().len(), 1);
+
+        // Noircir (0, 0) forme un bloc de 2 dans la première colonne (indice « 1 »)
+        session.press(0, 0, Tool::Fill);
+        session.release();
+        assert_eq!(session.line_state(LineKind::Column, 0), LineState::Error);
+
+        // Un second clic avec le même outil efface la case
+        session.press(0, 0, Tool::Fill);
+        session.release();
+        assert_eq!(session.grid().get(0, 0), Some(CellState::Empty));
+        assert!(!session.is_complete());
+    }
+
+    #[test]
+    fn test_hints_fix_mistakes_and_finish_the_grid() {
+        let mut session = cross();
+        session.press(0, 0, Tool::Fill);
+        session.release();
+
+        let first = session.hint().unwrap();
+        assert_eq!((first.row, first.col, first.reason), (0, 0, HintReason::Mistake));
+
+        let mut hints = 1;
+        while session.hint().is_some() {
+            hints += 1;
+            assert!(hints < 20);
+        }
+        assert!(session.is_complete());
+        let elapsed = session.elapsed();
+        assert_eq!(session.elapsed(), elapsed);
+        assert!(!session.press(2, 2, Tool::Fill));
+    }
+}
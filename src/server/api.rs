@@ -0,0 +1,409 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use crate::benchmark::SimpleRng;
+use crate::checker::{Checker, CheckerConfig};
+use crate::editor::{PuzzleDesign, Uniqueness};
+use crate::grid::{Constraints, Grid};
+use crate::image_parser::ImageParser;
+use crate::layout::LayoutAnalyzer;
+use crate::ocr::AdvancedConstraintExtractor;
+use crate::ocr::reconcile::ClueReconciler;
+use crate::play::{Hint, PlaySession};
+use crate::solver::{
+    AdvancedSolver, Deduction, DifficultyRater, NonogramSolver, PortfolioSolver, Rating, SolveReport, UltimateSolver,
+};
+
+/// Taille maximale des grilles reçues (largeur et hauteur)
+const MAX_GRID_SIZE: usize = 100;
+/// Nombre maximal de nonogrammes générés par requête
+const MAX_GENERATED: usize = 20;
+/// Grilles aléatoires essayées par nonogramme généré avant d'abandonner
+const GENERATE_ATTEMPTS: usize = 100;
+
+/// Routes de l'API: méthode, chemin, description
+pub const ENDPOINTS: [(&str, &str, &str); 7] = [
+    ("GET", "/health", "État du serveur"),
+    ("POST", "/solve", "Résout des contraintes (grille de départ facultative)"),
+    ("POST", "/solve/image", "Résout une image encodée en base64 (indices lus sur l'image si absents)"),
+    ("POST", "/hint", "Prochain indice pour une grille en cours"),
+    ("POST", "/check", "Vérifie une grille soumise (même rapport que `check --json`)"),
+    ("POST", "/rate", "Évalue la difficulté"),
+    ("POST", "/generate", "Génère des nonogrammes aléatoires à solution unique"),
+];
+
+/// Réponse JSON et son code HTTP
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl ApiResponse {
+    pub fn json<T: Serialize>(value: &T) -> Self {
+        match serde_json::to_string_pretty(value) {
+            Ok(body) => Self { status: 200, body },
+            Err(e) => Self::error(500, format!("Erreur de sérialisation: {}", e)),
+        }
+    }
+
+    /// Erreur au format `{"error": "..."}`
+    pub fn error(status: u16, message: impl Into<String>) -> Self {
+        let body = serde_json::json!({ "error": message.into() }).to_string();
+        Self { status, body }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SolveRequest {
+    constraints: Constraints,
+    /// Grille de départ au format texte (`#`, `x`, `.`), blanche si absente
+    grid: Option<String>,
+    /// `basic` (par défaut), `advanced`, `ultimate` ou `portfolio`
+    solver: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImageRequest {
+    /// Image encodée en base64 (préfixe `data:image/...;base64,` accepté)
+    image: String,
+    /// Indices connus; lus sur l'image s'ils sont absents
+    constraints: Option<Constraints>,
+    solver: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HintRequest {
+    constraints: Constraints,
+    grid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRequest {
+    constraints: Constraints,
+    grid: String,
+    /// Soumission terminée: les cases non marquées sont blanches
+    #[serde(default)]
+    complete: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateRequest {
+    constraints: Constraints,
+}
+
+#[derive(Debug, Deserialize)]
+struct GenerateRequest {
+    width: usize,
+    height: usize,
+    count: Option<usize>,
+    /// Proportion de cases noires
+    density: Option<f64>,
+    seed: Option<u64>,
+    /// N'accepter que les grilles à solution unique (vrai par défaut)
+    unique: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+struct HintResponse {
+    /// Aucun indice si la grille est terminée ou bloquée sans solution unique
+    hint: Option<Hint>,
+    /// Grille après application de l'indice
+    grid: Vec<String>,
+    complete: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct GeneratedPuzzle {
+    constraints: Constraints,
+    /// Solution, une rangée par chaîne (`#` noire, `.` blanche)
+    solution: Vec<String>,
+    unique: bool,
+    rating: Rating,
+}
+
+#[derive(Debug, Serialize)]
+struct GenerateResponse {
+    /// Graine utilisée, pour regénérer les mêmes grilles
+    seed: u64,
+    puzzles: Vec<GeneratedPuzzle>,
+}
+
+/// Traite une requête déjà lue (sans réseau, testable directement). Les calculs longs
+/// (résolution, évaluation, génération) s'arrêtent dès que `cancel` est levé.
+pub fn handle(method: &str, url: &str, body: &[u8], cancel: &Arc<AtomicBool>) -> ApiResponse {
+    let path = url.split('?').next().unwrap_or(url).trim_end_matches('/');
+    let result = match (method, path) {
+        ("GET", "" | "/health") => Ok(health()),
+        ("POST", "/solve") => parse(body).and_then(|request| solve(request, cancel)),
+        ("POST", "/solve/image") => parse(body).and_then(|request| solve_image(request, cancel)),
+        ("POST", "/hint") => parse(body).and_then(|request| hint(request, cancel)),
+        ("POST", "/check") => parse(body).and_then(|request| check(request, cancel)),
+        ("POST", "/rate") => parse(body).and_then(|request| rate(request, cancel)),
+        ("POST", "/generate") => parse(body).and_then(|request| generate(request, cancel)),
+        _ if ENDPOINTS.iter().any(|&(_, endpoint, _)| endpoint == path) => {
+            return ApiResponse::error(405, format!("Méthode {} non prise en charge pour {}", method, path));
+        }
+        _ => return ApiResponse::error(404, format!("Route inconnue: {}", path)),
+    };
+    result.unwrap_or_else(|e| ApiResponse::error(400, e))
+}
+
+fn health() -> ApiResponse {
+    let endpoints: Vec<_> = ENDPOINTS.iter()
+        .map(|&(method, path, description)| serde_json::json!({ "method": method, "path": path, "description": description }))
+        .collect();
+    ApiResponse::json(&serde_json::json!({
+        "status": "ok",
+        "version": env!("CARGO_PKG_VERSION"),
+        "endpoints": endpoints,
+    }))
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(body: &[u8]) -> Result<T, String> {
+    serde_json::from_slice(body).map_err(|e| format!("Requête JSON invalide: {}", e))
+}
+
+/// Contraintes reçues, revérifiées comme celles lues dans un fichier
+fn validate(constraints: Constraints) -> Result<Constraints, String> {
+    if constraints.width > MAX_GRID_SIZE || constraints.height > MAX_GRID_SIZE {
+        return Err(format!(
+            "Grille trop grande: {}x{} (au plus {} cases de côté)",
+            constraints.width, constraints.height, MAX_GRID_SIZE
+        ));
+    }
+    Constraints::new(constraints.width, constraints.height, constraints.rows, constraints.columns)
+}
+
+/// Grille de départ au format texte, ou grille blanche
+fn start_grid(text: Option<&str>, constraints: &Constraints) -> Result<Grid, String> {
+    let Some(text) = text else {
+        return Ok(Grid::new(constraints.width, constraints.height));
+    };
+    let grid = Grid::from_text(text)?;
+    if grid.width() != constraints.width || grid.height() != constraints.height {
+        return Err(format!(
+            "Dimensions incompatibles: grille {}x{}, contraintes {}x{}",
+            grid.width(), grid.height(), constraints.width, constraints.height
+        ));
+    }
+    Ok(grid)
+}
+
+/// Résout avec le solveur demandé, comme les options `--advanced`, `--ultimate` et `--portfolio`
+/// (la logique ligne par ligne, toujours rapide, n'est pas interrompue)
+fn run_solver(grid: &mut Grid, constraints: &Constraints, solver: &str, cancel: &Arc<AtomicBool>) -> Result<Vec<Deduction>, String> {
+    match solver {
+        "basic" => NonogramSolver::new().solve(grid, constraints),
+        "advanced" => {
+            let mut solver = AdvancedSolver::new();
+            solver.set_cancel_flag(Arc::clone(cancel));
+            solver.solve(grid, constraints)
+        }
+        "ultimate" => {
+            let mut solver = UltimateSolver::new();
+            solver.set_cancel_flag(Arc::clone(cancel));
+            solver.solve(grid, constraints)
+        }
+        "portfolio" => {
+            let mut solver = PortfolioSolver::new();
+            solver.set_cancel_flag(Arc::clone(cancel));
+            solver.solve(grid, constraints)
+        }
+        other => Err(format!("Solveur inconnu: {} (basic, advanced, ultimate ou portfolio)", other)),
+    }
+}
+
+fn report(mut grid: Grid, constraints: &Constraints, solver: Option<&str>, cancel: &Arc<AtomicBool>) -> Result<ApiResponse, String> {
+    let solver = solver.unwrap_or("basic");
+    let started = Instant::now();
+    let deductions = run_solver(&mut grid, constraints, solver, cancel)
+        .map_err(|e| format!("Erreur lors de la résolution: {}", e))?;
+    Ok(ApiResponse::json(&SolveReport::new(solver, &grid, constraints, deductions, started.elapsed())))
+}
+
+fn solve(request: SolveRequest, cancel: &Arc<AtomicBool>) -> Result<ApiResponse, String> {
+    let constraints = validate(request.constraints)?;
+    let grid = start_grid(request.grid.as_deref(), &constraints)?;
+    report(grid, &constraints, request.solver.as_deref(), cancel)
+}
+
+fn solve_image(request: ImageRequest, cancel: &Arc<AtomicBool>) -> Result<ApiResponse, String> {
+    let encoded = match request.image.split_once(";base64,") {
+        Some((_, data)) => data,
+        None => request.image.as_str(),
+    };
+    let bytes = base64::engine::general_purpose::STANDARD.decode(encoded.trim())
+        .map_err(|e| format!("Image base64 invalide: {}", e))?;
+    let image = image::load_from_memory(&bytes)
+        .map_err(|e| format!("Erreur lors du chargement de l'image: {}", e))?;
+
+    // Mêmes étapes que `--auto`: mise en page, lecteur de chiffres, traits de la grille
+    let (constraints, parser_config) = match request.constraints {
+        Some(constraints) => {
+            let constraints = validate(constraints)?;
+            let config = ImageParser::auto_detect_config(&image, constraints.width, constraints.height)
+                .map_err(|e| format!("Erreur lors de la détection automatique de la configuration: {}", e))?;
+            (constraints, config)
+        }
+        None => {
+            let layout = LayoutAnalyzer::new().analyze(&image)
+                .map_err(|e| format!("Erreur lors de l'analyse de la mise en page: {}. Fournissez les contraintes", e))?;
+            let mut reconciler = ClueReconciler::new();
+            reconciler.set_cancel_flag(Arc::clone(cancel));
+            let constraints = AdvancedConstraintExtractor::extract_with_reconciler(&image, &layout, &reconciler).constraints()
+                .map_err(|e| format!("Indices illisibles: {}. Fournissez les contraintes", e))?;
+            (validate(constraints)?, layout.parser_config())
+        }
+    };
+
+    let grid = ImageParser::new(parser_config).parse_image(&image, constraints.width, constraints.height)
+        .map_err(|e| format!("Erreur lors du parsing de l'image: {}", e))?;
+    report(grid, &constraints, request.solver.as_deref(), cancel)
+}
+
+fn hint(request: HintRequest, cancel: &Arc<AtomicBool>) -> Result<ApiResponse, String> {
+    let constraints = validate(request.constraints)?;
+    let grid = start_grid(request.grid.as_deref(), &constraints)?;
+    let mut session = PlaySession::with_cancel_flag(constraints, grid, Arc::clone(cancel))?;
+    let hint = session.hint();
+    Ok(ApiResponse::json(&HintResponse {
+        hint,
+        grid: session.grid().to_text().lines().map(str::to_string).collect(),
+        complete: session.is_complete(),
+    }))
+}
+
+fn check(request: CheckRequest, cancel: &Arc<AtomicBool>) -> Result<ApiResponse, String> {
+    let constraints = validate(request.constraints)?;
+    let grid = start_grid(Some(&request.grid), &constraints)?;
+    let mut checker = Checker::with_config(CheckerConfig {
+        treat_empty_as_crossed: request.complete,
+        ..Default::default()
+    });
+    checker.set_cancel_flag(Arc::clone(cancel));
+    let report = checker.check(&grid, &constraints)
+        .map_err(|e| format!("Erreur lors de la vérification: {}", e))?;
+    Ok(ApiResponse::json(&report))
+}
+
+fn rate(request: RateRequest, cancel: &Arc<AtomicBool>) -> Result<ApiResponse, String> {
+    let constraints = validate(request.constraints)?;
+    let mut rater = DifficultyRater::new();
+    rater.set_cancel_flag(Arc::clone(cancel));
+    let rating = rater.rate(&constraints)
+        .map_err(|e| format!("Erreur lors de l'évaluation: {}", e))?;
+    Ok(ApiResponse::json(&rating))
+}
+
+fn generate(request: GenerateRequest, cancel: &Arc<AtomicBool>) -> Result<ApiResponse, String> {
+    let count = request.count.unwrap_or(1);
+    if !(1..=MAX_GENERATED).contains(&count) {
+        return Err(format!("Nombre de nonogrammes invalide: {} (de 1 à {})", count, MAX_GENERATED));
+    }
+    let density = request.density.unwrap_or(0.6);
+    if !(0.0..=1.0).contains(&density) {
+        return Err(format!("Densité invalide: {} (entre 0 et 1)", density));
+    }
+    let unique = request.unique.unwrap_or(true);
+    let seed = request.seed.unwrap_or_else(|| {
+        SystemTime::now().duration_since(UNIX_EPOCH).map_or(1, |elapsed| elapsed.as_nanos() as u64)
+    });
+
+    let mut rng = SimpleRng::new(seed);
+    let mut puzzles = Vec::with_capacity(count);
+    while puzzles.len() < count {
+        let puzzle = (0..GENERATE_ATTEMPTS)
+            .map(|_| {
+                if cancel.load(Ordering::Relaxed) {
+                    return Err("Génération annulée".to_string());
+                }
+                random_puzzle(request.width, request.height, density, &mut rng, cancel)
+            })
+            .find(|puzzle| puzzle.as_ref().map_or(true, |puzzle| puzzle.unique || !unique))
+            .transpose()?
+            .ok_or_else(|| format!(
+                "Aucune grille à solution unique trouvée en {} essais (essayez une densité plus élevée)",
+                GENERATE_ATTEMPTS
+            ))?;
+        puzzles.push(puzzle);
+    }
+    Ok(ApiResponse::json(&GenerateResponse { seed, puzzles }))
+}
+
+/// Solution tirée au hasard, ses indices, son unicité et sa difficulté
+fn random_puzzle(width: usize, height: usize, density: f64, rng: &mut SimpleRng, cancel: &Arc<AtomicBool>) -> Result<GeneratedPuzzle, String> {
+    let mut design = PuzzleDesign::new(width, height)?;
+    design.set_cancel_flag(Arc::clone(cancel));
+    for row in 0..height {
+        for col in 0..width {
+            design.paint(row, col, rng.next_f64() < density);
+        }
+    }
+    Ok(GeneratedPuzzle {
+        constraints: design.constraints(),
+        solution: design.solution().to_text().lines().map(str::to_string).collect(),
+        unique: design.check_uniqueness()? == Uniqueness::Unique,
+        rating: design.rate()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CROSS: &str = r#"{"width": 3, "height": 3, "rows": [[1], [3], [1]], "columns": [[1], [3], [1]]}"#;
+
+    fn call(method: &str, path: &str, body: &[u8]) -> ApiResponse {
+        handle(method, path, body, &Arc::new(AtomicBool::new(false)))
+    }
+
+    fn post(path: &str, body: String) -> (u16, serde_json::Value) {
+        let response = call("POST", path, body.as_bytes());
+        (response.status, serde_json::from_str(&response.body).unwrap())
+    }
+
+    #[test]
+    fn test_solve_check_hint_and_rate() {
+        let (status, report) = post("/solve", format!(r#"{{"constraints": {}}}"#, CROSS));
+        assert_eq!(status, 200);
+        assert_eq!(report["solved"], true);
+        assert_eq!(report["grid"], serde_json::json!(["x#x", "###", "x#x"]));
+
+        let (_, check) = post("/check", format!(r#"{{"constraints": {}, "grid": "x#x\n###\nx#x"}}"#, CROSS));
+        assert_eq!(check["solved"], true);
+        assert_eq!(check["unique_solution"], true);
+
+        let (_, hint) = post("/hint", format!(r##"{{"constraints": {}, "grid": "#..\n...\n..."}}"##, CROSS));
+        assert_eq!(hint["hint"]["reason"], "mistake");
+        assert_eq!(hint["grid"][0], "...");
+
+        let (_, rating) = post("/rate", format!(r#"{{"constraints": {}}}"#, CROSS));
+        assert_eq!(rating["difficulty"], "easy");
+    }
+
+    #[test]
+    fn test_errors_and_generation() {
+        assert_eq!(call("GET", "/health", b"").status, 200);
+        assert_eq!(call("GET", "/solve", b"").status, 405);
+        assert_eq!(call("POST", "/nowhere", b"{}").status, 404);
+        assert_eq!(post("/solve", "{".to_string()).0, 400);
+        let (status, error) = post("/solve", format!(r#"{{"constraints": {}, "solver": "magic"}}"#, CROSS));
+        assert_eq!(status, 400);
+        assert!(error["error"].as_str().unwrap().contains("magic"));
+
+        // Même graine, mêmes grilles
+        let request = r#"{"width": 5, "height": 5, "count": 2, "seed": 7}"#;
+        let (status, first) = post("/generate", request.to_string());
+        assert_eq!(status, 200);
+        assert_eq!(first["puzzles"].as_array().unwrap().len(), 2);
+        assert_eq!(first["puzzles"][0]["unique"], true);
+        assert_eq!(first, post("/generate", request.to_string()).1);
+
+        // Drapeau d'annulation levé: la génération s'arrête sans essayer de grille
+        let cancelled = handle("POST", "/generate", request.as_bytes(), &Arc::new(AtomicBool::new(true)));
+        assert!(cancelled.body.contains("annulée"));
+    }
+}
@@ -0,0 +1,279 @@
+//! Serveur HTTP local: API JSON du solveur pour le front-end web, sans accès au réseau extérieur
+//! Requête (taille limitée) → route (`api::handle`) → traitement dans un thread (durée limitée) → réponse JSON
+//! Un nombre fixe de workers traite les requêtes; les suivantes attendent leur tour.
+//! Un calcul abandonné après le délai garde sa place de calcul jusqu'à son arrêt effectif.
+
+mod api;
+
+pub use api::{handle, ApiResponse, ENDPOINTS};
+
+use std::io::Read;
+use std::net::SocketAddr;
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use tiny_http::{Header, Request, Response, Server};
+
+/// Configuration du serveur
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Port d'écoute sur 127.0.0.1 (0: choisi par le système)
+    pub port: u16,
+    /// Taille maximale du corps d'une requête en octets
+    pub max_body_bytes: usize,
+    /// Durée maximale de traitement d'une requête
+    pub timeout: Duration,
+    /// Nombre de requêtes traitées en même temps (et de calculs en cours au plus)
+    pub workers: usize,
+    pub verbose: bool,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            port: 8080,
+            max_body_bytes: 10 * 1024 * 1024,
+            timeout: Duration::from_secs(30),
+            workers: thread::available_parallelism().map_or(4, |count| count.get()),
+            verbose: false,
+        }
+    }
+}
+
+/// Places de calcul partagées par les workers
+///
+/// Chaque calcul occupe une place jusqu'à sa fin, y compris après le délai: un calcul
+/// qui ne lit pas le drapeau d'annulation ne peut donc pas s'ajouter aux suivants.
+struct ComputeSlots {
+    free: Mutex<usize>,
+    released: Condvar,
+}
+
+/// Place occupée, libérée à sa destruction
+struct ComputeSlot(Arc<ComputeSlots>);
+
+impl ComputeSlots {
+    fn new(count: usize) -> Arc<Self> {
+        Arc::new(Self { free: Mutex::new(count), released: Condvar::new() })
+    }
+
+    /// Attend une place libre pendant `timeout` au plus
+    fn acquire(self: &Arc<Self>, timeout: Duration) -> Option<ComputeSlot> {
+        let free = self.free.lock().unwrap_or_else(|e| e.into_inner());
+        let (mut free, _) = self.released.wait_timeout_while(free, timeout, |free| *free == 0)
+            .unwrap_or_else(|e| e.into_inner());
+        if *free == 0 {
+            return None;
+        }
+        *free -= 1;
+        Some(ComputeSlot(Arc::clone(self)))
+    }
+}
+
+impl Drop for ComputeSlot {
+    fn drop(&mut self) {
+        *self.0.free.lock().unwrap_or_else(|e| e.into_inner()) += 1;
+        self.0.released.notify_one();
+    }
+}
+
+/// Serveur de l'API JSON, à l'écoute de la boucle locale uniquement
+pub struct ApiServer {
+    config: ServerConfig,
+}
+
+impl ApiServer {
+    pub fn new() -> Self {
+        Self::with_config(ServerConfig::default())
+    }
+
+    pub fn with_config(config: ServerConfig) -> Self {
+        Self { config }
+    }
+
+    /// Ouvre le port d'écoute sur 127.0.0.1
+    pub fn bind(&self) -> Result<Server, String> {
+        let address = SocketAddr::from(([127, 0, 0, 1], self.config.port));
+        Server::http(address)
+            .map_err(|e| format!("Impossible d'écouter sur {}: {}", address, e))
+    }
+
+    /// Répond aux requêtes jusqu'à l'arrêt du processus avec `workers` threads
+    pub fn serve(&self, server: Server) {
+        let server = Arc::new(server);
+        let slots = ComputeSlots::new(self.config.workers.max(1));
+        let workers: Vec<_> = (0..self.config.workers.max(1))
+            .map(|_| {
+                let server = Arc::clone(&server);
+                let slots = Arc::clone(&slots);
+                let config = self.config.clone();
+                thread::spawn(move || {
+                    for request in server.incoming_requests() {
+                        Self::respond(&config, &slots, request);
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            let _ = worker.join();
+        }
+    }
+
+    pub fn run(&self) -> Result<(), String> {
+        let server = self.bind()?;
+        println!("🌐 API à l'écoute sur http://{}", server.server_addr());
+        if self.config.verbose {
+            println!("   {} requêtes traitées en parallèle, {} s au plus chacune", self.config.workers.max(1), self.config.timeout.as_secs_f32());
+            for (method, path, description) in ENDPOINTS {
+                println!("   - {} {}: {}", method, path, description);
+            }
+        }
+        self.serve(server);
+        Ok(())
+    }
+
+    fn respond(config: &ServerConfig, slots: &Arc<ComputeSlots>, mut request: Request) {
+        let method = request.method().as_str().to_uppercase();
+        let url = request.url().to_string();
+        let response = match Self::read_body(config, &mut request) {
+            Ok(body) => Self::process(config, slots, method.clone(), url.clone(), body),
+            Err(response) => response,
+        };
+        if config.verbose {
+            println!("📨 {} {} → {}", method, url, response.status);
+        }
+
+        let content_type = Header::from_bytes("Content-Type", "application/json; charset=utf-8")
+            .expect("en-tête valide");
+        let _ = request.respond(Response::from_string(response.body)
+            .with_status_code(response.status)
+            .with_header(content_type));
+    }
+
+    /// Corps de la requête, refusé au-delà de la taille maximale (annoncée ou réelle)
+    fn read_body(config: &ServerConfig, request: &mut Request) -> Result<Vec<u8>, ApiResponse> {
+        let too_large = || ApiResponse::error(413, format!("Requête trop volumineuse (au plus {} octets)", config.max_body_bytes));
+        if request.body_length().is_some_and(|length| length > config.max_body_bytes) {
+            return Err(too_large());
+        }
+        let mut body = Vec::new();
+        request.as_reader()
+            .take(config.max_body_bytes as u64 + 1)
+            .read_to_end(&mut body)
+            .map_err(|e| ApiResponse::error(400, format!("Erreur lors de la lecture de la requête: {}", e)))?;
+        if body.len() > config.max_body_bytes {
+            return Err(too_large());
+        }
+        Ok(body)
+    }
+
+    /// Traite la requête dans un thread, sur une place de calcul libre; passé le délai
+    /// (attente de la place comprise), le calcul est annulé et la réponse est une erreur 504
+    /// sans attendre son arrêt. Sa place n'est libérée qu'à la fin effective du calcul:
+    /// il n'y a jamais plus de calculs en cours que de workers.
+    fn process(config: &ServerConfig, slots: &Arc<ComputeSlots>, method: String, url: String, body: Vec<u8>) -> ApiResponse {
+        let started = Instant::now();
+        let timed_out = || ApiResponse::error(504, format!("Délai dépassé ({} s)", config.timeout.as_secs_f32()));
+        let Some(slot) = slots.acquire(config.timeout) else {
+            return timed_out();
+        };
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = mpsc::channel();
+        let flag = Arc::clone(&cancel);
+        thread::spawn(move || {
+            let _slot = slot;
+            let _ = sender.send(handle(&method, &url, &body, &flag));
+        });
+        match receiver.recv_timeout(config.timeout.saturating_sub(started.elapsed())) {
+            Ok(response) => response,
+            Err(RecvTimeoutError::Timeout) => {
+                cancel.store(true, Ordering::Relaxed);
+                timed_out()
+            }
+            Err(RecvTimeoutError::Disconnected) => ApiResponse::error(500, "Erreur interne lors du traitement"),
+        }
+    }
+}
+
+impl Default for ApiServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpStream;
+    use std::time::Instant;
+
+    fn send(address: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_serves_on_localhost_with_size_limit() {
+        let server = ApiServer::with_config(ServerConfig { port: 0, max_body_bytes: 64, ..Default::default() });
+        let listener = server.bind().unwrap();
+        let address = listener.server_addr().to_ip().unwrap();
+        assert!(address.ip().is_loopback());
+        thread::spawn(move || server.serve(listener));
+
+        let health = send(address, "GET /health HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+        assert!(health.starts_with("HTTP/1.1 200"));
+        assert!(health.contains("\"status\": \"ok\""));
+
+        let body = format!(r#"{{"constraints": {{"width": 1, "height": 1, "rows": [[1]], "columns": [[1]]}}, "grid": "{}"}}"#, ".".repeat(64));
+        let request = format!(
+            "POST /solve HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(), body
+        );
+        assert!(send(address, &request).starts_with("HTTP/1.1 413"));
+    }
+
+    /// Grille en damier de côté `size`: deux solutions, que la recherche doit trouver par branchement
+    fn checkerboard(size: usize) -> String {
+        let clue = format!("[{}]", vec!["1"; size / 2].join(", "));
+        let clues = vec![clue; size].join(", ");
+        format!(r#"{{"width": {0}, "height": {0}, "rows": [{1}], "columns": [{1}]}}"#, size, clues)
+    }
+
+    #[test]
+    fn test_timeout_cancels_the_computation() {
+        let config = ServerConfig { timeout: Duration::from_millis(1), ..Default::default() };
+        let slots = ComputeSlots::new(1);
+        let body = br#"{"width": 60, "height": 60, "count": 20, "seed": 3}"#.to_vec();
+        let response = ApiServer::process(&config, &slots, "POST".to_string(), "/generate".to_string(), body);
+        assert_eq!(response.status, 504);
+        // La place n'est rendue qu'une fois le calcul arrêté: bien avant la fin des 20 recherches
+        assert!(slots.acquire(Duration::from_secs(10)).is_some());
+    }
+
+    #[test]
+    fn test_timeout_answers_without_waiting_for_the_computation() {
+        let config = ServerConfig { timeout: Duration::from_millis(1), ..Default::default() };
+        let slots = ComputeSlots::new(1);
+        let body = format!(r#"{{"constraints": {}, "grid": "{}"}}"#, checkerboard(60), vec![".".repeat(60); 60].join("\\n")).into_bytes();
+
+        // Aucune place libre: la requête expire sans lancer de calcul
+        let held = slots.acquire(Duration::ZERO).unwrap();
+        let response = ApiServer::process(&config, &slots, "POST".to_string(), "/check".to_string(), body.clone());
+        assert_eq!(response.status, 504);
+        drop(held);
+
+        let started = Instant::now();
+        let response = ApiServer::process(&config, &slots, "POST".to_string(), "/check".to_string(), body);
+        assert_eq!(response.status, 504);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        // Le calcul abandonné lit le drapeau d'annulation et rend sa place
+        assert!(slots.acquire(Duration::from_secs(10)).is_some());
+    }
+}
@@ -64,72 +64,22 @@ impl AdvancedHeuristics {
     }
 
     /// Glue method pour une ligne ou colonne
+    ///
+    /// Avec un seul indice, toutes les cases remplies appartiennent au même bloc:
+    /// les cases entre la première et la dernière sont remplies.
     fn glue_method_line(&self, index: usize, line: &[CellState], constraint: &[usize], is_row: bool) -> Result<Vec<Deduction>, String> {
         let mut deductions = Vec::new();
 
-        if constraint.is_empty() {
+        if constraint.len() != 1 {
             return Ok(deductions);
         }
 
-        let blocks = self.find_filled_blocks(line);
-
-        for (block_start, block_size) in blocks {
-            for &constraint_size in constraint.iter() {
-                if block_size > constraint_size / 2 && block_size < constraint_size {
-                    let missing = constraint_size - block_size;
-                    
-                    // Essayer d'étendre à gauche
-                    if block_start >= missing {
-                        let can_extend_left = (block_start - missing..block_start)
-                            .all(|i| line[i] == CellState::Empty || line[i] == CellState::Filled);
-                        
-                        if can_extend_left {
-                            for i in block_start - missing..block_start {
-                                if line[i] == CellState::Empty {
-                                    if is_row {
-                                        deductions.push(Deduction {
-                                            row: index,
-                                            col: i,
-                                            state: CellState::Filled,
-                                        });
-                                    } else {
-                                        deductions.push(Deduction {
-                                            row: i,
-                                            col: index,
-                                            state: CellState::Filled,
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-
-                    // Essayer d'étendre à droite
-                    let block_end = block_start + block_size;
-                    if block_end + missing <= line.len() {
-                        let can_extend_right = (block_end..block_end + missing)
-                            .all(|i| line[i] == CellState::Empty || line[i] == CellState::Filled);
-                        
-                        if can_extend_right {
-                            for i in block_end..block_end + missing {
-                                if line[i] == CellState::Empty {
-                                    if is_row {
-                                        deductions.push(Deduction {
-                                            row: index,
-                                            col: i,
-                                            state: CellState::Filled,
-                                        });
-                                    } else {
-                                        deductions.push(Deduction {
-                                            row: i,
-                                            col: index,
-                                            state: CellState::Filled,
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
+        let first = line.iter().position(|&cell| cell == CellState::Filled);
+        let last = line.iter().rposition(|&cell| cell == CellState::Filled);
+        if let (Some(first), Some(last)) = (first, last) {
+            for (i, &cell) in line.iter().enumerate().take(last).skip(first) {
+                if cell == CellState::Empty {
+                    deductions.push(Self::deduction(index, i, is_row, CellState::Filled));
                 }
             }
         }
@@ -181,23 +131,17 @@ impl AdvancedHeuristics {
 
         // Pour chaque contrainte, calculer les positions min et max
         for (block_idx, &block_size) in constraint.iter().enumerate() {
-            let mut min_pos = 0;
-            for i in 0..block_idx {
-                min_pos += constraint[i] + 1;
-            }
-
-            let mut max_pos = line.len() - block_size;
-            for i in (block_idx + 1)..constraint.len() {
-                max_pos = max_pos.saturating_sub(constraint[i] + 1);
-            }
+            let min_pos: usize = constraint[..block_idx].iter().map(|&size| size + 1).sum();
+            let max_pos = constraint[block_idx + 1..].iter()
+                .fold(line.len() - block_size, |max_pos, &size| max_pos.saturating_sub(size + 1));
 
             // Si le bloc ne peut pas "couler" beaucoup, il y a chevauchement
             if max_pos < min_pos + block_size {
                 let overlap_start = max_pos;
                 let overlap_end = (min_pos + block_size).min(line.len());
                 
-                for pos in overlap_start..overlap_end {
-                    if line[pos] == CellState::Empty {
+                for (pos, &cell) in line.iter().enumerate().take(overlap_end).skip(overlap_start) {
+                    if cell == CellState::Empty {
                         if is_row {
                             deductions.push(Deduction {
                                 row: index,
@@ -229,26 +173,8 @@ impl AdvancedHeuristics {
                 .ok_or_else(|| format!("Ligne {} non trouvée", row))?;
             let row_constraint = constraints.get_row_constraint(row)
                 .ok_or_else(|| format!("Contrainte de ligne {} non trouvée", row))?;
-            
-            let blocks = self.find_filled_blocks(&line);
-            
-            if blocks.len() > row_constraint.len() {
-                for i in 0..blocks.len() - 1 {
-                    let (start1, size1) = blocks[i];
-                    let (start2, _) = blocks[i + 1];
-                    let block1_end = start1 + size1;
-                    
-                    if start2 == block1_end + 1 {
-                        if line[block1_end] == CellState::Empty {
-                            deductions.push(Deduction {
-                                row,
-                                col: block1_end,
-                                state: CellState::Filled,
-                            });
-                        }
-                    }
-                }
-            }
+
+            deductions.extend(self.joining_line(row, &line, row_constraint, true));
         }
 
         // Pour chaque colonne
@@ -257,31 +183,35 @@ impl AdvancedHeuristics {
                 .ok_or_else(|| format!("Colonne {} non trouvée", col))?;
             let col_constraint = constraints.get_column_constraint(col)
                 .ok_or_else(|| format!("Contrainte de colonne {} non trouvée", col))?;
-            
-            let blocks = self.find_filled_blocks(&column);
-            
-            if blocks.len() > col_constraint.len() {
-                for i in 0..blocks.len() - 1 {
-                    let (start1, size1) = blocks[i];
-                    let (start2, _) = blocks[i + 1];
-                    let block1_end = start1 + size1;
-                    
-                    if start2 == block1_end + 1 {
-                        if column[block1_end] == CellState::Empty {
-                            deductions.push(Deduction {
-                                row: block1_end,
-                                col,
-                                state: CellState::Filled,
-                            });
-                        }
-                    }
-                }
-            }
+
+            deductions.extend(self.joining_line(col, &column, col_constraint, false));
         }
 
         Ok(deductions)
     }
 
+    /// Joining pour une ligne ou colonne
+    ///
+    /// Plus de blocs remplis que d'indices: au moins deux blocs voisins se rejoignent,
+    /// ce qui n'est possible que sans case barrée entre eux. Si une seule paire de blocs
+    /// voisins le permet, ce sont eux qui se rejoignent.
+    fn joining_line(&self, index: usize, line: &[CellState], constraint: &[usize], is_row: bool) -> Vec<Deduction> {
+        let blocks = self.find_filled_blocks(line);
+        if blocks.len() <= constraint.len() {
+            return Vec::new();
+        }
+
+        let mut joinable = blocks.windows(2)
+            .map(|pair| (pair[0].0 + pair[0].1, pair[1].0))
+            .filter(|&(gap_start, gap_end)| line[gap_start..gap_end].iter().all(|&cell| cell == CellState::Empty));
+        match (joinable.next(), joinable.next()) {
+            (Some((gap_start, gap_end)), None) => (gap_start..gap_end)
+                .map(|i| Self::deduction(index, i, is_row, CellState::Filled))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
     /// Puncturing: Identifie les cases qui doivent être barrées
     fn puncturing(&self, grid: &Grid, constraints: &Constraints) -> Result<Vec<Deduction>, String> {
         let mut deductions = Vec::new();
@@ -300,8 +230,8 @@ impl AdvancedHeuristics {
                     .all(|((_, size), &constraint)| *size == constraint);
                 
                 if all_match {
-                    for col in 0..line.len() {
-                        if line[col] == CellState::Empty {
+                    for (col, &cell) in line.iter().enumerate() {
+                        if cell == CellState::Empty {
                             deductions.push(Deduction {
                                 row,
                                 col,
@@ -327,8 +257,8 @@ impl AdvancedHeuristics {
                     .all(|((_, size), &constraint)| *size == constraint);
                 
                 if all_match {
-                    for row in 0..column.len() {
-                        if column[row] == CellState::Empty {
+                    for (row, &cell) in column.iter().enumerate() {
+                        if cell == CellState::Empty {
                             deductions.push(Deduction {
                                 row,
                                 col,
@@ -343,6 +273,14 @@ impl AdvancedHeuristics {
         Ok(deductions)
     }
 
+    fn deduction(index: usize, position: usize, is_row: bool, state: CellState) -> Deduction {
+        if is_row {
+            Deduction { row: index, col: position, state }
+        } else {
+            Deduction { row: position, col: index, state }
+        }
+    }
+
     /// Trouve tous les blocs de cases remplies dans une ligne
     fn find_filled_blocks(&self, line: &[CellState]) -> Vec<(usize, usize)> {
         let mut blocks = Vec::new();
@@ -373,6 +311,12 @@ impl AdvancedHeuristics {
     }
 }
 
+impl Default for AdvancedHeuristics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -397,8 +341,9 @@ mod tests {
     #[test]
     fn test_puncturing() {
         let mut grid = Grid::new(5, 1);
-        let mut constraints = Constraints::new(5, 1);
-        constraints.set_row_constraint(0, vec![2]);
+        let rows = vec![vec![2]];
+        let columns = vec![vec![], vec![1], vec![1], vec![], vec![]];
+        let constraints = Constraints::new(5, 1, rows, columns).unwrap();
         
         // Placer un bloc complet de 2
         grid.set(0, 1, CellState::Filled).unwrap();
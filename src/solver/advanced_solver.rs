@@ -1,5 +1,7 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use crate::grid::{Grid, Constraints};
-use crate::solver::{Deduction, NonogramSolver};
+use crate::solver::{Deduction, NonogramSolver, SolveStep, Technique};
 use super::cross_analysis::CrossAnalyzer;
 use super::advanced_heuristics::AdvancedHeuristics;
 
@@ -33,6 +35,7 @@ pub struct AdvancedSolver {
     base_solver: NonogramSolver,
     cross_analyzer: CrossAnalyzer,
     heuristics: AdvancedHeuristics,
+    cancel: Option<Arc<AtomicBool>>,
 }
 
 impl AdvancedSolver {
@@ -46,11 +49,24 @@ impl AdvancedSolver {
             base_solver: NonogramSolver::new(),
             cross_analyzer: CrossAnalyzer::new(),
             heuristics: AdvancedHeuristics::new(),
+            cancel: None,
         }
     }
 
+    /// Drapeau d'annulation, vérifié à chaque itération
+    pub fn set_cancel_flag(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
+    }
+
     pub fn solve(&mut self, grid: &mut Grid, constraints: &Constraints) -> Result<Vec<Deduction>, String> {
-        let mut all_deductions = Vec::new();
+        let steps = self.solve_steps(grid, constraints)?;
+        Ok(steps.into_iter().flat_map(|step| step.deductions).collect())
+    }
+
+    /// Comme `solve`, en une étape par ligne ou colonne de la logique de base,
+    /// puis une étape par passe d'analyse croisée ou d'heuristiques
+    pub fn solve_steps(&mut self, grid: &mut Grid, constraints: &Constraints) -> Result<Vec<SolveStep>, String> {
+        let mut steps = Vec::new();
         let mut iteration = 0;
         let mut changed = true;
 
@@ -61,6 +77,9 @@ impl AdvancedSolver {
         }
 
         while changed && iteration < self.config.max_iterations {
+            if self.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+                return Err("Résolution annulée".to_string());
+            }
             changed = false;
             iteration += 1;
 
@@ -72,13 +91,13 @@ impl AdvancedSolver {
             if self.config.verbose {
                 println!("   Phase 1: Line solving...");
             }
-            let line_deductions = self.base_solver.solve(grid, constraints)?;
-            if !line_deductions.is_empty() {
+            let line_steps = self.base_solver.solve_steps(grid, constraints)?;
+            if !line_steps.is_empty() {
                 if self.config.verbose {
-                    println!("      → {} déductions", line_deductions.len());
+                    let count: usize = line_steps.iter().map(|step| step.deductions.len()).sum();
+                    println!("      → {} déductions", count);
                 }
-                self.apply_deductions(grid, &line_deductions)?;
-                all_deductions.extend(line_deductions);
+                steps.extend(line_steps);
                 changed = true;
             }
 
@@ -97,7 +116,7 @@ impl AdvancedSolver {
                         println!("      → {} déductions", new_deductions.len());
                     }
                     self.apply_deductions(grid, &new_deductions)?;
-                    all_deductions.extend(new_deductions);
+                    steps.push(SolveStep { technique: Technique::CrossAnalysis, deductions: new_deductions });
                     changed = true;
                 }
             }
@@ -117,7 +136,7 @@ impl AdvancedSolver {
                         println!("      → {} déductions", new_deductions.len());
                     }
                     self.apply_deductions(grid, &new_deductions)?;
-                    all_deductions.extend(new_deductions);
+                    steps.push(SolveStep { technique: Technique::Heuristics, deductions: new_deductions });
                     changed = true;
                 }
             }
@@ -133,14 +152,15 @@ impl AdvancedSolver {
 
         if self.config.verbose {
             println!("\n✅ Résolution terminée");
-            println!("   Total: {} déductions en {} itérations", all_deductions.len(), iteration);
+            let count: usize = steps.iter().map(|step| step.deductions.len()).sum();
+            println!("   Total: {} déductions en {} itérations", count, iteration);
             let empty_cells = grid.count_empty_cells();
             let total_cells = grid.width() * grid.height();
             let progress = ((total_cells - empty_cells) as f64 / total_cells as f64 * 100.0) as usize;
             println!("   Progression: {}% ({}/{} cases résolues)", progress, total_cells - empty_cells, total_cells);
         }
 
-        Ok(all_deductions)
+        Ok(steps)
     }
 
     fn apply_deductions(&self, grid: &mut Grid, deductions: &[Deduction]) -> Result<(), String> {
@@ -155,27 +175,24 @@ impl AdvancedSolver {
     }
 }
 
+impl Default for AdvancedSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::grid::CellState;
+    use crate::solver::{SolutionCount, SolutionSearch};
 
     #[test]
     fn test_advanced_solver_simple() {
         let mut grid = Grid::new(5, 5);
-        let mut constraints = Constraints::new(5, 5);
-        
-        constraints.set_row_constraint(0, vec![2]);
-        constraints.set_row_constraint(1, vec![1, 1]);
-        constraints.set_row_constraint(2, vec![5]);
-        constraints.set_row_constraint(3, vec![1, 1]);
-        constraints.set_row_constraint(4, vec![2]);
-        
-        constraints.set_column_constraint(0, vec![2]);
-        constraints.set_column_constraint(1, vec![1, 1]);
-        constraints.set_column_constraint(2, vec![5]);
-        constraints.set_column_constraint(3, vec![1, 1]);
-        constraints.set_column_constraint(4, vec![2]);
+        let rows = vec![vec![2], vec![1, 1], vec![5], vec![1, 1], vec![2]];
+        let columns = vec![vec![2], vec![1, 1], vec![5], vec![1, 1], vec![2]];
+        let constraints = Constraints::new(5, 5, rows, columns).unwrap();
         
         let mut solver = AdvancedSolver::new();
         let deductions = solver.solve(&mut grid, &constraints).unwrap();
@@ -188,4 +205,42 @@ mod tests {
         assert_eq!(grid.get(2, 3), Some(CellState::Filled));
         assert_eq!(grid.get(2, 4), Some(CellState::Filled));
     }
+
+    #[test]
+    fn test_solve_steps_name_each_technique() {
+        // Chaque étape nomme sa technique; les rejouer dans l'ordre redonne la grille résolue
+        let rows = vec![vec![2], vec![1, 1], vec![5], vec![1, 1], vec![2]];
+        let constraints = Constraints::new(5, 5, rows.clone(), rows).unwrap();
+        let mut grid = Grid::new(5, 5);
+        let steps = AdvancedSolver::new().solve_steps(&mut grid, &constraints).unwrap();
+
+        assert!(matches!(steps[0].technique, Technique::Row(_) | Technique::Column(_)));
+        assert!(steps.iter().all(|step| !step.deductions.is_empty() && step.technique != Technique::Search));
+        let mut replay = Grid::new(5, 5);
+        for deduction in SolveStep::deductions_until(&steps, steps.len()) {
+            replay.set(deduction.row, deduction.col, deduction.state).unwrap();
+        }
+        assert_eq!(replay.to_text(), grid.to_text());
+    }
+
+    #[test]
+    fn test_heuristics_keep_unique_puzzle_consistent() {
+        // Grille 10x10 à solution unique sur laquelle les anciennes règles glue et joining
+        // déduisaient des cases fausses, jusqu'à rendre la grille contradictoire
+        let rows = vec![vec![1, 6], vec![1, 3], vec![3, 3], vec![1, 2, 1], vec![3, 2], vec![2, 2, 1], vec![2, 1, 1], vec![1, 1, 2], vec![2, 1, 4], vec![2, 5]];
+        let columns = vec![vec![1, 2], vec![1, 1, 1, 2], vec![1, 3, 1], vec![2, 1, 1], vec![3, 1], vec![1, 2, 1, 1, 1], vec![1, 2, 1, 2], vec![2, 1, 2], vec![2, 3, 3], vec![2, 1, 4]];
+        let constraints = Constraints::new(10, 10, rows, columns).unwrap();
+        let Ok(SolutionCount::Unique(solution)) = SolutionSearch::new().count(&Grid::new(10, 10), &constraints) else {
+            panic!("la grille doit avoir une solution unique");
+        };
+
+        let mut grid = Grid::new(10, 10);
+        AdvancedSolver::new().solve(&mut grid, &constraints).unwrap();
+        for row in 0..10 {
+            for col in 0..10 {
+                let state = grid.get(row, col).unwrap();
+                assert!(state == CellState::Empty || Some(state) == solution.get(row, col));
+            }
+        }
+    }
 }
@@ -1,6 +1,10 @@
 use crate::grid::{Grid, CellState, Constraints};
 use crate::solver::{Deduction, AdvancedSolver, AdvancedSolverConfig};
 use super::contradiction_detector::ContradictionDetector;
+use super::nogood::{Assignment, ConflictAnalyzer, NoGood, NoGoodPool};
+use super::branching::{Branch, BranchingHeuristic, BranchingStrategy};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::collections::HashSet;
 
 /// Configuration pour le backtracking
@@ -8,6 +12,12 @@ use std::collections::HashSet;
 pub struct BacktrackingConfig {
     pub max_depth: usize,
     pub max_states: usize,
+    /// Apprendre des no-goods et effectuer des retours arrière non chronologiques
+    pub use_nogood_learning: bool,
+    /// Stratégie de choix du point de branchement
+    pub branching: BranchingStrategy,
+    /// Graine permutant l'ordre des valeurs essayées (diversification en mode portfolio)
+    pub seed: Option<u64>,
     pub verbose: bool,
 }
 
@@ -16,6 +26,9 @@ impl Default for BacktrackingConfig {
         Self {
             max_depth: 10,
             max_states: 10000,
+            use_nogood_learning: true,
+            branching: BranchingStrategy::Score,
+            seed: None,
             verbose: false,
         }
     }
@@ -28,6 +41,13 @@ pub struct BacktrackingSolver {
     contradiction_detector: ContradictionDetector,
     states_explored: usize,
     visited_states: HashSet<String>,
+    conflict_analyzer: ConflictAnalyzer,
+    branching_heuristic: BranchingHeuristic,
+    /// Choix responsables du dernier échec (None si l'échec n'est pas une contradiction prouvée)
+    last_conflict: Option<Vec<Assignment>>,
+    backjumps: usize,
+    /// Drapeau d'annulation partagé (mode portfolio)
+    cancel: Option<Arc<AtomicBool>>,
 }
 
 impl BacktrackingSolver {
@@ -37,21 +57,36 @@ impl BacktrackingSolver {
 
     pub fn with_config(config: BacktrackingConfig) -> Self {
         let advanced_config = AdvancedSolverConfig {
-            use_cross_analysis: true,
-            use_advanced_heuristics: true,
+            use_cross_analysis: false,
+            use_advanced_heuristics: false,
             max_iterations: 50,
             verbose: false,
         };
 
         Self {
+            branching_heuristic: BranchingHeuristic::new(config.branching),
             config,
             advanced_solver: AdvancedSolver::with_config(advanced_config),
             contradiction_detector: ContradictionDetector::new(),
             states_explored: 0,
             visited_states: HashSet::new(),
+            conflict_analyzer: ConflictAnalyzer::new(1000),
+            last_conflict: None,
+            backjumps: 0,
+            cancel: None,
         }
     }
 
+    /// Interrompt la recherche dès que le drapeau passe à vrai
+    pub fn set_cancel_flag(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
+    }
+
+    /// Partage les no-goods appris avec d'autres recherches partant de la même grille
+    pub fn set_nogood_pool(&mut self, pool: NoGoodPool) {
+        self.conflict_analyzer.set_pool(pool);
+    }
+
     /// Résout la grille avec backtracking intelligent
     pub fn solve(&mut self, grid: &mut Grid, constraints: &Constraints) -> Result<Vec<Deduction>, String> {
         self.states_explored = 0;
@@ -61,6 +96,7 @@ impl BacktrackingSolver {
             println!("🔄 Démarrage du backtracking intelligent");
             println!("   - Profondeur max: {}", self.config.max_depth);
             println!("   - États max: {}", self.config.max_states);
+            println!("   - Branchement: {}", self.config.branching.name());
         }
 
         let initial_deductions = self.advanced_solver.solve(grid, constraints)?;
@@ -81,6 +117,9 @@ impl BacktrackingSolver {
         }
 
         let mut all_deductions = initial_deductions.clone();
+        self.conflict_analyzer.reset(grid);
+        self.last_conflict = None;
+        self.backjumps = 0;
         
         match self.backtrack(grid, constraints, 0) {
             Ok(additional_deductions) => {
@@ -89,6 +128,8 @@ impl BacktrackingSolver {
                 if self.config.verbose {
                     println!("   ✅ Backtracking réussi");
                     println!("   - États explorés: {}", self.states_explored);
+                    println!("   - No-goods appris: {}", self.conflict_analyzer.nogood_count());
+                    println!("   - Retours non chronologiques: {}", self.backjumps);
                     println!("   - Total déductions: {}", all_deductions.len());
                 }
                 
@@ -106,6 +147,12 @@ impl BacktrackingSolver {
 
     /// Fonction récursive de backtracking
     fn backtrack(&mut self, grid: &mut Grid, constraints: &Constraints, depth: usize) -> Result<Vec<Deduction>, String> {
+        self.last_conflict = None;
+
+        if self.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            return Err("Recherche annulée".to_string());
+        }
+
         if depth >= self.config.max_depth {
             return Err("Profondeur maximale atteinte".to_string());
         }
@@ -122,60 +169,147 @@ impl BacktrackingSolver {
         }
         self.visited_states.insert(state_key);
 
-        let deductions = self.advanced_solver.solve(grid, constraints)?;
+        let mut deductions = match self.advanced_solver.solve(grid, constraints) {
+            Ok(deductions) => deductions,
+            Err(e) => {
+                if self.config.use_nogood_learning {
+                    self.last_conflict = self.conflict_analyzer.explain(None, constraints);
+                }
+                return Err(e);
+            }
+        };
+
+        if self.config.use_nogood_learning {
+            self.conflict_analyzer.import_shared();
+            match self.conflict_analyzer.propagate(grid) {
+                Ok(implied) => deductions.extend(implied),
+                Err(conflict) => {
+                    self.last_conflict = Some(conflict);
+                    return Err("No-good violé".to_string());
+                }
+            }
+        }
 
         if grid.count_empty_cells() == 0 {
             return Ok(deductions);
         }
 
-        let (row, col) = match self.choose_best_cell(grid, constraints) {
-            Some(pos) => pos,
+        let branch = match self.choose_branch(grid, constraints) {
+            Some(branch) => branch,
             None => return Ok(deductions),
         };
 
-        // Essayer Filled
-        let mut test_grid = grid.clone();
-        if test_grid.set(row, col, CellState::Filled).is_ok() {
-            if self.contradiction_detector.is_valid(&test_grid, constraints) {
-                match self.backtrack(&mut test_grid, constraints, depth + 1) {
-                    Ok(mut branch_deductions) => {
-                        grid.set(row, col, CellState::Filled)?;
-                        branch_deductions.push(Deduction {
-                            row,
-                            col,
-                            state: CellState::Filled,
-                        });
-                        branch_deductions.extend(deductions);
-                        return Ok(branch_deductions);
+        // Ensembles de choix responsables de l'échec de chaque alternative essayée
+        let mut conflicts: Vec<Vec<Assignment>> = Vec::new();
+        let mut proven = branch.complete;
+
+        for alternative in &branch.alternatives {
+            let mut test_grid = grid.clone();
+            if alternative.iter().any(|&(row, col, state)| test_grid.set(row, col, state).is_err()) {
+                proven = false;
+                continue;
+            }
+
+            let conflict = if !self.config.use_nogood_learning {
+                if !self.contradiction_detector.is_valid(&test_grid, constraints) {
+                    continue;
+                }
+                None
+            } else if let Some(nogood) = self.conflict_analyzer.check(&test_grid) {
+                Some(nogood)
+            } else if !self.contradiction_detector.is_valid(&test_grid, constraints) {
+                match self.explain_alternative(alternative, constraints) {
+                    Some(conflict) => Some(conflict),
+                    None => {
+                        proven = false;
+                        continue;
                     }
-                    Err(_) => {}
                 }
-            }
-        }
+            } else {
+                None
+            };
+
+            let conflict = match conflict {
+                Some(conflict) => Some(conflict),
+                None => {
+                    for &decision in alternative {
+                        self.conflict_analyzer.push_decision(decision);
+                    }
+                    let result = self.backtrack(&mut test_grid, constraints, depth + 1);
+                    for _ in alternative {
+                        self.conflict_analyzer.pop_decision();
+                    }
 
-        // Essayer Crossed
-        let mut test_grid = grid.clone();
-        if test_grid.set(row, col, CellState::Crossed).is_ok() {
-            if self.contradiction_detector.is_valid(&test_grid, constraints) {
-                match self.backtrack(&mut test_grid, constraints, depth + 1) {
-                    Ok(mut branch_deductions) => {
-                        grid.set(row, col, CellState::Crossed)?;
-                        branch_deductions.push(Deduction {
-                            row,
-                            col,
-                            state: CellState::Crossed,
-                        });
-                        branch_deductions.extend(deductions);
-                        return Ok(branch_deductions);
+                    match result {
+                        Ok(mut branch_deductions) => {
+                            *grid = test_grid;
+                            branch_deductions.extend(
+                                alternative.iter().map(|&(row, col, state)| Deduction { row, col, state })
+                            );
+                            branch_deductions.extend(deductions);
+                            return Ok(branch_deductions);
+                        }
+                        Err(_) => self.last_conflict.take(),
                     }
-                    Err(_) => {}
                 }
+            };
+
+            match conflict {
+                Some(conflict) if !alternative.iter().any(|decision| conflict.contains(decision)) => {
+                    // L'échec ne dépend pas de ce choix: inutile d'essayer les autres alternatives
+                    self.backjumps += 1;
+                    self.last_conflict = Some(conflict);
+                    return Err("Retour arrière non chronologique".to_string());
+                }
+                Some(conflict) => conflicts.push(conflict),
+                None => proven = false,
             }
         }
 
+        if self.config.use_nogood_learning && proven {
+            // Toutes les alternatives échouent: la résolvante des conflits est un no-good
+            let nogood = NoGood::new(
+                conflicts.concat().into_iter()
+                    .filter(|&(r, c, _)| !branch.involves(r, c))
+                    .collect()
+            );
+            self.last_conflict = Some(nogood.assignments().to_vec());
+            self.conflict_analyzer.learn(nogood);
+        }
+
         Err("Aucune solution trouvée".to_string())
     }
 
+    /// Choisit le point de branchement selon la stratégie configurée
+    fn choose_branch(&self, grid: &Grid, constraints: &Constraints) -> Option<Branch> {
+        let branch = match self.config.branching {
+            BranchingStrategy::Score => self.choose_best_cell(grid, constraints)
+                .map(|(row, col)| Branch::cell(row, col, CellState::Filled)),
+            _ => self.branching_heuristic.choose(grid, constraints),
+        };
+        match self.config.seed {
+            Some(seed) => branch.map(|branch| branch.shuffled(seed)),
+            None => branch,
+        }
+    }
+
+    /// Explique la contradiction provoquée par une alternative à partir des choix courants
+    fn explain_alternative(&mut self, alternative: &[Assignment], constraints: &Constraints) -> Option<Vec<Assignment>> {
+        for &decision in alternative {
+            self.conflict_analyzer.push_decision(decision);
+        }
+        let explanation = self.conflict_analyzer.explain(None, constraints);
+        for _ in alternative {
+            self.conflict_analyzer.pop_decision();
+        }
+        explanation
+    }
+
+    /// Nombre d'états explorés lors de la dernière résolution
+    pub fn states_explored(&self) -> usize {
+        self.states_explored
+    }
+
     /// Choisit la meilleure case (MRV heuristic)
     fn choose_best_cell(&self, grid: &Grid, constraints: &Constraints) -> Option<(usize, usize)> {
         let mut best_cell = None;
@@ -237,6 +371,12 @@ impl BacktrackingSolver {
     }
 }
 
+impl Default for BacktrackingSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -244,19 +384,16 @@ mod tests {
     #[test]
     fn test_backtracking_simple() {
         let mut grid = Grid::new(3, 3);
-        let mut constraints = Constraints::new(3, 3);
-        
-        constraints.set_row_constraint(0, vec![1]);
-        constraints.set_row_constraint(1, vec![3]);
-        constraints.set_row_constraint(2, vec![1]);
-        
-        constraints.set_column_constraint(0, vec![1]);
-        constraints.set_column_constraint(1, vec![3]);
-        constraints.set_column_constraint(2, vec![1]);
+        let rows = vec![vec![1], vec![3], vec![1]];
+        let columns = vec![vec![1], vec![3], vec![1]];
+        let constraints = Constraints::new(3, 3, rows, columns).unwrap();
         
         let config = BacktrackingConfig {
             max_depth: 5,
             max_states: 100,
+            use_nogood_learning: true,
+            branching: BranchingStrategy::Score,
+            seed: None,
             verbose: false,
         };
         
@@ -265,4 +402,28 @@ mod tests {
         
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_backtracking_with_nogoods_finds_solution() {
+        // Deux solutions (diagonales): la propagation seule ne déduit rien
+        let rows = vec![vec![1], vec![1]];
+        let columns = vec![vec![1], vec![1]];
+        let constraints = Constraints::new(2, 2, rows, columns).unwrap();
+
+        for use_nogood_learning in [false, true] {
+            let mut grid = Grid::new(2, 2);
+            let config = BacktrackingConfig {
+                use_nogood_learning,
+                ..Default::default()
+            };
+
+            let mut solver = BacktrackingSolver::with_config(config);
+            solver.solve(&mut grid, &constraints).unwrap();
+
+            assert_eq!(grid.count_empty_cells(), 0);
+            assert_eq!(grid.count_filled_cells(), 2);
+            assert_ne!(grid.get(0, 0), grid.get(0, 1));
+            assert_eq!(grid.get(0, 0), grid.get(1, 1));
+        }
+    }
 }
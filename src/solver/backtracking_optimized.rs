@@ -1,6 +1,10 @@
 use crate::grid::{Grid, CellState, Constraints};
 use crate::solver::{Deduction, AdvancedSolver, AdvancedSolverConfig};
 use super::contradiction_detector::ContradictionDetector;
+use super::nogood::{Assignment, ConflictAnalyzer, NoGood, NoGoodPool};
+use super::branching::{Branch, BranchingHeuristic, BranchingStrategy};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::collections::{HashSet, HashMap};
 
 /// Configuration pour le backtracking optimisé
@@ -11,6 +15,12 @@ pub struct OptimizedBacktrackingConfig {
     pub use_constraint_propagation: bool,
     pub use_naked_singles: bool,
     pub use_hidden_singles: bool,
+    /// Apprendre des no-goods et effectuer des retours arrière non chronologiques
+    pub use_nogood_learning: bool,
+    /// Stratégie de choix du point de branchement
+    pub branching: BranchingStrategy,
+    /// Graine permutant l'ordre des valeurs essayées (diversification en mode portfolio)
+    pub seed: Option<u64>,
     pub verbose: bool,
 }
 
@@ -22,6 +32,9 @@ impl Default for OptimizedBacktrackingConfig {
             use_constraint_propagation: true,
             use_naked_singles: true,
             use_hidden_singles: true,
+            use_nogood_learning: true,
+            branching: BranchingStrategy::Score,
+            seed: None,
             verbose: false,
         }
     }
@@ -35,6 +48,13 @@ pub struct OptimizedBacktrackingSolver {
     states_explored: usize,
     visited_states: HashSet<String>,
     deduction_cache: HashMap<String, Vec<Deduction>>,
+    conflict_analyzer: ConflictAnalyzer,
+    branching_heuristic: BranchingHeuristic,
+    /// Choix responsables du dernier échec (None si l'échec n'est pas une contradiction prouvée)
+    last_conflict: Option<Vec<Assignment>>,
+    backjumps: usize,
+    /// Drapeau d'annulation partagé (mode portfolio)
+    cancel: Option<Arc<AtomicBool>>,
 }
 
 impl OptimizedBacktrackingSolver {
@@ -44,22 +64,37 @@ impl OptimizedBacktrackingSolver {
 
     pub fn with_config(config: OptimizedBacktrackingConfig) -> Self {
         let advanced_config = AdvancedSolverConfig {
-            use_cross_analysis: true,
-            use_advanced_heuristics: true,
+            use_cross_analysis: false,
+            use_advanced_heuristics: false,
             max_iterations: 100,
             verbose: false,
         };
 
         Self {
+            branching_heuristic: BranchingHeuristic::new(config.branching),
             config,
             advanced_solver: AdvancedSolver::with_config(advanced_config),
             contradiction_detector: ContradictionDetector::new(),
             states_explored: 0,
             visited_states: HashSet::new(),
             deduction_cache: HashMap::new(),
+            conflict_analyzer: ConflictAnalyzer::new(5000),
+            last_conflict: None,
+            backjumps: 0,
+            cancel: None,
         }
     }
 
+    /// Interrompt la recherche dès que le drapeau passe à vrai
+    pub fn set_cancel_flag(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
+    }
+
+    /// Partage les no-goods appris avec d'autres recherches partant de la même grille
+    pub fn set_nogood_pool(&mut self, pool: NoGoodPool) {
+        self.conflict_analyzer.set_pool(pool);
+    }
+
     /// Résout la grille avec backtracking optimisé
     pub fn solve(&mut self, grid: &mut Grid, constraints: &Constraints) -> Result<Vec<Deduction>, String> {
         self.states_explored = 0;
@@ -71,6 +106,7 @@ impl OptimizedBacktrackingSolver {
             println!("   - Profondeur max: {}", self.config.max_depth);
             println!("   - États max: {}", self.config.max_states);
             println!("   - Propagation de contraintes: {}", if self.config.use_constraint_propagation { "✓" } else { "✗" });
+            println!("   - Branchement: {}", self.config.branching.name());
         }
 
         // Phase 1: Appliquer le solveur avancé
@@ -108,11 +144,17 @@ impl OptimizedBacktrackingSolver {
         }
 
         // Phase 3: Backtracking avec propagation de contraintes
+        self.conflict_analyzer.reset(grid);
+        self.last_conflict = None;
+        self.backjumps = 0;
+
         match self.backtrack(grid, constraints, 0) {
             Ok(_) => {
                 if self.config.verbose {
                     println!("   ✅ Backtracking réussi");
                     println!("   - États explorés: {}", self.states_explored);
+                    println!("   - No-goods appris: {}", self.conflict_analyzer.nogood_count());
+                    println!("   - Retours non chronologiques: {}", self.backjumps);
                 }
                 
                 self.collect_all_deductions(grid)
@@ -193,7 +235,7 @@ impl OptimizedBacktrackingSolver {
                 .ok_or_else(|| format!("Contrainte de ligne {} non trouvée", row))?;
             
             // Trouver les positions possibles pour chaque bloc
-            for (block_idx, &block_size) in row_constraint.iter().enumerate() {
+            for &block_size in row_constraint {
                 let mut possible_positions = Vec::new();
                 
                 for col in 0..grid.width() {
@@ -220,7 +262,7 @@ impl OptimizedBacktrackingSolver {
             let col_constraint = constraints.get_column_constraint(col)
                 .ok_or_else(|| format!("Contrainte de colonne {} non trouvée", col))?;
             
-            for (block_idx, &block_size) in col_constraint.iter().enumerate() {
+            for &block_size in col_constraint {
                 let mut possible_positions = Vec::new();
                 
                 for row in 0..grid.height() {
@@ -253,9 +295,8 @@ impl OptimizedBacktrackingSolver {
             
             // Vérifier si toutes les cases sont vides ou remplies
             for i in 0..size {
-                match grid.get(start_row, start_col + i) {
-                    Some(CellState::Crossed) => return false,
-                    _ => {}
+                if grid.get(start_row, start_col + i) == Some(CellState::Crossed) {
+                    return false;
                 }
             }
             
@@ -268,9 +309,8 @@ impl OptimizedBacktrackingSolver {
             
             // Vérifier si toutes les cases sont vides ou remplies
             for i in 0..size {
-                match grid.get(start_row + i, start_col) {
-                    Some(CellState::Crossed) => return false,
-                    _ => {}
+                if grid.get(start_row + i, start_col) == Some(CellState::Crossed) {
+                    return false;
                 }
             }
             
@@ -280,6 +320,12 @@ impl OptimizedBacktrackingSolver {
 
     /// Backtracking récursif avec optimisations
     fn backtrack(&mut self, grid: &mut Grid, constraints: &Constraints, depth: usize) -> Result<(), String> {
+        self.last_conflict = None;
+
+        if self.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            return Err("Recherche annulée".to_string());
+        }
+
         // Vérifier les limites
         if depth >= self.config.max_depth {
             return Err("Profondeur maximale atteinte".to_string());
@@ -305,41 +351,141 @@ impl OptimizedBacktrackingSolver {
 
         // Propagation de contraintes après chaque choix
         if self.config.use_constraint_propagation {
-            let deductions = self.advanced_solver.solve(grid, constraints)?;
-            
-            if grid.count_empty_cells() == 0 {
-                return Ok(());
+            if let Err(e) = self.advanced_solver.solve(grid, constraints) {
+                if self.config.use_nogood_learning {
+                    self.last_conflict = self.conflict_analyzer.explain(None, constraints);
+                }
+                return Err(e);
+            }
+        }
+
+        // Propagation des no-goods appris
+        if self.config.use_nogood_learning {
+            self.conflict_analyzer.import_shared();
+            if let Err(conflict) = self.conflict_analyzer.propagate(grid) {
+                self.last_conflict = Some(conflict);
+                return Err("No-good violé".to_string());
             }
         }
 
-        // Choisir la meilleure case avec heuristique MRV améliorée
-        let (best_row, best_col) = self.choose_best_cell_mrv_plus(grid, constraints)?;
+        if grid.count_empty_cells() == 0 {
+            return Ok(());
+        }
+
+        // Choisir le point de branchement (heuristique MRV améliorée par défaut)
+        let branch = self.choose_branch(grid, constraints)?;
 
-        // Essayer Filled en premier (heuristique: les grilles ont généralement plus de cases noires)
-        for &state in &[CellState::Filled, CellState::Crossed] {
+        // Ensembles de choix responsables de l'échec de chaque alternative essayée
+        let mut conflicts: Vec<Vec<Assignment>> = Vec::new();
+        let mut proven = branch.complete;
+
+        // Les alternatives sont ordonnées par la stratégie (Filled en premier pour le score MRV+)
+        for alternative in &branch.alternatives {
             let mut test_grid = grid.clone();
-            test_grid.set(best_row, best_col, state)?;
-
-            // Vérifier rapidement les contradictions
-            if !self.contradiction_detector.has_contradiction(&test_grid, constraints) {
-                // Appliquer le choix
-                grid.set(best_row, best_col, state)?;
-
-                // Continuer le backtracking
-                match self.backtrack(grid, constraints, depth + 1) {
-                    Ok(()) => return Ok(()),
-                    Err(_) => {
-                        // Annuler le choix
-                        grid.set(best_row, best_col, CellState::Empty)?;
+            for &(row, col, state) in alternative {
+                test_grid.set(row, col, state)?;
+            }
+
+            // Vérifier rapidement les contradictions (no-goods puis propagation)
+            let conflict = if !self.config.use_nogood_learning {
+                if self.contradiction_detector.has_contradiction(&test_grid, constraints) {
+                    continue;
+                }
+                None
+            } else if let Some(nogood) = self.conflict_analyzer.check(&test_grid) {
+                Some(nogood)
+            } else if self.contradiction_detector.has_contradiction(&test_grid, constraints) {
+                match self.explain_alternative(alternative, constraints) {
+                    Some(conflict) => Some(conflict),
+                    None => {
+                        proven = false;
+                        continue;
                     }
                 }
+            } else {
+                None
+            };
+
+            let conflict = match conflict {
+                Some(conflict) => Some(conflict),
+                None => {
+                    // Appliquer le choix sur une copie pour pouvoir annuler aussi la propagation
+                    for &decision in alternative {
+                        self.conflict_analyzer.push_decision(decision);
+                    }
+                    let result = self.backtrack(&mut test_grid, constraints, depth + 1);
+                    for _ in alternative {
+                        self.conflict_analyzer.pop_decision();
+                    }
+
+                    match result {
+                        Ok(()) => {
+                            *grid = test_grid;
+                            return Ok(());
+                        }
+                        Err(_) => self.last_conflict.take(),
+                    }
+                }
+            };
+
+            match conflict {
+                Some(conflict) if !alternative.iter().any(|decision| conflict.contains(decision)) => {
+                    // L'échec ne dépend pas de ce choix: remonter directement
+                    self.backjumps += 1;
+                    self.last_conflict = Some(conflict);
+                    return Err("Retour arrière non chronologique".to_string());
+                }
+                Some(conflict) => conflicts.push(conflict),
+                None => proven = false,
             }
         }
 
+        if self.config.use_nogood_learning && proven {
+            // Toutes les alternatives échouent: la résolvante des conflits est un no-good
+            let nogood = NoGood::new(
+                conflicts.concat().into_iter()
+                    .filter(|&(row, col, _)| !branch.involves(row, col))
+                    .collect()
+            );
+            self.last_conflict = Some(nogood.assignments().to_vec());
+            self.conflict_analyzer.learn(nogood);
+        }
+
         // Aucun choix n'a fonctionné
         Err("Aucune solution trouvée".to_string())
     }
 
+    /// Choisit le point de branchement selon la stratégie configurée
+    fn choose_branch(&self, grid: &Grid, constraints: &Constraints) -> Result<Branch, String> {
+        let branch = match self.config.branching {
+            BranchingStrategy::Score => self.choose_best_cell_mrv_plus(grid, constraints)
+                .map(|(row, col)| Branch::cell(row, col, CellState::Filled))?,
+            _ => self.branching_heuristic.choose(grid, constraints)
+                .ok_or_else(|| "Aucune case vide trouvée".to_string())?,
+        };
+        Ok(match self.config.seed {
+            Some(seed) => branch.shuffled(seed),
+            None => branch,
+        })
+    }
+
+    /// Explique la contradiction provoquée par une alternative à partir des choix courants
+    fn explain_alternative(&mut self, alternative: &[Assignment], constraints: &Constraints) -> Option<Vec<Assignment>> {
+        for &decision in alternative {
+            self.conflict_analyzer.push_decision(decision);
+        }
+        let explanation = self.conflict_analyzer.explain(None, constraints);
+        for _ in alternative {
+            self.conflict_analyzer.pop_decision();
+        }
+        explanation
+    }
+
+    /// Nombre d'états explorés lors de la dernière résolution
+    pub fn states_explored(&self) -> usize {
+        self.states_explored
+    }
+
     /// Heuristique MRV améliorée avec analyse de contraintes
     fn choose_best_cell_mrv_plus(&self, grid: &Grid, constraints: &Constraints) -> Result<(usize, usize), String> {
         let mut best_score = 0;
@@ -440,7 +586,7 @@ mod tests {
     fn test_optimized_backtracking_simple() {
         let mut grid = Grid::new(5, 5);
         let rows = vec![vec![5], vec![1], vec![1], vec![1], vec![5]];
-        let columns = vec![vec![2], vec![1, 1], vec![1, 1], vec![1, 1], vec![2]];
+        let columns = vec![vec![5], vec![1, 1], vec![1, 1], vec![1, 1], vec![1, 1]];
         let constraints = Constraints::new(5, 5, rows, columns).unwrap();
         
         let mut solver = OptimizedBacktrackingSolver::new();
@@ -448,4 +594,25 @@ mod tests {
         
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_branching_strategies_solve_ambiguous_puzzle() {
+        // Deux solutions (diagonales): chaque stratégie doit en trouver une valide
+        let rows = vec![vec![1], vec![1]];
+        let columns = vec![vec![1], vec![1]];
+        let constraints = Constraints::new(2, 2, rows, columns).unwrap();
+
+        for branching in BranchingStrategy::ALL {
+            let mut grid = Grid::new(2, 2);
+            let config = OptimizedBacktrackingConfig {
+                branching,
+                use_hidden_singles: false,
+                ..Default::default()
+            };
+
+            let mut solver = OptimizedBacktrackingSolver::with_config(config);
+            solver.solve(&mut grid, &constraints).unwrap();
+            assert!(constraints.is_satisfied_by(&grid), "stratégie {}", branching.name());
+        }
+    }
 }
@@ -0,0 +1,241 @@
+use crate::grid::{Grid, CellState, Constraints};
+use super::line_solver_optimized::OptimizedLineSolver;
+use super::nogood::Assignment;
+use super::probability::HeatMap;
+
+/// Stratégie de choix du point de branchement pour le backtracking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum BranchingStrategy {
+    /// Score ad hoc propre à chaque solveur (comportement historique)
+    #[default]
+    Score,
+    /// Case de la ligne (ou colonne) ayant le moins de placements possibles
+    FewestPlacements,
+    /// Case dont la probabilité d'être noire est la plus tranchée, valeur probable en premier
+    Probability,
+    /// Branchement sur un placement complet de la ligne la plus contrainte
+    LinePlacement,
+}
+
+impl BranchingStrategy {
+    pub const ALL: [BranchingStrategy; 4] = [
+        BranchingStrategy::Score,
+        BranchingStrategy::FewestPlacements,
+        BranchingStrategy::Probability,
+        BranchingStrategy::LinePlacement,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BranchingStrategy::Score => "score",
+            BranchingStrategy::FewestPlacements => "fewest-placements",
+            BranchingStrategy::Probability => "probability",
+            BranchingStrategy::LinePlacement => "line-placement",
+        }
+    }
+}
+
+/// Point de branchement: alternatives mutuellement exclusives à essayer dans l'ordre
+#[derive(Debug, Clone, PartialEq)]
+pub struct Branch {
+    pub alternatives: Vec<Vec<Assignment>>,
+    /// Vrai si les alternatives couvrent tous les cas indépendamment de l'état courant
+    /// (branchement sur une case). Seuls ces branchements permettent d'apprendre un no-good.
+    pub complete: bool,
+}
+
+impl Branch {
+    /// Branchement sur une case: d'abord `first`, puis l'état opposé
+    pub fn cell(row: usize, col: usize, first: CellState) -> Self {
+        let second = if first == CellState::Filled { CellState::Crossed } else { CellState::Filled };
+        Self {
+            alternatives: vec![vec![(row, col, first)], vec![(row, col, second)]],
+            complete: true,
+        }
+    }
+
+    /// Indique si une case fait partie du branchement
+    pub fn involves(&self, row: usize, col: usize) -> bool {
+        self.alternatives.iter().flatten().any(|&(r, c, _)| (r, c) == (row, col))
+    }
+
+    /// Permute l'ordre des alternatives de façon déterministe à partir d'une graine
+    /// (diversifie des recherches concurrentes utilisant la même stratégie)
+    pub fn shuffled(mut self, seed: u64) -> Self {
+        let key = self.alternatives.first()
+            .and_then(|alternative| alternative.first())
+            .map(|&(row, col, _)| ((row as u64) << 32) | col as u64)
+            .unwrap_or(0);
+        let mut state = seed ^ key.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+
+        for i in (1..self.alternatives.len()).rev() {
+            let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+            self.alternatives.swap(i, j);
+        }
+        self
+    }
+}
+
+/// Générateur SplitMix64
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Placements possibles d'une ligne (ou colonne) non résolue
+struct LineStats {
+    is_row: bool,
+    index: usize,
+    line: Vec<CellState>,
+    /// Log du nombre de placements
+    log_placements: f64,
+    fill_probabilities: Vec<f64>,
+}
+
+impl LineStats {
+    fn cell(&self, pos: usize) -> (usize, usize) {
+        if self.is_row { (self.index, pos) } else { (pos, self.index) }
+    }
+}
+
+/// Choix des points de branchement à partir du comptage des placements de lignes
+pub struct BranchingHeuristic {
+    strategy: BranchingStrategy,
+    /// Au-delà, le branchement sur ligne retombe sur un branchement par case
+    max_line_placements: usize,
+}
+
+impl BranchingHeuristic {
+    pub fn new(strategy: BranchingStrategy) -> Self {
+        Self {
+            strategy,
+            max_line_placements: 64,
+        }
+    }
+
+    /// Choisit le prochain branchement (None si la stratégie est `Score`,
+    /// qui reste gérée par le solveur, ou s'il n'y a plus de case vide)
+    pub fn choose(&self, grid: &Grid, constraints: &Constraints) -> Option<Branch> {
+        match self.strategy {
+            BranchingStrategy::Score => None,
+            BranchingStrategy::FewestPlacements => self.choose_fewest_placements(grid, constraints),
+            BranchingStrategy::Probability => self.choose_probability(grid, constraints)
+                .or_else(|| self.choose_fewest_placements(grid, constraints)),
+            BranchingStrategy::LinePlacement => self.choose_line_placement(grid, constraints)
+                .or_else(|| self.choose_fewest_placements(grid, constraints)),
+        }
+    }
+
+    /// Case de la ligne la plus contrainte qui partage le mieux ses placements
+    fn choose_fewest_placements(&self, grid: &Grid, constraints: &Constraints) -> Option<Branch> {
+        let stats = self.line_stats(grid, constraints);
+        let line = stats.iter()
+            .min_by(|a, b| a.log_placements.total_cmp(&b.log_placements))?;
+
+        let (pos, probability) = (0..line.line.len())
+            .filter(|&pos| line.line[pos] == CellState::Empty)
+            .map(|pos| (pos, line.fill_probabilities[pos]))
+            .min_by(|a, b| (a.1 - 0.5).abs().total_cmp(&(b.1 - 0.5).abs()))?;
+
+        let (row, col) = line.cell(pos);
+        let first = if probability >= 0.5 { CellState::Filled } else { CellState::Crossed };
+        Some(Branch::cell(row, col, first))
+    }
+
+    /// Case dont les probabilités de ligne et de colonne combinées sont les plus tranchées
+    fn choose_probability(&self, grid: &Grid, constraints: &Constraints) -> Option<Branch> {
+        let heat_map = HeatMap::compute(grid, constraints).ok()?;
+        let (row, col, probability) = heat_map.most_certain_cell(grid)?;
+        let first = if probability >= 0.5 { CellState::Filled } else { CellState::Crossed };
+        Some(Branch::cell(row, col, first))
+    }
+
+    /// Branchement sur tous les placements de la ligne qui en a le moins
+    fn choose_line_placement(&self, grid: &Grid, constraints: &Constraints) -> Option<Branch> {
+        let stats = self.line_stats(grid, constraints);
+        let line = stats.iter()
+            .filter(|line| line.log_placements > std::f64::consts::LN_2 / 2.0)
+            .min_by(|a, b| a.log_placements.total_cmp(&b.log_placements))?;
+
+        if line.log_placements > (self.max_line_placements as f64).ln() {
+            return None;
+        }
+
+        let constraint = if line.is_row {
+            constraints.get_row_constraint(line.index)?
+        } else {
+            constraints.get_column_constraint(line.index)?
+        };
+
+        let line_solver = OptimizedLineSolver::new();
+        let alternatives: Vec<Vec<Assignment>> = line_solver
+            .generate_valid_configurations(&line.line, constraint)
+            .into_iter()
+            .map(|placement| {
+                placement.iter().enumerate()
+                    .filter(|&(pos, _)| line.line[pos] == CellState::Empty)
+                    .map(|(pos, &state)| {
+                        let (row, col) = line.cell(pos);
+                        (row, col, state)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        if alternatives.len() < 2 {
+            return None;
+        }
+
+        Some(Branch { alternatives, complete: false })
+    }
+
+    /// Statistiques de placement des lignes et colonnes contenant encore des cases vides
+    /// (les lignes sans placement possible sont ignorées)
+    fn line_stats(&self, grid: &Grid, constraints: &Constraints) -> Vec<LineStats> {
+        let line_solver = OptimizedLineSolver::new();
+        let mut stats = Vec::new();
+
+        let rows = (0..grid.height())
+            .filter_map(|row| Some((true, row, grid.get_row(row)?, constraints.get_row_constraint(row)?)));
+        let columns = (0..grid.width())
+            .filter_map(|col| Some((false, col, grid.get_column(col)?, constraints.get_column_constraint(col)?)));
+
+        for (is_row, index, line, constraint) in rows.chain(columns) {
+            if !line.contains(&CellState::Empty) {
+                continue;
+            }
+            if let Ok(fill_probabilities) = line_solver.fill_probabilities(&line, constraint) {
+                let log_placements = line_solver.log_placement_count(&line, constraint);
+                stats.push(LineStats { is_row, index, line, log_placements, fill_probabilities });
+            }
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_placement_branch_covers_all_placements() {
+        let rows = vec![vec![1], vec![2], vec![1]];
+        let columns = vec![vec![1], vec![2], vec![1]];
+        let constraints = Constraints::new(3, 3, rows, columns).unwrap();
+        let grid = Grid::new(3, 3);
+
+        let heuristic = BranchingHeuristic::new(BranchingStrategy::LinePlacement);
+        let branch = heuristic.choose(&grid, &constraints).unwrap();
+        assert!(!branch.complete);
+        assert_eq!(branch.alternatives.len(), 2);
+
+        let fewest = BranchingHeuristic::new(BranchingStrategy::FewestPlacements);
+        let branch = fewest.choose(&grid, &constraints).unwrap();
+        assert!(branch.complete);
+        assert_eq!(branch.alternatives.len(), 2);
+    }
+}
@@ -82,17 +82,15 @@ impl ContradictionDetector {
     }
 
     /// Vérifie les contradictions de base pour une ligne
+    ///
+    /// Deux segments noirs séparés par des cases vides peuvent encore former un
+    /// seul bloc: seuls les blocs dépassant le plus grand indice sont contradictoires.
     fn check_line_basic(&self, line: &[CellState], constraint: &[usize]) -> bool {
         let filled_blocks = self.count_filled_blocks(line);
-        
-        if filled_blocks.len() > constraint.len() {
-            return false;
-        }
+        let largest_block = constraint.iter().copied().max().unwrap_or(0);
 
-        for (i, (_, size)) in filled_blocks.iter().enumerate() {
-            if i < constraint.len() && *size > constraint[i] {
-                return false;
-            }
+        if filled_blocks.iter().any(|&(_, size)| size > largest_block) {
+            return false;
         }
 
         let filled_count = line.iter().filter(|&&c| c == CellState::Filled).count();
@@ -103,13 +101,7 @@ impl ContradictionDetector {
         }
 
         let available_space = line.iter().filter(|&&c| c != CellState::Crossed).count();
-        let min_required_space = if constraint.is_empty() {
-            0
-        } else {
-            constraint.iter().sum::<usize>() + constraint.len() - 1
-        };
-
-        if available_space < min_required_space {
+        if available_space < required_count {
             return false;
         }
 
@@ -154,7 +146,7 @@ impl ContradictionDetector {
     /// Vérifie les blocs impossibles pour une ligne
     fn check_line_impossible_blocks(&self, line: &[CellState], constraint: &[usize]) -> bool {
         if constraint.is_empty() {
-            return !line.iter().any(|&c| c == CellState::Filled);
+            return !line.contains(&CellState::Filled);
         }
 
         let segments = self.find_available_segments(line);
@@ -173,10 +165,7 @@ impl ContradictionDetector {
     fn check_deduction_contradictions(&mut self, grid: &Grid, constraints: &Constraints) -> bool {
         let mut test_grid = grid.clone();
         
-        match self.solver.solve(&mut test_grid, constraints) {
-            Ok(_) => true,
-            Err(_) => false,
-        }
+        self.solver.solve(&mut test_grid, constraints).is_ok()
     }
 
     /// Compte les blocs de cases remplies
@@ -248,6 +237,12 @@ impl ContradictionDetector {
     }
 }
 
+impl Default for ContradictionDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,8 +250,9 @@ mod tests {
     #[test]
     fn test_valid_grid() {
         let mut grid = Grid::new(5, 1);
-        let mut constraints = Constraints::new(5, 1);
-        constraints.set_row_constraint(0, vec![2]);
+        let rows = vec![vec![2]];
+        let columns = vec![vec![], vec![1], vec![1], vec![], vec![]];
+        let constraints = Constraints::new(5, 1, rows, columns).unwrap();
         
         grid.set(0, 1, CellState::Filled).unwrap();
         grid.set(0, 2, CellState::Filled).unwrap();
@@ -268,8 +264,9 @@ mod tests {
     #[test]
     fn test_invalid_grid_too_many_blocks() {
         let mut grid = Grid::new(5, 1);
-        let mut constraints = Constraints::new(5, 1);
-        constraints.set_row_constraint(0, vec![1]);
+        let rows = vec![vec![1]];
+        let columns = vec![vec![1], vec![], vec![], vec![], vec![]];
+        let constraints = Constraints::new(5, 1, rows, columns).unwrap();
         
         grid.set(0, 0, CellState::Filled).unwrap();
         grid.set(0, 2, CellState::Filled).unwrap();
@@ -282,8 +279,9 @@ mod tests {
     #[test]
     fn test_hypothesis_valid() {
         let grid = Grid::new(5, 1);
-        let mut constraints = Constraints::new(5, 1);
-        constraints.set_row_constraint(0, vec![2]);
+        let rows = vec![vec![2]];
+        let columns = vec![vec![], vec![1], vec![1], vec![], vec![]];
+        let constraints = Constraints::new(5, 1, rows, columns).unwrap();
         
         let mut detector = ContradictionDetector::new();
         assert!(detector.test_hypothesis(&grid, &constraints, 0, 1, CellState::Filled));
@@ -86,8 +86,8 @@ impl CrossAnalyzer {
                 let overlap_start = max_pos;
                 let overlap_end = min_pos + block_size;
                 
-                for col in overlap_start..overlap_end {
-                    if col < length && line[col] == CellState::Empty {
+                for (col, &cell) in line.iter().enumerate().take(overlap_end).skip(overlap_start) {
+                    if cell == CellState::Empty {
                         deductions.push(Deduction {
                             row,
                             col,
@@ -121,8 +121,8 @@ impl CrossAnalyzer {
                 let overlap_start = max_pos;
                 let overlap_end = min_pos + block_size;
                 
-                for row in overlap_start..overlap_end {
-                    if row < length && column[row] == CellState::Empty {
+                for (row, &cell) in column.iter().enumerate().take(overlap_end).skip(overlap_start) {
+                    if cell == CellState::Empty {
                         deductions.push(Deduction {
                             row,
                             col,
@@ -152,11 +152,11 @@ impl CrossAnalyzer {
         let first_block = constraint[0];
         for (col, &cell) in line.iter().enumerate() {
             if cell == CellState::Filled {
-                // Une case remplie proche du début force le premier bloc
+                // Une case remplie proche du début appartient au premier bloc
                 if col < first_block {
-                    // Remplir les cases nécessaires pour compléter le bloc
-                    for fill_col in 0..first_block {
-                        if line[fill_col] == CellState::Empty {
+                    // Le bloc commence au plus tard ici: il couvre au moins jusqu'à first_block
+                    for (fill_col, &fill) in line.iter().enumerate().take(first_block).skip(col) {
+                        if fill == CellState::Empty {
                             deductions.push(Deduction {
                                 row,
                                 col: fill_col,
@@ -164,8 +164,8 @@ impl CrossAnalyzer {
                             });
                         }
                     }
-                    // Barrer la case après le bloc
-                    if first_block < line.len() && line[first_block] == CellState::Empty {
+                    // Barrer la case après le bloc s'il commence forcément au bord
+                    if col == 0 && first_block < line.len() && line[first_block] == CellState::Empty {
                         deductions.push(Deduction {
                             row,
                             col: first_block,
@@ -182,11 +182,10 @@ impl CrossAnalyzer {
         let length = line.len();
         for col in (0..length).rev() {
             if line[col] == CellState::Filled {
-                // Une case remplie proche de la fin force le dernier bloc
+                // Une case remplie proche de la fin appartient au dernier bloc
                 if col >= length - last_block {
-                    // Remplir les cases nécessaires pour compléter le bloc
-                    for fill_col in (length - last_block)..length {
-                        if line[fill_col] == CellState::Empty {
+                    for (fill_col, &fill) in line.iter().enumerate().take(col + 1).skip(length - last_block) {
+                        if fill == CellState::Empty {
                             deductions.push(Deduction {
                                 row,
                                 col: fill_col,
@@ -194,8 +193,8 @@ impl CrossAnalyzer {
                             });
                         }
                     }
-                    // Barrer la case avant le bloc
-                    if length > last_block && line[length - last_block - 1] == CellState::Empty {
+                    // Barrer la case avant le bloc s'il finit forcément au bord
+                    if col == length - 1 && length > last_block && line[length - last_block - 1] == CellState::Empty {
                         deductions.push(Deduction {
                             row,
                             col: length - last_block - 1,
@@ -223,26 +222,24 @@ impl CrossAnalyzer {
         // Forcer depuis le haut
         let first_block = constraint[0];
         for (row, &cell) in column.iter().enumerate() {
-            if cell == CellState::Filled {
-                if row < first_block {
-                    for fill_row in 0..first_block {
-                        if column[fill_row] == CellState::Empty {
-                            deductions.push(Deduction {
-                                row: fill_row,
-                                col,
-                                state: CellState::Filled,
-                            });
-                        }
-                    }
-                    if first_block < column.len() && column[first_block] == CellState::Empty {
+            if cell == CellState::Filled && row < first_block {
+                for (fill_row, &fill) in column.iter().enumerate().take(first_block).skip(row) {
+                    if fill == CellState::Empty {
                         deductions.push(Deduction {
-                            row: first_block,
+                            row: fill_row,
                             col,
-                            state: CellState::Crossed,
+                            state: CellState::Filled,
                         });
                     }
-                    break;
                 }
+                if row == 0 && first_block < column.len() && column[first_block] == CellState::Empty {
+                    deductions.push(Deduction {
+                        row: first_block,
+                        col,
+                        state: CellState::Crossed,
+                    });
+                }
+                break;
             }
         }
 
@@ -250,26 +247,24 @@ impl CrossAnalyzer {
         let last_block = *constraint.last().unwrap();
         let length = column.len();
         for row in (0..length).rev() {
-            if column[row] == CellState::Filled {
-                if row >= length - last_block {
-                    for fill_row in (length - last_block)..length {
-                        if column[fill_row] == CellState::Empty {
-                            deductions.push(Deduction {
-                                row: fill_row,
-                                col,
-                                state: CellState::Filled,
-                            });
-                        }
-                    }
-                    if length > last_block && column[length - last_block - 1] == CellState::Empty {
+            if column[row] == CellState::Filled && row >= length - last_block {
+                for (fill_row, &fill) in column.iter().enumerate().take(row + 1).skip(length - last_block) {
+                    if fill == CellState::Empty {
                         deductions.push(Deduction {
-                            row: length - last_block - 1,
+                            row: fill_row,
                             col,
-                            state: CellState::Crossed,
+                            state: CellState::Filled,
                         });
                     }
-                    break;
                 }
+                if row == length - 1 && length > last_block && column[length - last_block - 1] == CellState::Empty {
+                    deductions.push(Deduction {
+                        row: length - last_block - 1,
+                        col,
+                        state: CellState::Crossed,
+                    });
+                }
+                break;
             }
         }
 
@@ -278,20 +273,21 @@ impl CrossAnalyzer {
 
     /// Calcule la position minimale d'un bloc
     fn calculate_min_position(&self, constraint: &[usize], block_idx: usize) -> usize {
-        let mut pos = 0;
-        for i in 0..block_idx {
-            pos += constraint[i] + 1; // Bloc + espace minimum
-        }
-        pos
+        // Bloc + espace minimum pour chaque bloc précédent
+        constraint[..block_idx].iter().map(|&size| size + 1).sum()
     }
 
     /// Calcule la position maximale d'un bloc
     fn calculate_max_position(&self, constraint: &[usize], block_idx: usize, length: usize) -> usize {
-        let mut pos = length;
-        for i in (block_idx + 1)..constraint.len() {
-            pos = pos.saturating_sub(constraint[i] + 1);
-        }
-        pos.saturating_sub(constraint[block_idx])
+        constraint[block_idx + 1..].iter()
+            .fold(length, |pos, &size| pos.saturating_sub(size + 1))
+            .saturating_sub(constraint[block_idx])
+    }
+}
+
+impl Default for CrossAnalyzer {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -301,9 +297,10 @@ mod tests {
 
     #[test]
     fn test_overlap_analysis_simple() {
-        let mut grid = Grid::new(7, 1);
-        let mut constraints = Constraints::new(7, 1);
-        constraints.set_row_constraint(0, vec![5]);
+        let grid = Grid::new(7, 1);
+        let rows = vec![vec![5]];
+        let columns = vec![vec![], vec![1], vec![1], vec![1], vec![1], vec![1], vec![]];
+        let constraints = Constraints::new(7, 1, rows, columns).unwrap();
         
         let analyzer = CrossAnalyzer::new();
         let deductions = analyzer.analyze(&grid, &constraints).unwrap();
@@ -317,17 +314,27 @@ mod tests {
     #[test]
     fn test_edge_forcing() {
         let mut grid = Grid::new(5, 1);
-        let mut constraints = Constraints::new(5, 1);
-        constraints.set_row_constraint(0, vec![3]);
+        let rows = vec![vec![3]];
+        let columns = vec![vec![1], vec![1], vec![1], vec![], vec![]];
+        let constraints = Constraints::new(5, 1, rows, columns).unwrap();
         
-        // Placer une case remplie près du début
-        grid.set(0, 1, CellState::Filled).unwrap();
+        // Placer une case remplie au bord
+        grid.set(0, 0, CellState::Filled).unwrap();
         
         let analyzer = CrossAnalyzer::new();
         let deductions = analyzer.analyze(&grid, &constraints).unwrap();
         
         // Le bloc de 3 doit être forcé à partir de la position 0
-        assert!(deductions.iter().any(|d| d.col == 0 && d.state == CellState::Filled));
+        assert!(deductions.iter().any(|d| d.col == 1 && d.state == CellState::Filled));
         assert!(deductions.iter().any(|d| d.col == 2 && d.state == CellState::Filled));
+        assert!(deductions.iter().any(|d| d.col == 3 && d.state == CellState::Crossed));
+
+        // Une case noire en position 1 ne garantit pas la position 0 (bloc en 1..4 possible)
+        let columns = vec![vec![], vec![1], vec![1], vec![1], vec![]];
+        let constraints = Constraints::new(5, 1, vec![vec![3]], columns).unwrap();
+        let mut grid = Grid::new(5, 1);
+        grid.set(0, 1, CellState::Filled).unwrap();
+        let deductions = analyzer.analyze(&grid, &constraints).unwrap();
+        assert!(!deductions.iter().any(|d| d.col == 0));
     }
 }
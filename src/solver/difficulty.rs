@@ -0,0 +1,127 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use serde::Serialize;
+use crate::grid::{Constraints, Grid};
+use super::{AdvancedSolver, NonogramSolver, SolveStep, Technique};
+
+/// Niveau de difficulté d'un nonogramme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    /// Résolu ligne par ligne en quelques passes
+    Easy,
+    /// Résolu ligne par ligne, en revenant souvent sur les mêmes lignes
+    Medium,
+    /// Demande l'analyse croisée ou les heuristiques avancées
+    Hard,
+    /// Demande des hypothèses (recherche avec retour arrière)
+    Expert,
+}
+
+impl Difficulty {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Facile",
+            Difficulty::Medium => "Moyen",
+            Difficulty::Hard => "Difficile",
+            Difficulty::Expert => "Expert",
+        }
+    }
+}
+
+/// Évaluation de la difficulté
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Rating {
+    pub difficulty: Difficulty,
+    /// Passes sur les lignes et colonnes de la logique ligne par ligne
+    pub passes: usize,
+    /// Cases trouvées par la logique ligne par ligne
+    pub logic_cells: usize,
+    pub total_cells: usize,
+}
+
+/// Évalue la difficulté d'un nonogramme d'après les techniques nécessaires pour le résoudre
+pub struct DifficultyRater {
+    /// Nombre maximal de passes pour un nonogramme facile
+    easy_passes: usize,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl DifficultyRater {
+    pub fn new() -> Self {
+        Self { easy_passes: 3, cancel: None }
+    }
+
+    /// Drapeau d'annulation, transmis au solveur avancé
+    pub fn set_cancel_flag(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
+    }
+
+    pub fn rate(&self, constraints: &Constraints) -> Result<Rating, String> {
+        let mut grid = Grid::new(constraints.width, constraints.height);
+        let steps = NonogramSolver::new().solve_steps(&mut grid, constraints)?;
+        let total_cells = constraints.width * constraints.height;
+        let logic_cells = total_cells - grid.count_empty_cells();
+        let passes = Self::count_passes(&steps, constraints.height);
+
+        let difficulty = if logic_cells == total_cells {
+            if passes <= self.easy_passes { Difficulty::Easy } else { Difficulty::Medium }
+        } else {
+            // Les heuristiques peuvent se tromper: seule une grille complète et juste compte
+            let mut solver = AdvancedSolver::new();
+            if let Some(cancel) = &self.cancel {
+                solver.set_cancel_flag(Arc::clone(cancel));
+            }
+            let solved = solver.solve(&mut grid, constraints).is_ok()
+                && grid.count_empty_cells() == 0
+                && constraints.is_satisfied_by(&grid);
+            if solved { Difficulty::Hard } else { Difficulty::Expert }
+        };
+
+        Ok(Rating { difficulty, passes, logic_cells, total_cells })
+    }
+
+    /// Une passe parcourt les lignes puis les colonnes; revenir en arrière en commence une nouvelle
+    fn count_passes(steps: &[SolveStep], height: usize) -> usize {
+        let order = |step: &SolveStep| match step.technique {
+            Technique::Row(row) => row,
+            Technique::Column(col) => height + col,
+            _ => usize::MAX,
+        };
+        let mut passes = 0;
+        let mut previous = None;
+        for step in steps {
+            let current = order(step);
+            if previous.is_none_or(|previous| current <= previous) {
+                passes += 1;
+            }
+            previous = Some(current);
+        }
+        passes
+    }
+}
+
+impl Default for DifficultyRater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rates_line_solvable_and_ambiguous_puzzles() {
+        let cross = Constraints::new(3, 3, vec![vec![1], vec![3], vec![1]], vec![vec![1], vec![3], vec![1]]).unwrap();
+        let rating = DifficultyRater::new().rate(&cross).unwrap();
+        assert_eq!(rating.difficulty, Difficulty::Easy);
+        assert_eq!(rating.logic_cells, 9);
+
+        // Deux solutions: aucune technique logique ne peut conclure
+        let diagonal = Constraints::new(2, 2, vec![vec![1], vec![1]], vec![vec![1], vec![1]]).unwrap();
+        let rating = DifficultyRater::new().rate(&diagonal).unwrap();
+        assert_eq!(rating.difficulty, Difficulty::Expert);
+        assert_eq!(rating.logic_cells, 0);
+    }
+}
@@ -77,9 +77,9 @@ impl LineSolver {
         // Cas de base: tous les blocs ont été placés
         if block_index >= constraint.len() {
             // Remplir le reste avec des Crossed
-            for i in start_pos..length {
-                if current[i] == CellState::Empty {
-                    current[i] = CellState::Crossed;
+            for cell in &mut current[start_pos..length] {
+                if *cell == CellState::Empty {
+                    *cell = CellState::Crossed;
                 }
             }
 
@@ -104,9 +104,9 @@ impl LineSolver {
             let mut new_current = current.clone();
 
             // Placer des Crossed avant le bloc
-            for i in start_pos..pos {
-                if new_current[i] == CellState::Empty {
-                    new_current[i] = CellState::Crossed;
+            for cell in &mut new_current[start_pos..pos] {
+                if *cell == CellState::Empty {
+                    *cell = CellState::Crossed;
                 }
             }
 
@@ -170,6 +170,12 @@ impl LineSolver {
     }
 }
 
+impl Default for LineSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -219,22 +225,20 @@ mod tests {
         let solver = LineSolver::new();
         let line = vec![CellState::Empty; 7];
         let constraint = vec![2, 2];
-        let deductions = solver.solve_line(&line, &constraint).unwrap();
-        
         // Peut ou non avoir des déductions selon les positions possibles
         // Ce test vérifie simplement qu'il n'y a pas d'erreur
-        assert!(true);
+        assert!(solver.solve_line(&line, &constraint).is_ok());
     }
 
     #[test]
     fn test_with_existing_filled() {
         let solver = LineSolver::new();
         let mut line = vec![CellState::Empty; 5];
-        line[2] = CellState::Filled;
+        line[1] = CellState::Filled;
         let constraint = vec![3];
         let deductions = solver.solve_line(&line, &constraint).unwrap();
         
-        // Le bloc de 3 doit inclure la case 2, donc les cases adjacentes peuvent être déduites
+        // Le bloc de 3 doit inclure la case 1, donc la case 2 est noire et la case 4 barrée
         assert!(!deductions.is_empty());
     }
 }
@@ -1,10 +1,13 @@
 use crate::grid::CellState;
 use std::collections::HashMap;
 
+/// Déductions d'une ligne: (position, état) des cases déterminées
+type LineDeductions = Result<Vec<(usize, CellState)>, String>;
+
 /// Solveur optimisé pour une ligne ou colonne individuelle avec cache
 pub struct OptimizedLineSolver {
-    /// Cache des configurations valides pour éviter les recalculs
-    cache: HashMap<(Vec<CellState>, Vec<usize>), Vec<Vec<CellState>>>,
+    /// Cache des déductions par ligne pour éviter les recalculs
+    cache: HashMap<(Vec<CellState>, Vec<usize>), LineDeductions>,
 }
 
 impl OptimizedLineSolver {
@@ -15,11 +18,12 @@ impl OptimizedLineSolver {
     }
 
     /// Résout une ligne en utilisant la déduction logique avec optimisations
-    pub fn solve_line(&mut self, line: &[CellState], constraint: &[usize]) -> Result<Vec<(usize, CellState)>, String> {
-        let length = line.len();
-        
+    pub fn solve_line(&mut self, line: &[CellState], constraint: &[usize]) -> LineDeductions {
         // Cas spécial: contrainte vide
         if constraint.is_empty() {
+            if line.contains(&CellState::Filled) {
+                return Err("Aucune configuration valide trouvée pour cette ligne".to_string());
+            }
             let mut deductions = Vec::new();
             for (i, &cell) in line.iter().enumerate() {
                 if cell == CellState::Empty {
@@ -31,204 +35,214 @@ impl OptimizedLineSolver {
 
         // Vérifier le cache
         let cache_key = (line.to_vec(), constraint.to_vec());
-        let valid_configs = if let Some(cached) = self.cache.get(&cache_key) {
-            cached.clone()
-        } else {
-            let configs = self.generate_valid_configurations_optimized(line, constraint)?;
-            self.cache.insert(cache_key, configs.clone());
-            configs
-        };
-
-        if valid_configs.is_empty() {
+        if let Some(cached) = self.cache.get(&cache_key) {
+            return cached.clone();
+        }
+        let deductions = Self::common_cells(line, constraint);
+        self.cache.insert(cache_key, deductions.clone());
+        deductions
+    }
+
+    /// Cases qui ont la même valeur dans toutes les configurations valides, trouvées
+    /// sans les énumérer (leur nombre explose sur les longues lignes): une case peut être
+    /// noire (ou blanche) si un placement complet passe par cet état
+    fn common_cells(line: &[CellState], constraint: &[usize]) -> LineDeductions {
+        let length = line.len();
+        let blocks = constraint.len();
+        let backward = Self::backward_log_counts(line, constraint);
+        if backward[0][0] == f64::NEG_INFINITY {
             return Err("Aucune configuration valide trouvée pour cette ligne".to_string());
         }
+        let completes = |pos: usize, k: usize| backward[pos][k] != f64::NEG_INFINITY;
+
+        // forward[pos][k]: les blocs 0..k tiennent dans les cases 0..pos et la suite peut être placée
+        let mut forward = vec![vec![false; blocks + 1]; length + 2];
+        forward[0][0] = true;
+        let mut can_cross = vec![false; length];
+        // Couverture par les blocs (somme préfixe non nulle: la case peut être noire)
+        let mut coverage = vec![0i64; length + 1];
+
+        for pos in 0..length {
+            for k in 0..=blocks {
+                if !forward[pos][k] {
+                    continue;
+                }
+
+                if line[pos] != CellState::Filled && completes(pos + 1, k) {
+                    forward[pos + 1][k] = true;
+                    can_cross[pos] = true;
+                }
 
-        // Optimisation: utiliser un compteur pour chaque position
-        let mut filled_count = vec![0usize; length];
-        let mut crossed_count = vec![0usize; length];
-        let total_configs = valid_configs.len();
-
-        for config in &valid_configs {
-            for (pos, &state) in config.iter().enumerate() {
-                match state {
-                    CellState::Filled => filled_count[pos] += 1,
-                    CellState::Crossed => crossed_count[pos] += 1,
-                    CellState::Empty => {}
+                if k < blocks && Self::block_fits(line, pos, constraint[k]) {
+                    let end = pos + constraint[k];
+                    if completes(end + 1, k + 1) {
+                        forward[end + 1][k + 1] = true;
+                        coverage[pos] += 1;
+                        coverage[end] -= 1;
+                        // Case de séparation après le bloc
+                        if end < length {
+                            can_cross[end] = true;
+                        }
+                    }
                 }
             }
         }
 
-        // Déduire les cases qui ont la même valeur dans toutes les configurations
         let mut deductions = Vec::new();
+        let mut covered = 0;
         for pos in 0..length {
+            covered += coverage[pos];
             if line[pos] != CellState::Empty {
                 continue;
             }
 
-            if filled_count[pos] == total_configs {
-                deductions.push((pos, CellState::Filled));
-            } else if crossed_count[pos] == total_configs {
-                deductions.push((pos, CellState::Crossed));
+            match (covered > 0, can_cross[pos]) {
+                (true, false) => deductions.push((pos, CellState::Filled)),
+                (false, true) => deductions.push((pos, CellState::Crossed)),
+                _ => {}
             }
         }
 
         Ok(deductions)
     }
 
-    /// Génère les configurations valides (interface publique)
-    pub fn generate_valid_configurations(&self, line: &[CellState], constraint: &[usize]) -> Vec<Vec<CellState>> {
-        self.generate_valid_configurations_optimized(line, constraint).unwrap_or_default()
+    /// Logarithme naturel du nombre de placements compatibles avec la ligne
+    /// (`f64::NEG_INFINITY` si aucun placement n'est possible)
+    pub fn log_placement_count(&self, line: &[CellState], constraint: &[usize]) -> f64 {
+        Self::backward_log_counts(line, constraint)[0][0]
     }
 
-    /// Génère les configurations valides avec optimisations
-    fn generate_valid_configurations_optimized(&self, line: &[CellState], constraint: &[usize]) -> Result<Vec<Vec<CellState>>, String> {
+    /// Probabilité que chaque case soit noire, tous les placements compatibles
+    /// étant équiprobables. Les comptages sont faits en espace logarithmique
+    /// pour ne pas déborder sur les longues lignes.
+    pub fn fill_probabilities(&self, line: &[CellState], constraint: &[usize]) -> Result<Vec<f64>, String> {
         let length = line.len();
-        let mut configurations = Vec::new();
+        let blocks = constraint.len();
+        let backward = Self::backward_log_counts(line, constraint);
+        let total = backward[0][0];
 
-        // Élagage précoce: vérifier si la contrainte est satisfaisable
-        let min_length = self.min_line_length(constraint);
-        if min_length > length {
-            return Ok(configurations);
+        if total == f64::NEG_INFINITY {
+            return Err("Aucune configuration valide trouvée pour cette ligne".to_string());
         }
 
-        self.generate_recursive_optimized(
-            line,
-            constraint,
-            0,
-            0,
-            vec![CellState::Empty; length],
-            &mut configurations,
-        );
+        // forward[pos][k] = placements des blocs 0..k dans les cases 0..pos,
+        // la case pos étant libre de commencer un bloc
+        let mut forward = vec![vec![f64::NEG_INFINITY; blocks + 1]; length + 2];
+        forward[0][0] = 0.0;
+        // coverage[pos] = différences de probabilité de couverture (somme préfixe)
+        let mut coverage = vec![0.0f64; length + 1];
 
-        Ok(configurations)
-    }
+        for pos in 0..length {
+            for k in 0..=blocks {
+                let ways = forward[pos][k];
+                if ways == f64::NEG_INFINITY {
+                    continue;
+                }
 
-    /// Génération récursive optimisée avec élagage précoce
-    fn generate_recursive_optimized(
-        &self,
-        line: &[CellState],
-        constraint: &[usize],
-        block_index: usize,
-        start_pos: usize,
-        mut current: Vec<CellState>,
-        results: &mut Vec<Vec<CellState>>,
-    ) {
-        let length = line.len();
+                if line[pos] != CellState::Filled {
+                    forward[pos + 1][k] = log_add(forward[pos + 1][k], ways);
+                }
 
-        // Cas de base
-        if block_index >= constraint.len() {
-            for i in start_pos..length {
-                if current[i] == CellState::Empty {
-                    current[i] = CellState::Crossed;
+                if k < blocks && Self::block_fits(line, pos, constraint[k]) {
+                    let end = pos + constraint[k];
+                    forward[end + 1][k + 1] = log_add(forward[end + 1][k + 1], ways);
+
+                    let probability = (ways + backward[end + 1][k + 1] - total).exp();
+                    coverage[pos] += probability;
+                    coverage[end] -= probability;
                 }
             }
+        }
 
-            if self.is_compatible(&current, line) {
-                results.push(current);
-            }
-            return;
+        let mut probabilities = Vec::with_capacity(length);
+        let mut running = 0.0;
+        for pos in 0..length {
+            running += coverage[pos];
+            probabilities.push(match line[pos] {
+                CellState::Filled => 1.0,
+                CellState::Crossed => 0.0,
+                CellState::Empty => running.clamp(0.0, 1.0),
+            });
         }
 
-        let block_size = constraint[block_index];
-        let remaining_blocks: usize = constraint[block_index + 1..].iter().sum();
-        let remaining_spaces = constraint.len().saturating_sub(block_index + 1);
-        let min_space_needed = remaining_blocks + remaining_spaces;
+        Ok(probabilities)
+    }
 
-        // Élagage: si pas assez d'espace, arrêter
-        if start_pos + block_size + min_space_needed > length {
-            return;
-        }
+    /// backward[pos][k] = log du nombre de placements des blocs k.. dans les cases pos..
+    /// (la ligne pos = length + 1 sert de sentinelle pour un bloc finissant au bord)
+    fn backward_log_counts(line: &[CellState], constraint: &[usize]) -> Vec<Vec<f64>> {
+        let length = line.len();
+        let blocks = constraint.len();
+        let mut backward = vec![vec![f64::NEG_INFINITY; blocks + 1]; length + 2];
+        backward[length][blocks] = 0.0;
+        backward[length + 1][blocks] = 0.0;
 
-        // Essayer de placer le bloc à différentes positions
-        let max_pos = length.saturating_sub(block_size + min_space_needed);
-        
-        for pos in start_pos..=max_pos {
-            // Élagage précoce: vérifier la compatibilité avant de continuer
-            let mut can_place = true;
-            
-            // Vérifier que les cases avant peuvent être Crossed
-            for i in start_pos..pos {
-                if line[i] == CellState::Filled {
-                    can_place = false;
-                    break;
+        for pos in (0..length).rev() {
+            for k in (0..=blocks).rev() {
+                let mut total = f64::NEG_INFINITY;
+
+                if line[pos] != CellState::Filled {
+                    total = log_add(total, backward[pos + 1][k]);
                 }
-            }
-            
-            if !can_place {
-                continue;
-            }
 
-            // Vérifier que le bloc peut être placé ici
-            for i in pos..(pos + block_size) {
-                if line[i] == CellState::Crossed {
-                    can_place = false;
-                    break;
+                if k < blocks && Self::block_fits(line, pos, constraint[k]) {
+                    total = log_add(total, backward[pos + constraint[k] + 1][k + 1]);
                 }
-            }
 
-            if !can_place {
-                continue;
+                backward[pos][k] = total;
             }
+        }
 
-            // Vérifier l'espace après le bloc
-            if block_index + 1 < constraint.len() && pos + block_size < length {
-                if line[pos + block_size] == CellState::Filled {
-                    continue;
-                }
-            }
+        backward
+    }
 
-            // Placer le bloc
-            let mut new_current = current.clone();
+    /// Un bloc peut commencer en `pos` s'il ne couvre aucune case barrée
+    /// et n'est pas immédiatement suivi d'une case noire
+    fn block_fits(line: &[CellState], pos: usize, block_size: usize) -> bool {
+        let end = pos + block_size;
+        end <= line.len()
+            && line[pos..end].iter().all(|&cell| cell != CellState::Crossed)
+            && (end == line.len() || line[end] != CellState::Filled)
+    }
 
-            for i in start_pos..pos {
-                if new_current[i] == CellState::Empty {
-                    new_current[i] = CellState::Crossed;
-                }
-            }
+    /// Énumère les placements compatibles avec la ligne, en ne suivant que les choix
+    /// qui mènent à un placement complet (à réserver aux lignes qui en ont peu)
+    pub fn generate_valid_configurations(&self, line: &[CellState], constraint: &[usize]) -> Vec<Vec<CellState>> {
+        let backward = Self::backward_log_counts(line, constraint);
+        let mut configurations = Vec::new();
+        if backward[0][0] != f64::NEG_INFINITY {
+            let mut current = vec![CellState::Crossed; line.len()];
+            Self::collect_placements(line, constraint, &backward, (0, 0), &mut current, &mut configurations);
+        }
+        configurations
+    }
 
-            for i in pos..(pos + block_size) {
-                new_current[i] = CellState::Filled;
-            }
+    /// Complète `current` à partir de la case `pos` avec les blocs `k..`
+    fn collect_placements(
+        line: &[CellState],
+        constraint: &[usize],
+        backward: &[Vec<f64>],
+        (pos, k): (usize, usize),
+        current: &mut Vec<CellState>,
+        results: &mut Vec<Vec<CellState>>,
+    ) {
+        if pos >= line.len() {
+            results.push(current.clone());
+            return;
+        }
 
-            let next_start = if block_index + 1 < constraint.len() {
-                if pos + block_size < length {
-                    new_current[pos + block_size] = CellState::Crossed;
-                    pos + block_size + 1
-                } else {
-                    continue;
-                }
-            } else {
-                pos + block_size
-            };
-
-            self.generate_recursive_optimized(
-                line,
-                constraint,
-                block_index + 1,
-                next_start,
-                new_current,
-                results,
-            );
+        if line[pos] != CellState::Filled && backward[pos + 1][k] != f64::NEG_INFINITY {
+            Self::collect_placements(line, constraint, backward, (pos + 1, k), current, results);
         }
-    }
 
-    /// Vérifie la compatibilité
-    fn is_compatible(&self, config: &[CellState], line: &[CellState]) -> bool {
-        config.iter().zip(line.iter()).all(|(&config_cell, &line_cell)| {
-            match line_cell {
-                CellState::Empty => true,
-                CellState::Filled => config_cell == CellState::Filled,
-                CellState::Crossed => config_cell == CellState::Crossed,
+        if k < constraint.len() && Self::block_fits(line, pos, constraint[k]) {
+            let end = pos + constraint[k];
+            if backward[end + 1][k + 1] != f64::NEG_INFINITY {
+                current[pos..end].fill(CellState::Filled);
+                Self::collect_placements(line, constraint, backward, (end + 1, k + 1), current, results);
+                current[pos..end].fill(CellState::Crossed);
             }
-        })
-    }
-
-    /// Calcule la longueur minimale nécessaire
-    fn min_line_length(&self, constraint: &[usize]) -> usize {
-        if constraint.is_empty() {
-            return 0;
         }
-        constraint.iter().sum::<usize>() + constraint.len() - 1
     }
 
     /// Vide le cache (utile pour libérer la mémoire)
@@ -237,6 +251,18 @@ impl OptimizedLineSolver {
     }
 }
 
+/// log(exp(a) + exp(b)) sans débordement
+fn log_add(a: f64, b: f64) -> f64 {
+    if a == f64::NEG_INFINITY {
+        return b;
+    }
+    if b == f64::NEG_INFINITY {
+        return a;
+    }
+    let max = a.max(b);
+    max + ((a - max).exp() + (b - max).exp()).ln()
+}
+
 impl Default for OptimizedLineSolver {
     fn default() -> Self {
         Self::new()
@@ -278,4 +304,77 @@ mod tests {
         solver.clear_cache();
         assert_eq!(solver.cache.len(), 0);
     }
+
+    /// Placements obtenus en essayant toutes les lignes possibles
+    fn brute_force(line: &[CellState], constraint: &[usize]) -> Vec<Vec<CellState>> {
+        (0..1u32 << line.len())
+            .map(|mask| (0..line.len())
+                .map(|pos| if mask >> pos & 1 == 1 { CellState::Filled } else { CellState::Crossed })
+                .collect::<Vec<_>>())
+            .filter(|config| config.iter().zip(line).all(|(&cell, &known)| known == CellState::Empty || cell == known))
+            .filter(|config| {
+                let runs: Vec<usize> = config.split(|&cell| cell != CellState::Filled)
+                    .map(<[CellState]>::len)
+                    .filter(|&run| run > 0)
+                    .collect();
+                runs == constraint
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_long_lines_match_enumeration() {
+        let mut solver = OptimizedLineSolver::new();
+        let mut line = vec![CellState::Empty; 9];
+        line[1] = CellState::Filled;
+        line[5] = CellState::Crossed;
+        let constraint = vec![2, 1, 2];
+
+        // Mêmes placements et mêmes déductions qu'en essayant toutes les lignes
+        let mut configs = solver.generate_valid_configurations(&line, &constraint);
+        let mut expected_configs = brute_force(&line, &constraint);
+        configs.sort_by_key(|config| format!("{:?}", config));
+        expected_configs.sort_by_key(|config| format!("{:?}", config));
+        assert_eq!(configs, expected_configs);
+
+        let expected: Vec<(usize, CellState)> = (0..line.len())
+            .filter(|&pos| line[pos] == CellState::Empty)
+            .filter_map(|pos| {
+                let state = configs[0][pos];
+                configs.iter().all(|config| config[pos] == state).then_some((pos, state))
+            })
+            .collect();
+        assert_eq!(solver.solve_line(&line, &constraint).unwrap(), expected);
+        assert!(solver.solve_line(&line, &[3, 3, 3]).is_err());
+        assert!(solver.generate_valid_configurations(&line, &[3, 3, 3]).is_empty());
+
+        // Des milliards de placements: la ligne est résolue sans les énumérer
+        let long_line = vec![CellState::Empty; 100];
+        assert!(solver.solve_line(&long_line, &[1; 30]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fill_probabilities() {
+        let solver = OptimizedLineSolver::new();
+        let line = vec![CellState::Empty; 5];
+
+        assert!((solver.log_placement_count(&line, &[1, 1]).exp() - 6.0).abs() < 1e-9);
+
+        // 4 placements pour un bloc de 2: les extrémités ne sont couvertes qu'une fois
+        let probabilities = solver.fill_probabilities(&line, &[2]).unwrap();
+        assert!((probabilities[0] - 0.25).abs() < 1e-9);
+        assert!((probabilities[2] - 0.5).abs() < 1e-9);
+
+        // Ligne longue: le nombre de placements dépasse largement u64
+        let long_line = vec![CellState::Empty; 400];
+        let constraint = vec![1; 100];
+        assert!(solver.log_placement_count(&long_line, &constraint) > (u64::MAX as f64).ln());
+        let probabilities = solver.fill_probabilities(&long_line, &constraint).unwrap();
+        let expected: f64 = probabilities.iter().sum();
+        assert!((expected - 100.0).abs() < 1e-6);
+
+        let mut blocked = vec![CellState::Empty; 3];
+        blocked[1] = CellState::Crossed;
+        assert!(solver.fill_probabilities(&blocked, &[2]).is_err());
+    }
 }
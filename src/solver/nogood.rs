@@ -0,0 +1,301 @@
+use crate::grid::{Grid, CellState, Constraints};
+use crate::solver::Deduction;
+use super::contradiction_detector::ContradictionDetector;
+use std::sync::{Arc, Mutex};
+
+/// Affectation d'une case faite lors d'un choix de branchement: (ligne, colonne, état)
+pub type Assignment = (usize, usize, CellState);
+
+/// Ensemble d'affectations qui, prises ensemble, mènent à une contradiction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoGood {
+    assignments: Vec<Assignment>,
+}
+
+impl NoGood {
+    pub fn new(mut assignments: Vec<Assignment>) -> Self {
+        assignments.sort_by_key(|&(row, col, state)| (row, col, state == CellState::Filled));
+        assignments.dedup();
+        Self { assignments }
+    }
+
+    pub fn assignments(&self) -> &[Assignment] {
+        &self.assignments
+    }
+
+    /// Le no-good est violé si toutes ses affectations sont présentes dans la grille
+    pub fn is_violated(&self, grid: &Grid) -> bool {
+        self.assignments.iter().all(|&(row, col, state)| grid.get(row, col) == Some(state))
+    }
+
+    /// Si toutes les affectations sauf une sont présentes et que la dernière case est vide,
+    /// cette case doit prendre l'état opposé
+    pub fn implied(&self, grid: &Grid) -> Option<Assignment> {
+        let mut open = None;
+
+        for &(row, col, state) in &self.assignments {
+            match grid.get(row, col) {
+                Some(current) if current == state => {}
+                Some(CellState::Empty) if open.is_none() => open = Some((row, col, state)),
+                _ => return None,
+            }
+        }
+
+        open.map(|(row, col, state)| {
+            let opposite = if state == CellState::Filled { CellState::Crossed } else { CellState::Filled };
+            (row, col, opposite)
+        })
+    }
+}
+
+/// Taille maximale des no-goods publiés dans une réserve partagée
+const MAX_SHARED_NOGOOD_SIZE: usize = 8;
+
+/// Réserve de no-goods partagée entre des recherches concurrentes (mode portfolio)
+///
+/// Un no-good ne dépend que des contraintes et de la grille de départ commune:
+/// il reste valable pour toutes les recherches partant de la même grille.
+#[derive(Debug, Clone, Default)]
+pub struct NoGoodPool {
+    nogoods: Arc<Mutex<Vec<NoGood>>>,
+}
+
+impl NoGoodPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn publish(&self, nogood: &NoGood) {
+        if let Ok(mut nogoods) = self.nogoods.lock() {
+            if !nogoods.contains(nogood) {
+                nogoods.push(nogood.clone());
+            }
+        }
+    }
+
+    /// No-goods publiés à partir de l'indice `cursor`
+    pub fn fetch_from(&self, cursor: usize) -> Vec<NoGood> {
+        self.nogoods.lock()
+            .map(|nogoods| nogoods.get(cursor..).map(|slice| slice.to_vec()).unwrap_or_default())
+            .unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nogoods.lock().map(|nogoods| nogoods.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Analyse de conflits et mémoire des no-goods appris pendant le backtracking
+pub struct ConflictAnalyzer {
+    root: Option<Grid>,
+    decisions: Vec<Assignment>,
+    nogoods: Vec<NoGood>,
+    max_nogoods: usize,
+    detector: ContradictionDetector,
+    pool: Option<NoGoodPool>,
+    pool_cursor: usize,
+}
+
+impl ConflictAnalyzer {
+    pub fn new(max_nogoods: usize) -> Self {
+        Self {
+            root: None,
+            decisions: Vec::new(),
+            nogoods: Vec::new(),
+            max_nogoods,
+            detector: ContradictionDetector::new(),
+            pool: None,
+            pool_cursor: 0,
+        }
+    }
+
+    /// Partage les no-goods appris (courts) avec d'autres recherches
+    pub fn set_pool(&mut self, pool: NoGoodPool) {
+        self.pool = Some(pool);
+        self.pool_cursor = 0;
+    }
+
+    /// Intègre les no-goods publiés par les autres recherches depuis le dernier appel
+    pub fn import_shared(&mut self) {
+        let shared = match &self.pool {
+            Some(pool) => pool.fetch_from(self.pool_cursor),
+            None => return,
+        };
+        self.pool_cursor += shared.len();
+        for nogood in shared {
+            self.store(nogood);
+        }
+    }
+
+    /// Réinitialise l'analyse à partir de la grille de départ du backtracking
+    pub fn reset(&mut self, root: &Grid) {
+        self.root = Some(root.clone());
+        self.decisions.clear();
+        self.nogoods.clear();
+        self.pool_cursor = 0;
+    }
+
+    pub fn push_decision(&mut self, decision: Assignment) {
+        self.decisions.push(decision);
+    }
+
+    pub fn pop_decision(&mut self) {
+        self.decisions.pop();
+    }
+
+    pub fn nogood_count(&self) -> usize {
+        self.nogoods.len()
+    }
+
+    /// Réduit les choix courants (plus un choix candidat) à un sous-ensemble
+    /// suffisant pour reproduire la contradiction par propagation.
+    /// Retourne None si la propagation seule ne retrouve pas la contradiction.
+    pub fn explain(&mut self, candidate: Option<Assignment>, constraints: &Constraints) -> Option<Vec<Assignment>> {
+        let root = self.root.clone()?;
+        let mut conflict: Vec<Assignment> = self.decisions.iter().copied().chain(candidate).collect();
+
+        if !self.is_contradictory(&root, &conflict, constraints) {
+            return None;
+        }
+
+        let mut index = 0;
+        while index < conflict.len() {
+            let mut reduced = conflict.clone();
+            reduced.remove(index);
+
+            if self.is_contradictory(&root, &reduced, constraints) {
+                conflict = reduced;
+            } else {
+                index += 1;
+            }
+        }
+
+        Some(conflict)
+    }
+
+    fn is_contradictory(&mut self, root: &Grid, assignments: &[Assignment], constraints: &Constraints) -> bool {
+        let mut grid = root.clone();
+        for &(row, col, state) in assignments {
+            match grid.get(row, col) {
+                Some(CellState::Empty) => {
+                    if grid.set(row, col, state).is_err() {
+                        return false;
+                    }
+                }
+                Some(current) if current == state => {}
+                _ => return true,
+            }
+        }
+        self.detector.has_contradiction(&grid, constraints)
+    }
+
+    /// Mémorise un no-good (les plus anciens sont oubliés au-delà de la limite)
+    pub fn learn(&mut self, nogood: NoGood) {
+        if let Some(pool) = &self.pool {
+            if nogood.assignments().len() <= MAX_SHARED_NOGOOD_SIZE {
+                pool.publish(&nogood);
+            }
+        }
+        self.store(nogood);
+    }
+
+    fn store(&mut self, nogood: NoGood) {
+        if self.nogoods.contains(&nogood) {
+            return;
+        }
+        if self.nogoods.len() >= self.max_nogoods {
+            self.nogoods.remove(0);
+        }
+        self.nogoods.push(nogood);
+    }
+
+    /// Retourne les affectations du premier no-good violé par la grille
+    pub fn check(&self, grid: &Grid) -> Option<Vec<Assignment>> {
+        self.nogoods.iter()
+            .find(|nogood| nogood.is_violated(grid))
+            .map(|nogood| nogood.assignments().to_vec())
+    }
+
+    /// Applique les implications des no-goods jusqu'au point fixe
+    /// Retourne les affectations du no-good violé en cas de contradiction
+    pub fn propagate(&self, grid: &mut Grid) -> Result<Vec<Deduction>, Vec<Assignment>> {
+        let mut deductions = Vec::new();
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            if let Some(conflict) = self.check(grid) {
+                return Err(conflict);
+            }
+
+            for nogood in &self.nogoods {
+                if let Some((row, col, state)) = nogood.implied(grid) {
+                    if grid.set(row, col, state).is_ok() {
+                        deductions.push(Deduction { row, col, state });
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        Ok(deductions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nogood_violation_and_implication() {
+        let nogood = NoGood::new(vec![(0, 0, CellState::Filled), (1, 1, CellState::Crossed)]);
+        let mut grid = Grid::new(2, 2);
+
+        grid.set(0, 0, CellState::Filled).unwrap();
+        assert!(!nogood.is_violated(&grid));
+        assert_eq!(nogood.implied(&grid), Some((1, 1, CellState::Filled)));
+
+        grid.set(1, 1, CellState::Crossed).unwrap();
+        assert!(nogood.is_violated(&grid));
+        assert_eq!(nogood.implied(&grid), None);
+    }
+
+    #[test]
+    fn test_explain_drops_irrelevant_decisions() {
+        // Ligne 0: [1] sur 3 cases; la case (1, 2) n'a rien à voir avec la contradiction
+        let rows = vec![vec![1], vec![1]];
+        let columns = vec![vec![1], vec![], vec![1]];
+        let constraints = Constraints::new(3, 2, rows, columns).unwrap();
+
+        let mut analyzer = ConflictAnalyzer::new(10);
+        analyzer.reset(&Grid::new(3, 2));
+        analyzer.push_decision((1, 2, CellState::Filled));
+        analyzer.push_decision((0, 0, CellState::Filled));
+
+        let conflict = analyzer.explain(Some((0, 2, CellState::Filled)), &constraints).unwrap();
+        assert!(!conflict.contains(&(1, 2, CellState::Filled)));
+        assert!(conflict.contains(&(0, 2, CellState::Filled)));
+    }
+
+    #[test]
+    fn test_pool_shares_nogoods_between_analyzers() {
+        let pool = NoGoodPool::new();
+        let mut first = ConflictAnalyzer::new(10);
+        let mut second = ConflictAnalyzer::new(10);
+        first.set_pool(pool.clone());
+        second.set_pool(pool.clone());
+
+        first.learn(NoGood::new(vec![(0, 0, CellState::Filled)]));
+        assert_eq!(pool.len(), 1);
+
+        second.import_shared();
+        let mut grid = Grid::new(2, 2);
+        grid.set(0, 0, CellState::Filled).unwrap();
+        assert!(second.check(&grid).is_some());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::grid::{Grid, CellState, Constraints};
+use crate::grid::{Grid, Constraints};
 use crate::solver::{Deduction, line_solver_optimized::OptimizedLineSolver};
 use rayon::prelude::*;
 use std::sync::{Arc, Mutex};
@@ -79,34 +79,14 @@ impl ParallelSolver {
             let constraint = constraints.get_row_constraint(row)
                 .ok_or_else(|| format!("Contrainte de ligne {} non trouvée", row))?;
 
+            // Une ligne sans configuration valide ne donne aucune déduction
             let mut solver = OptimizedLineSolver::new();
-            let valid_configs = solver.generate_valid_configurations(&line, constraint);
-
-            if valid_configs.is_empty() {
+            let Ok(found) = solver.solve_line(&line, constraint) else {
                 return Ok(());
-            }
-
-            for col in 0..line.len() {
-                if line[col] != CellState::Empty {
-                    continue;
-                }
+            };
 
-                let all_filled = valid_configs.iter().all(|config| config[col] == CellState::Filled);
-                let all_crossed = valid_configs.iter().all(|config| config[col] == CellState::Crossed);
-
-                if all_filled {
-                    deductions.lock().unwrap().push(Deduction {
-                        row,
-                        col,
-                        state: CellState::Filled,
-                    });
-                } else if all_crossed {
-                    deductions.lock().unwrap().push(Deduction {
-                        row,
-                        col,
-                        state: CellState::Crossed,
-                    });
-                }
+            for (col, state) in found {
+                deductions.lock().unwrap().push(Deduction { row, col, state });
             }
 
             Ok::<(), String>(())
@@ -131,34 +111,14 @@ impl ParallelSolver {
             let constraint = constraints.get_column_constraint(col)
                 .ok_or_else(|| format!("Contrainte de colonne {} non trouvée", col))?;
 
+            // Une ligne sans configuration valide ne donne aucune déduction
             let mut solver = OptimizedLineSolver::new();
-            let valid_configs = solver.generate_valid_configurations(&column, constraint);
-
-            if valid_configs.is_empty() {
+            let Ok(found) = solver.solve_line(&column, constraint) else {
                 return Ok(());
-            }
+            };
 
-            for row in 0..column.len() {
-                if column[row] != CellState::Empty {
-                    continue;
-                }
-
-                let all_filled = valid_configs.iter().all(|config| config[row] == CellState::Filled);
-                let all_crossed = valid_configs.iter().all(|config| config[row] == CellState::Crossed);
-
-                if all_filled {
-                    deductions.lock().unwrap().push(Deduction {
-                        row,
-                        col,
-                        state: CellState::Filled,
-                    });
-                } else if all_crossed {
-                    deductions.lock().unwrap().push(Deduction {
-                        row,
-                        col,
-                        state: CellState::Crossed,
-                    });
-                }
+            for (row, state) in found {
+                deductions.lock().unwrap().push(Deduction { row, col, state });
             }
 
             Ok::<(), String>(())
@@ -180,6 +140,12 @@ impl ParallelSolver {
     }
 }
 
+impl Default for ParallelSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,19 +153,9 @@ mod tests {
     #[test]
     fn test_parallel_solver() {
         let mut grid = Grid::new(5, 5);
-        let mut constraints = Constraints::new(5, 5);
-        
-        constraints.set_row_constraint(0, vec![2]);
-        constraints.set_row_constraint(1, vec![1, 1]);
-        constraints.set_row_constraint(2, vec![5]);
-        constraints.set_row_constraint(3, vec![1, 1]);
-        constraints.set_row_constraint(4, vec![2]);
-        
-        constraints.set_column_constraint(0, vec![2]);
-        constraints.set_column_constraint(1, vec![1, 1]);
-        constraints.set_column_constraint(2, vec![5]);
-        constraints.set_column_constraint(3, vec![1, 1]);
-        constraints.set_column_constraint(4, vec![2]);
+        let rows = vec![vec![2], vec![1, 1], vec![5], vec![1, 1], vec![2]];
+        let columns = vec![vec![2], vec![1, 1], vec![5], vec![1, 1], vec![2]];
+        let constraints = Constraints::new(5, 5, rows, columns).unwrap();
         
         let solver = ParallelSolver::new();
         let deductions = solver.solve(&mut grid, &constraints).unwrap();
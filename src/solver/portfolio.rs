@@ -0,0 +1,254 @@
+use crate::grid::{Grid, CellState, Constraints};
+use crate::solver::{
+    Deduction,
+    BacktrackingSolver, BacktrackingConfig,
+    OptimizedBacktrackingSolver, OptimizedBacktrackingConfig,
+    BranchingStrategy,
+};
+use super::nogood::NoGoodPool;
+#[cfg(feature = "sat")]
+use crate::solver::{SatSolver, SatSolverConfig};
+use rayon::prelude::*;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Recherche lancée en parallèle par le portfolio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortfolioStrategy {
+    /// Backtracking simple avec une stratégie de branchement et une graine éventuelle
+    Backtracking(BranchingStrategy, Option<u64>),
+    /// Backtracking optimisé avec une stratégie de branchement et une graine éventuelle
+    OptimizedBacktracking(BranchingStrategy, Option<u64>),
+    /// Solveur SAT intégré
+    #[cfg(feature = "sat")]
+    Sat,
+}
+
+impl PortfolioStrategy {
+    pub fn name(&self) -> String {
+        let seeded = |base: String, seed: &Option<u64>| match seed {
+            Some(seed) => format!("{} (graine {})", base, seed),
+            None => base,
+        };
+
+        match self {
+            PortfolioStrategy::Backtracking(strategy, seed) =>
+                seeded(format!("backtracking/{}", strategy.name()), seed),
+            PortfolioStrategy::OptimizedBacktracking(strategy, seed) =>
+                seeded(format!("optimisé/{}", strategy.name()), seed),
+            #[cfg(feature = "sat")]
+            PortfolioStrategy::Sat => "sat".to_string(),
+        }
+    }
+}
+
+/// Configuration du solveur portfolio
+#[derive(Debug, Clone)]
+pub struct PortfolioConfig {
+    /// Recherches lancées en parallèle; la première qui trouve une solution arrête les autres
+    pub strategies: Vec<PortfolioStrategy>,
+    /// Partager les no-goods courts entre les recherches par backtracking
+    pub share_nogoods: bool,
+    /// Limite d'états explorés par recherche
+    pub max_states: usize,
+    pub verbose: bool,
+}
+
+impl Default for PortfolioConfig {
+    fn default() -> Self {
+        let mut strategies: Vec<PortfolioStrategy> = BranchingStrategy::ALL.iter()
+            .map(|&strategy| PortfolioStrategy::OptimizedBacktracking(strategy, None))
+            .collect();
+        strategies.push(PortfolioStrategy::Backtracking(BranchingStrategy::Probability, Some(1)));
+        strategies.push(PortfolioStrategy::Backtracking(BranchingStrategy::FewestPlacements, Some(2)));
+        #[cfg(feature = "sat")]
+        strategies.push(PortfolioStrategy::Sat);
+
+        Self {
+            strategies,
+            share_nogoods: true,
+            max_states: 100000,
+            verbose: false,
+        }
+    }
+}
+
+/// Solveur lançant plusieurs recherches en parallèle (stratégies et graines différentes)
+///
+/// Toutes les recherches partent de la même grille: un no-good appris par l'une
+/// reste valide pour les autres, d'où le partage via un `NoGoodPool`.
+pub struct PortfolioSolver {
+    config: PortfolioConfig,
+    winner: Option<PortfolioStrategy>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl PortfolioSolver {
+    pub fn new() -> Self {
+        Self::with_config(PortfolioConfig::default())
+    }
+
+    pub fn with_config(config: PortfolioConfig) -> Self {
+        Self { config, winner: None, cancel: None }
+    }
+
+    /// Drapeau d'annulation partagé par toutes les recherches; il est aussi levé
+    /// par le portfolio dès qu'une recherche aboutit
+    pub fn set_cancel_flag(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
+    }
+
+    /// Recherche ayant trouvé la solution lors du dernier appel à `solve`
+    pub fn winner(&self) -> Option<PortfolioStrategy> {
+        self.winner
+    }
+
+    /// Résout la grille avec la première recherche qui aboutit
+    /// Retourne les cases vides de la grille qui ont été déterminées
+    pub fn solve(&mut self, grid: &mut Grid, constraints: &Constraints) -> Result<Vec<Deduction>, String> {
+        self.winner = None;
+
+        if self.config.strategies.is_empty() {
+            return Err("Aucune stratégie configurée pour le portfolio".to_string());
+        }
+
+        if self.config.verbose {
+            println!("🏁 Démarrage du portfolio ({} recherches en parallèle)", self.config.strategies.len());
+        }
+
+        let cancel = self.cancel.clone().unwrap_or_else(|| Arc::new(AtomicBool::new(false)));
+        let pool = NoGoodPool::new();
+        let result: Mutex<Option<(PortfolioStrategy, Grid)>> = Mutex::new(None);
+
+        let errors: Vec<String> = self.config.strategies.par_iter()
+            .filter_map(|&strategy| {
+                let mut candidate = grid.clone();
+                let outcome = self.run_strategy(strategy, &mut candidate, constraints, &cancel, &pool);
+
+                match outcome {
+                    Ok(()) if candidate.count_empty_cells() == 0 && constraints.is_satisfied_by(&candidate) => {
+                        let mut result = result.lock().unwrap();
+                        if result.is_none() {
+                            *result = Some((strategy, candidate));
+                            cancel.store(true, Ordering::Relaxed);
+                        }
+                        None
+                    }
+                    Ok(()) => Some(format!("{}: grille incomplète", strategy.name())),
+                    Err(e) => Some(format!("{}: {}", strategy.name(), e)),
+                }
+            })
+            .collect();
+
+        let (strategy, solution) = result.into_inner().unwrap()
+            .ok_or_else(|| format!("Aucune recherche n'a abouti ({})", errors.join("; ")))?;
+
+        if self.config.verbose {
+            println!("   ✓ Solution trouvée par {}", strategy.name());
+            if self.config.share_nogoods {
+                println!("   - No-goods partagés: {}", pool.len());
+            }
+        }
+
+        let mut deductions = Vec::new();
+        for row in 0..grid.height() {
+            for col in 0..grid.width() {
+                if grid.get(row, col) == Some(CellState::Empty) {
+                    let state = solution.get(row, col).unwrap_or(CellState::Crossed);
+                    grid.set(row, col, state)?;
+                    deductions.push(Deduction { row, col, state });
+                }
+            }
+        }
+
+        self.winner = Some(strategy);
+        Ok(deductions)
+    }
+
+    fn run_strategy(
+        &self,
+        strategy: PortfolioStrategy,
+        grid: &mut Grid,
+        constraints: &Constraints,
+        cancel: &Arc<AtomicBool>,
+        pool: &NoGoodPool,
+    ) -> Result<(), String> {
+        let max_depth = constraints.width * constraints.height;
+
+        match strategy {
+            PortfolioStrategy::Backtracking(branching, seed) => {
+                let config = BacktrackingConfig {
+                    max_depth,
+                    max_states: self.config.max_states,
+                    branching,
+                    seed,
+                    ..Default::default()
+                };
+                let mut solver = BacktrackingSolver::with_config(config);
+                solver.set_cancel_flag(Arc::clone(cancel));
+                if self.config.share_nogoods {
+                    solver.set_nogood_pool(pool.clone());
+                }
+                solver.solve(grid, constraints).map(|_| ())
+            }
+            PortfolioStrategy::OptimizedBacktracking(branching, seed) => {
+                let config = OptimizedBacktrackingConfig {
+                    max_depth,
+                    max_states: self.config.max_states,
+                    branching,
+                    seed,
+                    ..Default::default()
+                };
+                let mut solver = OptimizedBacktrackingSolver::with_config(config);
+                solver.set_cancel_flag(Arc::clone(cancel));
+                if self.config.share_nogoods {
+                    solver.set_nogood_pool(pool.clone());
+                }
+                solver.solve(grid, constraints).map(|_| ())
+            }
+            #[cfg(feature = "sat")]
+            PortfolioStrategy::Sat => {
+                let mut solver = SatSolver::with_config(SatSolverConfig::default());
+                solver.set_cancel_flag(Arc::clone(cancel));
+                solver.solve(grid, constraints).map(|_| ())
+            }
+        }
+    }
+}
+
+impl Default for PortfolioSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_portfolio_finds_a_solution() {
+        let mut solution = Grid::new(5, 5);
+        for (row, col) in [(0, 0), (0, 2), (1, 1), (1, 2), (1, 3), (2, 4), (3, 0), (3, 1), (4, 3)] {
+            solution.set(row, col, CellState::Filled).unwrap();
+        }
+        let constraints = Constraints::from_grid(&solution);
+        let mut grid = Grid::new(5, 5);
+
+        let mut solver = PortfolioSolver::new();
+        solver.solve(&mut grid, &constraints).unwrap();
+
+        assert!(constraints.is_satisfied_by(&grid));
+        assert!(solver.winner().is_some());
+    }
+
+    #[test]
+    fn test_portfolio_reports_unsatisfiable_constraints() {
+        let constraints = Constraints::new(2, 2, vec![vec![2], vec![]], vec![vec![], vec![]]).unwrap();
+        let mut grid = Grid::new(2, 2);
+
+        let mut solver = PortfolioSolver::new();
+        assert!(solver.solve(&mut grid, &constraints).is_err());
+        assert!(solver.winner().is_none());
+    }
+}
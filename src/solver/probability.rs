@@ -0,0 +1,115 @@
+use crate::grid::{Grid, CellState, Constraints};
+use super::line_solver_optimized::OptimizedLineSolver;
+
+/// Carte de chaleur: probabilité que chaque case soit noire
+///
+/// Chaque ligne et chaque colonne donne une probabilité par case à partir du
+/// comptage de ses placements; les deux estimations sont combinées en les
+/// supposant indépendantes. Ce n'est qu'une indication ("indice doux"), pas une déduction.
+#[derive(Debug, Clone)]
+pub struct HeatMap {
+    width: usize,
+    height: usize,
+    row_probabilities: Vec<Vec<f64>>,
+    column_probabilities: Vec<Vec<f64>>,
+}
+
+impl HeatMap {
+    /// Calcule la carte de chaleur de la grille (erreur si une ligne n'a aucun placement)
+    pub fn compute(grid: &Grid, constraints: &Constraints) -> Result<Self, String> {
+        let line_solver = OptimizedLineSolver::new();
+        let width = grid.width();
+        let height = grid.height();
+
+        let mut row_probabilities = Vec::with_capacity(height);
+        for row in 0..height {
+            let line = grid.get_row(row)
+                .ok_or_else(|| format!("Ligne {} non trouvée", row))?;
+            let constraint = constraints.get_row_constraint(row)
+                .ok_or_else(|| format!("Contrainte de ligne {} non trouvée", row))?;
+            row_probabilities.push(line_solver.fill_probabilities(&line, constraint)
+                .map_err(|e| format!("Ligne {}: {}", row, e))?);
+        }
+
+        let mut column_probabilities = Vec::with_capacity(width);
+        for col in 0..width {
+            let column = grid.get_column(col)
+                .ok_or_else(|| format!("Colonne {} non trouvée", col))?;
+            let constraint = constraints.get_column_constraint(col)
+                .ok_or_else(|| format!("Contrainte de colonne {} non trouvée", col))?;
+            column_probabilities.push(line_solver.fill_probabilities(&column, constraint)
+                .map_err(|e| format!("Colonne {}: {}", col, e))?);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            row_probabilities,
+            column_probabilities,
+        })
+    }
+
+    /// Probabilité selon la ligne seule
+    pub fn row_probability(&self, row: usize, col: usize) -> Option<f64> {
+        self.row_probabilities.get(row)?.get(col).copied()
+    }
+
+    /// Probabilité selon la colonne seule
+    pub fn column_probability(&self, row: usize, col: usize) -> Option<f64> {
+        self.column_probabilities.get(col)?.get(row).copied()
+    }
+
+    /// Probabilité combinée que la case soit noire
+    pub fn get(&self, row: usize, col: usize) -> Option<f64> {
+        let p_row = self.row_probability(row, col)?;
+        let p_col = self.column_probability(row, col)?;
+        let filled = p_row * p_col;
+        let crossed = (1.0 - p_row) * (1.0 - p_col);
+
+        if filled + crossed > 0.0 {
+            Some(filled / (filled + crossed))
+        } else {
+            // Ligne et colonne en désaccord total: aucune information exploitable
+            Some(0.5)
+        }
+    }
+
+    /// Case vide dont la probabilité est la plus tranchée, avec sa probabilité
+    pub fn most_certain_cell(&self, grid: &Grid) -> Option<(usize, usize, f64)> {
+        let mut best: Option<(usize, usize, f64)> = None;
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if grid.get(row, col) != Some(CellState::Empty) {
+                    continue;
+                }
+                let probability = self.get(row, col)?;
+                let certainty = (probability - 0.5).abs();
+                if best.is_none_or(|(_, _, p)| certainty > (p - 0.5).abs()) {
+                    best = Some((row, col, probability));
+                }
+            }
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heat_map_combines_rows_and_columns() {
+        // Diagonales ambiguës: toutes les cases à 50%
+        let constraints = Constraints::new(2, 2, vec![vec![1], vec![1]], vec![vec![1], vec![1]]).unwrap();
+        let mut grid = Grid::new(2, 2);
+        let heat_map = HeatMap::compute(&grid, &constraints).unwrap();
+        assert!((heat_map.get(0, 0).unwrap() - 0.5).abs() < 1e-9);
+
+        grid.set(0, 0, CellState::Filled).unwrap();
+        let heat_map = HeatMap::compute(&grid, &constraints).unwrap();
+        assert_eq!(heat_map.get(0, 0), Some(1.0));
+        assert_eq!(heat_map.get(0, 1), Some(0.0));
+    }
+}
@@ -0,0 +1,40 @@
+use std::time::Duration;
+use serde::Serialize;
+use crate::grid::{Constraints, Grid};
+use super::Deduction;
+
+/// Rapport de résolution au format JSON (`--json` et serveur `serve`)
+#[derive(Debug, Clone, Serialize)]
+pub struct SolveReport {
+    pub width: usize,
+    pub height: usize,
+    /// Solveur utilisé (`basic`, `advanced`, `ultimate`, `portfolio`, `sat`)
+    pub solver: String,
+    /// Indices utilisés (lus sur l'image en mode automatique)
+    pub constraints: Constraints,
+    /// Toutes les cases sont déterminées et respectent les indices
+    pub solved: bool,
+    /// Cases encore indéterminées
+    pub remaining_cells: usize,
+    pub deductions: Vec<Deduction>,
+    /// Grille obtenue, une rangée par chaîne (`#` noire, `x` barrée, `.` vide)
+    pub grid: Vec<String>,
+    pub duration_ms: u64,
+}
+
+impl SolveReport {
+    pub fn new(solver: &str, grid: &Grid, constraints: &Constraints, deductions: Vec<Deduction>, duration: Duration) -> Self {
+        let remaining_cells = grid.count_empty_cells();
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            solver: solver.to_string(),
+            constraints: constraints.clone(),
+            solved: remaining_cells == 0 && constraints.is_satisfied_by(grid),
+            remaining_cells,
+            deductions,
+            grid: grid.to_text().lines().map(str::to_string).collect(),
+            duration_ms: duration.as_millis() as u64,
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use crate::grid::{Grid, CellState, Constraints};
+
+/// Formule en forme normale conjonctive (CNF)
+/// Les littéraux suivent la convention DIMACS: variable `v` positive = `v`, négation = `-v`
+#[derive(Debug, Clone, Default)]
+pub struct Cnf {
+    pub num_vars: usize,
+    pub clauses: Vec<Vec<i32>>,
+}
+
+impl Cnf {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Alloue une nouvelle variable et retourne son numéro (à partir de 1)
+    pub fn new_var(&mut self) -> i32 {
+        self.num_vars += 1;
+        self.num_vars as i32
+    }
+
+    /// Ajoute une clause
+    pub fn add_clause(&mut self, clause: Vec<i32>) {
+        self.clauses.push(clause);
+    }
+
+    /// Exporte la formule au format DIMACS
+    pub fn to_dimacs(&self) -> String {
+        let mut output = String::new();
+        output.push_str(&format!("p cnf {} {}\n", self.num_vars, self.clauses.len()));
+        for clause in &self.clauses {
+            for lit in clause {
+                output.push_str(&lit.to_string());
+                output.push(' ');
+            }
+            output.push_str("0\n");
+        }
+        output
+    }
+}
+
+/// Encodage d'un nonogramme en CNF par variables de début de bloc
+///
+/// Chaque case possède une variable (vraie = noire). Pour chaque bloc de chaque
+/// ligne/colonne, une variable par position de départ possible: exactement une
+/// est vraie, les blocs restent ordonnés et séparés, et une case est noire si et
+/// seulement si un bloc de sa ligne (et de sa colonne) la couvre.
+#[derive(Debug, Clone)]
+pub struct SatEncoding {
+    pub cnf: Cnf,
+    width: usize,
+    height: usize,
+}
+
+impl SatEncoding {
+    /// Encode les contraintes (et les cases déjà connues de la grille, si fournie)
+    pub fn encode(constraints: &Constraints, grid: Option<&Grid>) -> Result<Self, String> {
+        let width = constraints.width;
+        let height = constraints.height;
+        let mut cnf = Cnf::new();
+
+        // Variables des cases: 1..=width*height
+        for _ in 0..width * height {
+            cnf.new_var();
+        }
+
+        for row in 0..height {
+            let constraint = constraints.get_row_constraint(row)
+                .ok_or_else(|| format!("Contrainte de ligne {} non trouvée", row))?;
+            let cells: Vec<i32> = (0..width).map(|col| Self::cell_var_for(width, row, col)).collect();
+            Self::encode_line(&mut cnf, &cells, constraint)?;
+        }
+
+        for col in 0..width {
+            let constraint = constraints.get_column_constraint(col)
+                .ok_or_else(|| format!("Contrainte de colonne {} non trouvée", col))?;
+            let cells: Vec<i32> = (0..height).map(|row| Self::cell_var_for(width, row, col)).collect();
+            Self::encode_line(&mut cnf, &cells, constraint)?;
+        }
+
+        if let Some(grid) = grid {
+            if grid.width() != width || grid.height() != height {
+                return Err(format!(
+                    "Dimensions de la grille ({}x{}) différentes des contraintes ({}x{})",
+                    grid.width(), grid.height(), width, height
+                ));
+            }
+            for row in 0..height {
+                for col in 0..width {
+                    let var = Self::cell_var_for(width, row, col);
+                    match grid.get(row, col) {
+                        Some(CellState::Filled) => cnf.add_clause(vec![var]),
+                        Some(CellState::Crossed) => cnf.add_clause(vec![-var]),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        Ok(Self { cnf, width, height })
+    }
+
+    /// Variable associée à une case
+    pub fn cell_var(&self, row: usize, col: usize) -> i32 {
+        Self::cell_var_for(self.width, row, col)
+    }
+
+    fn cell_var_for(width: usize, row: usize, col: usize) -> i32 {
+        (row * width + col + 1) as i32
+    }
+
+    /// Encode une ligne (ou colonne) à partir des variables de ses cases
+    fn encode_line(cnf: &mut Cnf, cells: &[i32], constraint: &[usize]) -> Result<(), String> {
+        let length = cells.len();
+
+        if constraint.is_empty() {
+            for &cell in cells {
+                cnf.add_clause(vec![-cell]);
+            }
+            return Ok(());
+        }
+
+        let min_length: usize = constraint.iter().sum::<usize>() + constraint.len() - 1;
+        if min_length > length {
+            return Err(format!(
+                "La contrainte {:?} nécessite au moins {} cases, la ligne n'en a que {}",
+                constraint, min_length, length
+            ));
+        }
+
+        // starts[k] = liste des (position, variable) de début possibles du bloc k
+        let mut starts: Vec<Vec<(usize, i32)>> = Vec::with_capacity(constraint.len());
+        let mut min_start = 0;
+        for (k, &block_size) in constraint.iter().enumerate() {
+            let after: usize = constraint[k + 1..].iter().map(|&b| b + 1).sum();
+            let max_start = length - after - block_size;
+            let block_starts: Vec<(usize, i32)> = (min_start..=max_start)
+                .map(|pos| (pos, cnf.new_var()))
+                .collect();
+            starts.push(block_starts);
+            min_start += block_size + 1;
+        }
+
+        // Exactement une position de départ par bloc
+        for block_starts in &starts {
+            cnf.add_clause(block_starts.iter().map(|&(_, var)| var).collect());
+            for i in 0..block_starts.len() {
+                for j in (i + 1)..block_starts.len() {
+                    cnf.add_clause(vec![-block_starts[i].1, -block_starts[j].1]);
+                }
+            }
+        }
+
+        // Ordre des blocs: le bloc k+1 commence au moins une case après la fin du bloc k
+        for k in 0..constraint.len().saturating_sub(1) {
+            for &(pos, var) in &starts[k] {
+                let earliest_next = pos + constraint[k] + 1;
+                for &(next_pos, next_var) in &starts[k + 1] {
+                    if next_pos < earliest_next {
+                        cnf.add_clause(vec![-var, -next_var]);
+                    }
+                }
+            }
+        }
+
+        // Un bloc placé noircit les cases qu'il couvre
+        let mut covering: Vec<Vec<i32>> = vec![Vec::new(); length];
+        for (k, block_starts) in starts.iter().enumerate() {
+            for &(pos, var) in block_starts {
+                for (offset, covered) in covering[pos..pos + constraint[k]].iter_mut().enumerate() {
+                    cnf.add_clause(vec![-var, cells[pos + offset]]);
+                    covered.push(var);
+                }
+            }
+        }
+
+        // Une case noire est couverte par au moins un bloc
+        for (pos, covered) in covering.into_iter().enumerate() {
+            let mut clause = vec![-cells[pos]];
+            clause.extend(covered);
+            cnf.add_clause(clause);
+        }
+
+        Ok(())
+    }
+
+    /// Reconstruit une grille à partir d'un modèle (indexé par variable - 1)
+    pub fn decode(&self, model: &[bool]) -> Result<Grid, String> {
+        if model.len() < self.width * self.height {
+            return Err("Modèle SAT incomplet".to_string());
+        }
+
+        let mut grid = Grid::new(self.width, self.height);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let var = self.cell_var(row, col) as usize;
+                let state = if model[var - 1] { CellState::Filled } else { CellState::Crossed };
+                grid.set(row, col, state)?;
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Clause interdisant exactement la solution donnée (utile pour tester l'unicité)
+    pub fn blocking_clause(&self, grid: &Grid) -> Vec<i32> {
+        let mut clause = Vec::with_capacity(self.width * self.height);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let var = self.cell_var(row, col);
+                match grid.get(row, col) {
+                    Some(CellState::Filled) => clause.push(-var),
+                    _ => clause.push(var),
+                }
+            }
+        }
+        clause
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dimacs_header() {
+        let constraints = Constraints::new(2, 1, vec![vec![1]], vec![vec![1], vec![]]).unwrap();
+        let encoding = SatEncoding::encode(&constraints, None).unwrap();
+        let dimacs = encoding.cnf.to_dimacs();
+
+        let header = format!("p cnf {} {}", encoding.cnf.num_vars, encoding.cnf.clauses.len());
+        assert!(dimacs.starts_with(&header));
+        assert_eq!(dimacs.lines().count(), encoding.cnf.clauses.len() + 1);
+    }
+
+    #[test]
+    fn test_empty_line_forces_crossed() {
+        let constraints = Constraints::new(2, 1, vec![vec![]], vec![vec![], vec![]]).unwrap();
+        let encoding = SatEncoding::encode(&constraints, None).unwrap();
+        assert!(encoding.cnf.clauses.contains(&vec![-1]));
+        assert!(encoding.cnf.clauses.contains(&vec![-2]));
+    }
+}
@@ -0,0 +1,606 @@
+use crate::grid::{Grid, CellState, Constraints};
+use crate::solver::Deduction;
+use super::sat_encoding::{Cnf, SatEncoding};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Résultat d'une résolution SAT
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SatResult {
+    /// Modèle trouvé (valeur de chaque variable, indexée par variable - 1)
+    Satisfiable(Vec<bool>),
+    Unsatisfiable,
+    /// Limite de conflits atteinte avant de conclure
+    Unknown,
+}
+
+/// Solveur SAT CDCL minimal (watched literals, apprentissage 1UIP, VSIDS, redémarrages Luby)
+pub struct CdclSolver {
+    num_vars: usize,
+    clauses: Vec<Vec<u32>>,
+    watches: Vec<Vec<usize>>,
+    units: Vec<u32>,
+    trivially_unsat: bool,
+    assigns: Vec<i8>,
+    levels: Vec<usize>,
+    reasons: Vec<Option<usize>>,
+    trail: Vec<u32>,
+    trail_lim: Vec<usize>,
+    qhead: usize,
+    activity: Vec<f64>,
+    var_inc: f64,
+    phase: Vec<bool>,
+    seen: Vec<bool>,
+    conflicts: usize,
+    /// Drapeau d'annulation partagé (mode portfolio)
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+const RESTART_UNIT: usize = 100;
+const VAR_DECAY: f64 = 0.95;
+
+/// Valeur d'un littéral interne: 1 vrai, -1 faux, 0 non assigné
+fn lit_value(assigns: &[i8], lit: u32) -> i8 {
+    let value = assigns[(lit >> 1) as usize];
+    if lit & 1 == 1 { -value } else { value }
+}
+
+/// Suite de Luby (1, 1, 2, 1, 1, 2, 4, ...)
+fn luby(mut index: usize) -> usize {
+    let mut size = 1;
+    let mut seq = 0;
+    while size < index + 1 {
+        seq += 1;
+        size = 2 * size + 1;
+    }
+    while size - 1 != index {
+        size = (size - 1) >> 1;
+        seq -= 1;
+        index %= size;
+    }
+    1 << seq
+}
+
+impl CdclSolver {
+    /// Construit un solveur à partir d'une formule CNF
+    pub fn from_cnf(cnf: &Cnf) -> Self {
+        let num_vars = cnf.num_vars;
+        let mut solver = Self {
+            num_vars,
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); 2 * num_vars],
+            units: Vec::new(),
+            trivially_unsat: false,
+            assigns: vec![0; num_vars],
+            levels: vec![0; num_vars],
+            reasons: vec![None; num_vars],
+            trail: Vec::new(),
+            trail_lim: Vec::new(),
+            qhead: 0,
+            activity: vec![0.0; num_vars],
+            var_inc: 1.0,
+            phase: vec![false; num_vars],
+            seen: vec![false; num_vars],
+            conflicts: 0,
+            cancel: None,
+        };
+
+        for clause in &cnf.clauses {
+            solver.add_clause(clause);
+        }
+
+        solver
+    }
+
+    /// Ajoute une clause au format DIMACS
+    pub fn add_clause(&mut self, clause: &[i32]) {
+        let mut lits: Vec<u32> = Vec::with_capacity(clause.len());
+        for &lit in clause {
+            let var = lit.unsigned_abs() - 1;
+            let internal = 2 * var + u32::from(lit < 0);
+            if lits.contains(&(internal ^ 1)) {
+                // Tautologie: la clause est toujours vraie
+                return;
+            }
+            if !lits.contains(&internal) {
+                lits.push(internal);
+            }
+        }
+
+        match lits.len() {
+            0 => self.trivially_unsat = true,
+            1 => self.units.push(lits[0]),
+            _ => {
+                let index = self.clauses.len();
+                self.watches[lits[0] as usize].push(index);
+                self.watches[lits[1] as usize].push(index);
+                self.clauses.push(lits);
+            }
+        }
+    }
+
+    /// Nombre de conflits rencontrés lors de la dernière résolution
+    pub fn conflicts(&self) -> usize {
+        self.conflicts
+    }
+
+    /// Interrompt la résolution (résultat `Unknown`) dès que le drapeau passe à vrai
+    pub fn set_cancel_flag(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+
+    /// Résout la formule; `max_conflicts` limite l'effort (0 = illimité)
+    pub fn solve(&mut self, max_conflicts: usize) -> SatResult {
+        if self.trivially_unsat {
+            return SatResult::Unsatisfiable;
+        }
+
+        self.cancel_until(0);
+        for unit in self.units.clone() {
+            match lit_value(&self.assigns, unit) {
+                -1 => return SatResult::Unsatisfiable,
+                0 => self.enqueue(unit, None),
+                _ => {}
+            }
+        }
+
+        if self.propagate().is_some() {
+            return SatResult::Unsatisfiable;
+        }
+
+        let mut restart_index = 0;
+        let mut conflicts_since_restart = 0;
+
+        loop {
+            if self.is_cancelled() {
+                return SatResult::Unknown;
+            }
+
+            if let Some(conflict) = self.propagate() {
+                self.conflicts += 1;
+                conflicts_since_restart += 1;
+
+                if self.trail_lim.is_empty() {
+                    return SatResult::Unsatisfiable;
+                }
+
+                let (learnt, backjump_level) = self.analyze(conflict);
+                self.cancel_until(backjump_level);
+
+                if learnt.len() == 1 {
+                    self.enqueue(learnt[0], None);
+                } else {
+                    let index = self.clauses.len();
+                    self.watches[learnt[0] as usize].push(index);
+                    self.watches[learnt[1] as usize].push(index);
+                    let asserting = learnt[0];
+                    self.clauses.push(learnt);
+                    self.enqueue(asserting, Some(index));
+                }
+
+                self.var_inc /= VAR_DECAY;
+
+                if max_conflicts > 0 && self.conflicts >= max_conflicts {
+                    return SatResult::Unknown;
+                }
+
+                if conflicts_since_restart >= RESTART_UNIT * luby(restart_index) {
+                    restart_index += 1;
+                    conflicts_since_restart = 0;
+                    self.cancel_until(0);
+                }
+            } else {
+                match self.pick_branch_var() {
+                    Some(var) => {
+                        self.trail_lim.push(self.trail.len());
+                        let lit = 2 * var as u32 + u32::from(!self.phase[var]);
+                        self.enqueue(lit, None);
+                    }
+                    None => {
+                        let model = self.assigns.iter().map(|&value| value == 1).collect();
+                        return SatResult::Satisfiable(model);
+                    }
+                }
+            }
+        }
+    }
+
+    fn decision_level(&self) -> usize {
+        self.trail_lim.len()
+    }
+
+    fn enqueue(&mut self, lit: u32, reason: Option<usize>) {
+        let var = (lit >> 1) as usize;
+        self.assigns[var] = if lit & 1 == 1 { -1 } else { 1 };
+        self.levels[var] = self.decision_level();
+        self.reasons[var] = reason;
+        self.trail.push(lit);
+    }
+
+    fn cancel_until(&mut self, level: usize) {
+        if self.decision_level() <= level {
+            return;
+        }
+
+        let limit = self.trail_lim[level];
+        for &lit in &self.trail[limit..] {
+            let var = (lit >> 1) as usize;
+            self.phase[var] = lit & 1 == 0;
+            self.assigns[var] = 0;
+            self.reasons[var] = None;
+        }
+        self.trail.truncate(limit);
+        self.trail_lim.truncate(level);
+        self.qhead = self.trail.len();
+    }
+
+    /// Propagation unitaire; retourne l'index de la clause en conflit le cas échéant
+    fn propagate(&mut self) -> Option<usize> {
+        while self.qhead < self.trail.len() {
+            let false_lit = self.trail[self.qhead] ^ 1;
+            self.qhead += 1;
+
+            let mut watchers = std::mem::take(&mut self.watches[false_lit as usize]);
+            let mut conflict = None;
+            let mut kept = 0;
+            let mut i = 0;
+
+            while i < watchers.len() {
+                let index = watchers[i];
+                i += 1;
+
+                let clause = &mut self.clauses[index];
+                if clause[0] == false_lit {
+                    clause.swap(0, 1);
+                }
+
+                if lit_value(&self.assigns, clause[0]) == 1 {
+                    watchers[kept] = index;
+                    kept += 1;
+                    continue;
+                }
+
+                // Chercher un nouveau littéral à surveiller
+                let mut moved = false;
+                for k in 2..clause.len() {
+                    if lit_value(&self.assigns, clause[k]) != -1 {
+                        clause.swap(1, k);
+                        self.watches[clause[1] as usize].push(index);
+                        moved = true;
+                        break;
+                    }
+                }
+                if moved {
+                    continue;
+                }
+
+                watchers[kept] = index;
+                kept += 1;
+
+                let first = clause[0];
+                if lit_value(&self.assigns, first) == -1 {
+                    conflict = Some(index);
+                    while i < watchers.len() {
+                        watchers[kept] = watchers[i];
+                        kept += 1;
+                        i += 1;
+                    }
+                } else {
+                    self.enqueue(first, Some(index));
+                }
+            }
+
+            watchers.truncate(kept);
+            self.watches[false_lit as usize] = watchers;
+
+            if conflict.is_some() {
+                self.qhead = self.trail.len();
+                return conflict;
+            }
+        }
+
+        None
+    }
+
+    /// Analyse de conflit (premier point d'implication unique)
+    fn analyze(&mut self, conflict: usize) -> (Vec<u32>, usize) {
+        let mut learnt = vec![0u32];
+        let mut path_count = 0;
+        let mut clause_index = conflict;
+        let mut pivot: Option<u32> = None;
+        let mut trail_index = self.trail.len();
+        let current_level = self.decision_level();
+
+        loop {
+            let skip = usize::from(pivot.is_some());
+            for position in skip..self.clauses[clause_index].len() {
+                let lit = self.clauses[clause_index][position];
+                let var = (lit >> 1) as usize;
+                if !self.seen[var] && self.levels[var] > 0 {
+                    self.seen[var] = true;
+                    self.bump(var);
+                    if self.levels[var] >= current_level {
+                        path_count += 1;
+                    } else {
+                        learnt.push(lit);
+                    }
+                }
+            }
+
+            loop {
+                trail_index -= 1;
+                if self.seen[(self.trail[trail_index] >> 1) as usize] {
+                    break;
+                }
+            }
+
+            let lit = self.trail[trail_index];
+            let var = (lit >> 1) as usize;
+            self.seen[var] = false;
+            path_count -= 1;
+            pivot = Some(lit);
+
+            if path_count == 0 {
+                break;
+            }
+            clause_index = self.reasons[var].expect("littéral propagé sans raison");
+        }
+
+        learnt[0] = pivot.unwrap_or_default() ^ 1;
+
+        for &lit in &learnt[1..] {
+            self.seen[(lit >> 1) as usize] = false;
+        }
+
+        // Placer le littéral de plus haut niveau en seconde position (surveillé)
+        let mut backjump_level = 0;
+        if learnt.len() > 1 {
+            let mut max_index = 1;
+            for position in 2..learnt.len() {
+                if self.levels[(learnt[position] >> 1) as usize] > self.levels[(learnt[max_index] >> 1) as usize] {
+                    max_index = position;
+                }
+            }
+            learnt.swap(1, max_index);
+            backjump_level = self.levels[(learnt[1] >> 1) as usize];
+        }
+
+        (learnt, backjump_level)
+    }
+
+    fn bump(&mut self, var: usize) {
+        self.activity[var] += self.var_inc;
+        if self.activity[var] > 1e100 {
+            for activity in &mut self.activity {
+                *activity *= 1e-100;
+            }
+            self.var_inc *= 1e-100;
+        }
+    }
+
+    fn pick_branch_var(&self) -> Option<usize> {
+        let mut best: Option<usize> = None;
+        for var in 0..self.num_vars {
+            if self.assigns[var] == 0 && best.is_none_or(|b| self.activity[var] > self.activity[b]) {
+                best = Some(var);
+            }
+        }
+        best
+    }
+}
+
+/// Configuration pour le solveur SAT
+#[derive(Debug, Clone)]
+pub struct SatSolverConfig {
+    /// Nombre maximal de conflits avant abandon (0 = illimité)
+    pub max_conflicts: usize,
+    pub verbose: bool,
+}
+
+impl Default for SatSolverConfig {
+    fn default() -> Self {
+        Self {
+            max_conflicts: 1_000_000,
+            verbose: false,
+        }
+    }
+}
+
+/// Solveur de nonogramme par encodage SAT
+pub struct SatSolver {
+    config: SatSolverConfig,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl SatSolver {
+    pub fn new() -> Self {
+        Self::with_config(SatSolverConfig::default())
+    }
+
+    pub fn with_config(config: SatSolverConfig) -> Self {
+        Self { config, cancel: None }
+    }
+
+    /// Permet d'annuler la résolution depuis un autre thread
+    pub fn set_cancel_flag(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
+    }
+
+    fn unknown_reason(&self) -> String {
+        if self.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            "Résolution SAT annulée".to_string()
+        } else {
+            "Limite de conflits SAT atteinte".to_string()
+        }
+    }
+
+    fn new_cdcl(&self, cnf: &Cnf) -> CdclSolver {
+        let mut solver = CdclSolver::from_cnf(cnf);
+        if let Some(cancel) = &self.cancel {
+            solver.set_cancel_flag(Arc::clone(cancel));
+        }
+        solver
+    }
+
+    /// Résout la grille complètement via SAT
+    /// Retourne les cases vides de la grille qui ont été déterminées
+    pub fn solve(&mut self, grid: &mut Grid, constraints: &Constraints) -> Result<Vec<Deduction>, String> {
+        if self.config.verbose {
+            println!("🧮 Démarrage du solveur SAT");
+        }
+
+        let solution = self.find_solution(grid, constraints)?
+            .ok_or_else(|| "Aucune solution: les contraintes sont insatisfiables".to_string())?;
+
+        let mut deductions = Vec::new();
+        for row in 0..grid.height() {
+            for col in 0..grid.width() {
+                if grid.get(row, col) == Some(CellState::Empty) {
+                    let state = solution.get(row, col).unwrap_or(CellState::Crossed);
+                    grid.set(row, col, state)?;
+                    deductions.push(Deduction { row, col, state });
+                }
+            }
+        }
+
+        if self.config.verbose {
+            println!("   ✓ {} déductions", deductions.len());
+        }
+
+        Ok(deductions)
+    }
+
+    /// Cherche une solution compatible avec les cases déjà connues de la grille
+    pub fn find_solution(&self, grid: &Grid, constraints: &Constraints) -> Result<Option<Grid>, String> {
+        let encoding = SatEncoding::encode(constraints, Some(grid))?;
+
+        if self.config.verbose {
+            println!("   - Variables: {}", encoding.cnf.num_vars);
+            println!("   - Clauses: {}", encoding.cnf.clauses.len());
+        }
+
+        let mut solver = self.new_cdcl(&encoding.cnf);
+        let result = solver.solve(self.config.max_conflicts);
+
+        if self.config.verbose {
+            println!("   - Conflits: {}", solver.conflicts());
+        }
+
+        match result {
+            SatResult::Satisfiable(model) => encoding.decode(&model).map(Some),
+            SatResult::Unsatisfiable => Ok(None),
+            SatResult::Unknown => Err(self.unknown_reason()),
+        }
+    }
+
+    /// Compte les solutions jusqu'à `limit` (2 suffit pour tester l'unicité)
+    pub fn count_solutions(&self, grid: &Grid, constraints: &Constraints, limit: usize) -> Result<usize, String> {
+        let encoding = SatEncoding::encode(constraints, Some(grid))?;
+        let mut cnf = encoding.cnf.clone();
+        let mut count = 0;
+
+        while count < limit {
+            let mut solver = self.new_cdcl(&cnf);
+            match solver.solve(self.config.max_conflicts) {
+                SatResult::Satisfiable(model) => {
+                    count += 1;
+                    let solution = encoding.decode(&model)?;
+                    cnf.add_clause(encoding.blocking_clause(&solution));
+                }
+                SatResult::Unsatisfiable => break,
+                SatResult::Unknown => return Err(self.unknown_reason()),
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Vérifie de façon indépendante la grille produite par un autre solveur:
+    /// les cases déterminées doivent être compatibles avec au moins une solution
+    pub fn cross_check(&self, grid: &Grid, constraints: &Constraints) -> Result<bool, String> {
+        Ok(self.find_solution(grid, constraints)?.is_some())
+    }
+}
+
+impl Default for SatSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cdcl_simple() {
+        let mut cnf = Cnf::new();
+        let a = cnf.new_var();
+        let b = cnf.new_var();
+        cnf.add_clause(vec![a, b]);
+        cnf.add_clause(vec![-a, b]);
+        cnf.add_clause(vec![a, -b]);
+
+        let mut solver = CdclSolver::from_cnf(&cnf);
+        assert_eq!(solver.solve(0), SatResult::Satisfiable(vec![true, true]));
+
+        cnf.add_clause(vec![-a, -b]);
+        let mut solver = CdclSolver::from_cnf(&cnf);
+        assert_eq!(solver.solve(0), SatResult::Unsatisfiable);
+    }
+
+    #[test]
+    fn test_sat_solve_grid() {
+        let rows = vec![vec![2], vec![1, 1], vec![5], vec![1, 1], vec![2]];
+        let columns = vec![vec![1], vec![4], vec![1, 1], vec![4], vec![1, 1]];
+        let constraints = Constraints::new(5, 5, rows, columns).unwrap();
+        let mut grid = Grid::new(5, 5);
+
+        let mut solver = SatSolver::new();
+        let deductions = solver.solve(&mut grid, &constraints).unwrap();
+
+        assert_eq!(deductions.len(), 25);
+        assert_eq!(grid.count_empty_cells(), 0);
+        for col in 0..5 {
+            assert_eq!(grid.get(2, col), Some(CellState::Filled));
+        }
+        assert!(solver.cross_check(&grid, &constraints).unwrap());
+    }
+
+    #[test]
+    fn test_count_solutions() {
+        // Deux solutions symétriques: diagonale ou anti-diagonale
+        let rows = vec![vec![1], vec![1]];
+        let columns = vec![vec![1], vec![1]];
+        let constraints = Constraints::new(2, 2, rows, columns).unwrap();
+
+        let solver = SatSolver::new();
+        assert_eq!(solver.count_solutions(&Grid::new(2, 2), &constraints, 5).unwrap(), 2);
+        assert_eq!(solver.count_solutions(&Grid::new(2, 2), &constraints, 1).unwrap(), 1);
+
+        // Une case connue départage les deux solutions
+        let mut grid = Grid::new(2, 2);
+        grid.set(0, 0, CellState::Filled).unwrap();
+        assert_eq!(solver.count_solutions(&grid, &constraints, 5).unwrap(), 1);
+        assert!(SatSolver::new().solve(&mut grid, &constraints).is_ok());
+        assert_eq!(grid.get(1, 1), Some(CellState::Filled));
+    }
+
+    #[test]
+    fn test_unsatisfiable_puzzle() {
+        // Sommes des lignes et des colonnes différentes: aucune grille ne convient
+        let constraints = Constraints::new(2, 2, vec![vec![2], vec![2]], vec![vec![1], vec![1]]).unwrap();
+        let mut grid = Grid::new(2, 2);
+
+        let mut solver = SatSolver::new();
+        assert_eq!(solver.count_solutions(&grid, &constraints, 2).unwrap(), 0);
+        assert!(solver.find_solution(&grid, &constraints).unwrap().is_none());
+        assert!(!solver.cross_check(&grid, &constraints).unwrap());
+        assert!(solver.solve(&mut grid, &constraints).is_err());
+        assert_eq!(grid.count_empty_cells(), 4);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::grid::{CellState, Constraints, Grid};
+use super::{Deduction, NonogramSolver};
+
+/// Technique à l'origine d'une étape de résolution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Technique {
+    /// Déductions sur une ligne
+    Row(usize),
+    /// Déductions sur une colonne
+    Column(usize),
+    /// Passe d'analyse croisée du solveur avancé
+    CrossAnalysis,
+    /// Passe d'heuristiques avancées du solveur avancé
+    Heuristics,
+    /// Logique ligne par ligne du solveur parallèle
+    Parallel,
+    /// Backtracking du solveur ultime
+    Backtracking,
+    /// Cases trouvées par un solveur qui ne détaille pas ses étapes (portfolio, SAT)
+    Search,
+}
+
+/// Lot de déductions obtenu en une étape, dans l'ordre de la résolution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveStep {
+    pub technique: Technique,
+    pub deductions: Vec<Deduction>,
+}
+
+impl SolveStep {
+    pub fn label(&self) -> String {
+        match self.technique {
+            Technique::Row(row) => format!("Ligne {}", row + 1),
+            Technique::Column(col) => format!("Colonne {}", col + 1),
+            Technique::CrossAnalysis => "Analyse croisée".to_string(),
+            Technique::Heuristics => "Heuristiques avancées".to_string(),
+            Technique::Parallel => "Solveur parallèle".to_string(),
+            Technique::Backtracking => "Backtracking".to_string(),
+            Technique::Search => "Techniques avancées".to_string(),
+        }
+    }
+
+    /// Étapes menant de `start` à `solved` pour les solveurs qui ne les fournissent pas
+    /// (portfolio, SAT): la logique ligne par ligne d'abord, puis en une dernière étape
+    /// les cases que seul le solveur choisi a trouvées
+    pub fn trace(start: &Grid, constraints: &Constraints, solved: &Grid) -> Result<Vec<SolveStep>, String> {
+        let mut grid = start.clone();
+        let mut steps = NonogramSolver::new().solve_steps(&mut grid, constraints)?;
+
+        let remaining: Vec<Deduction> = (0..solved.height())
+            .flat_map(|row| (0..solved.width()).map(move |col| (row, col)))
+            .filter_map(|(row, col)| {
+                let state = solved.get(row, col)?;
+                (state != CellState::Empty && grid.get(row, col) == Some(CellState::Empty))
+                    .then_some(Deduction { row, col, state })
+            })
+            .collect();
+        if !remaining.is_empty() {
+            steps.push(SolveStep { technique: Technique::Search, deductions: remaining });
+        }
+        Ok(steps)
+    }
+
+    /// Déductions des `count` premières étapes, pour afficher la résolution à ce stade
+    pub fn deductions_until(steps: &[SolveStep], count: usize) -> Vec<Deduction> {
+        steps.iter().take(count).flat_map(|step| step.deductions.iter().copied()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_follows_lines_then_search() {
+        // Deux solutions: la logique ne déduit rien, la dernière étape reprend la grille résolue
+        let constraints = Constraints::new(2, 2, vec![vec![1], vec![1]], vec![vec![1], vec![1]]).unwrap();
+        let solved = Grid::from_text("#x\nx#").unwrap();
+        let steps = SolveStep::trace(&Grid::new(2, 2), &constraints, &solved).unwrap();
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].label(), "Techniques avancées");
+        assert_eq!(steps[0].deductions.len(), 4);
+
+        // Une croix: chaque étape est une ligne ou une colonne
+        let constraints = Constraints::new(3, 3, vec![vec![1], vec![3], vec![1]], vec![vec![1], vec![3], vec![1]]).unwrap();
+        let mut grid = Grid::new(3, 3);
+        let deductions = NonogramSolver::new().solve(&mut grid, &constraints).unwrap();
+        let steps = SolveStep::trace(&Grid::new(3, 3), &constraints, &grid).unwrap();
+        assert_eq!(steps[0].technique, Technique::Row(1));
+        assert!(steps.iter().all(|step| step.technique != Technique::Search));
+        assert_eq!(SolveStep::deductions_until(&steps, steps.len()), deductions);
+    }
+}
@@ -1,7 +1,10 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use crate::grid::{Grid, Constraints};
-use crate::solver::{Deduction, AdvancedSolver, AdvancedSolverConfig};
+use crate::solver::{Deduction, AdvancedSolver, AdvancedSolverConfig, SolveStep, Technique};
 use super::parallel_solver::ParallelSolver;
 use super::backtracking_optimized::{OptimizedBacktrackingSolver, OptimizedBacktrackingConfig};
+use super::branching::BranchingStrategy;
 
 /// Configuration pour le solveur ultime
 #[derive(Debug, Clone)]
@@ -9,6 +12,7 @@ pub struct UltimateSolverConfig {
     pub use_parallel: bool,
     pub use_backtracking: bool,
     pub backtracking_depth: usize,
+    pub branching: BranchingStrategy,
     pub verbose: bool,
 }
 
@@ -18,6 +22,7 @@ impl Default for UltimateSolverConfig {
             use_parallel: true,
             use_backtracking: true,
             backtracking_depth: 10,
+            branching: BranchingStrategy::Score,
             verbose: false,
         }
     }
@@ -26,6 +31,7 @@ impl Default for UltimateSolverConfig {
 /// Solveur ultime combinant toutes les techniques
 pub struct UltimateSolver {
     config: UltimateSolverConfig,
+    cancel: Option<Arc<AtomicBool>>,
 }
 
 impl UltimateSolver {
@@ -34,11 +40,23 @@ impl UltimateSolver {
     }
 
     pub fn with_config(config: UltimateSolverConfig) -> Self {
-        Self { config }
+        Self { config, cancel: None }
+    }
+
+    /// Drapeau d'annulation, transmis au solveur avancé et au backtracking
+    pub fn set_cancel_flag(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
     }
 
     /// Résout la grille avec toutes les techniques disponibles
     pub fn solve(&mut self, grid: &mut Grid, constraints: &Constraints) -> Result<Vec<Deduction>, String> {
+        let steps = self.solve_steps(grid, constraints)?;
+        Ok(steps.into_iter().flat_map(|step| step.deductions).collect())
+    }
+
+    /// Comme `solve`, avec les étapes du solveur avancé suivies d'une étape
+    /// pour le solveur parallèle et d'une pour le backtracking
+    pub fn solve_steps(&mut self, grid: &mut Grid, constraints: &Constraints) -> Result<Vec<SolveStep>, String> {
         if self.config.verbose {
             println!("🌟 Démarrage du solveur ultime");
             println!("   Configuration:");
@@ -46,7 +64,7 @@ impl UltimateSolver {
             println!("   - Backtracking: {}", if self.config.use_backtracking { "✓" } else { "✗" });
         }
 
-        let mut all_deductions = Vec::new();
+        let mut steps = Vec::new();
 
         // Phase 1: Solveur avancé
         if self.config.verbose {
@@ -61,19 +79,27 @@ impl UltimateSolver {
         };
 
         let mut advanced_solver = AdvancedSolver::with_config(advanced_config);
-        let advanced_deductions = advanced_solver.solve(grid, constraints)?;
+        if let Some(cancel) = &self.cancel {
+            advanced_solver.set_cancel_flag(Arc::clone(cancel));
+        }
+        let advanced_steps = advanced_solver.solve_steps(grid, constraints)?;
         
         if self.config.verbose {
-            println!("   ✓ {} déductions", advanced_deductions.len());
+            let count: usize = advanced_steps.iter().map(|step| step.deductions.len()).sum();
+            println!("   ✓ {} déductions", count);
         }
 
-        all_deductions.extend(advanced_deductions);
+        steps.extend(advanced_steps);
 
         if grid.count_empty_cells() == 0 {
             if self.config.verbose {
                 println!("\n✅ Grille complète après le solveur avancé");
             }
-            return Ok(all_deductions);
+            return Ok(steps);
+        }
+
+        if self.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            return Err("Résolution annulée".to_string());
         }
 
         // Phase 2: Parallélisation
@@ -89,14 +115,14 @@ impl UltimateSolver {
                 if self.config.verbose {
                     println!("   ✓ {} déductions supplémentaires", parallel_deductions.len());
                 }
-                all_deductions.extend(parallel_deductions);
+                steps.push(SolveStep { technique: Technique::Parallel, deductions: parallel_deductions });
             }
 
             if grid.count_empty_cells() == 0 {
                 if self.config.verbose {
                     println!("\n✅ Grille complète après parallélisation");
                 }
-                return Ok(all_deductions);
+                return Ok(steps);
             }
         }
 
@@ -113,17 +139,23 @@ impl UltimateSolver {
                 use_constraint_propagation: true,
                 use_naked_singles: true,
                 use_hidden_singles: true,
+                use_nogood_learning: true,
+                branching: self.config.branching,
+                seed: None,
                 verbose: self.config.verbose,
             };
 
             let mut backtracking_solver = OptimizedBacktrackingSolver::with_config(backtracking_config);
+            if let Some(cancel) = &self.cancel {
+                backtracking_solver.set_cancel_flag(Arc::clone(cancel));
+            }
             let backtracking_deductions = backtracking_solver.solve(grid, constraints)?;
 
             if !backtracking_deductions.is_empty() {
                 if self.config.verbose {
                     println!("   ✓ {} déductions supplémentaires", backtracking_deductions.len());
                 }
-                all_deductions.extend(backtracking_deductions);
+                steps.push(SolveStep { technique: Technique::Backtracking, deductions: backtracking_deductions });
             }
         }
 
@@ -135,7 +167,8 @@ impl UltimateSolver {
             let progress = (solved_cells as f64 / total_cells as f64 * 100.0) as usize;
 
             println!("\n✅ Résolution terminée");
-            println!("   Total déductions: {}", all_deductions.len());
+            let count: usize = steps.iter().map(|step| step.deductions.len()).sum();
+            println!("   Total déductions: {}", count);
             println!("   Progression: {}% ({}/{} cases)", progress, solved_cells, total_cells);
             
             if empty_cells > 0 {
@@ -145,7 +178,13 @@ impl UltimateSolver {
             }
         }
 
-        Ok(all_deductions)
+        Ok(steps)
+    }
+}
+
+impl Default for UltimateSolver {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -157,19 +196,9 @@ mod tests {
     #[test]
     fn test_ultimate_solver_simple() {
         let mut grid = Grid::new(5, 5);
-        let mut constraints = Constraints::new(5, 5);
-        
-        constraints.set_row_constraint(0, vec![2]);
-        constraints.set_row_constraint(1, vec![1, 1]);
-        constraints.set_row_constraint(2, vec![5]);
-        constraints.set_row_constraint(3, vec![1, 1]);
-        constraints.set_row_constraint(4, vec![2]);
-        
-        constraints.set_column_constraint(0, vec![2]);
-        constraints.set_column_constraint(1, vec![1, 1]);
-        constraints.set_column_constraint(2, vec![5]);
-        constraints.set_column_constraint(3, vec![1, 1]);
-        constraints.set_column_constraint(4, vec![2]);
+        let rows = vec![vec![2], vec![1, 1], vec![5], vec![1, 1], vec![2]];
+        let columns = vec![vec![2], vec![1, 1], vec![5], vec![1, 1], vec![2]];
+        let constraints = Constraints::new(5, 5, rows, columns).unwrap();
         
         let mut solver = UltimateSolver::new();
         let deductions = solver.solve(&mut grid, &constraints).unwrap();
@@ -0,0 +1,150 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::grid::{Grid, Constraints};
+use super::NonogramSolver;
+use super::branching::{BranchingHeuristic, BranchingStrategy};
+
+/// Résultat du comptage des solutions (arrêté à deux)
+#[derive(Debug, Clone)]
+pub enum SolutionCount {
+    /// Aucune solution compatible
+    None,
+    /// Une seule solution
+    Unique(Grid),
+    /// Au moins deux solutions distinctes
+    Multiple(Grid, Grid),
+    /// Limite de recherche atteinte (ou recherche annulée) avant de conclure
+    Unknown,
+}
+
+/// Recherche exhaustive des solutions: propagation par lignes puis branchement par case
+///
+/// Contrairement aux solveurs par backtracking, la recherche est complète: elle
+/// distingue une grille sans solution d'une recherche interrompue.
+pub struct SolutionSearch {
+    max_nodes: usize,
+    nodes: usize,
+    heuristic: BranchingHeuristic,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl SolutionSearch {
+    pub fn new() -> Self {
+        Self::with_max_nodes(20000)
+    }
+
+    pub fn with_max_nodes(max_nodes: usize) -> Self {
+        Self {
+            max_nodes,
+            nodes: 0,
+            heuristic: BranchingHeuristic::new(BranchingStrategy::FewestPlacements),
+            cancel: None,
+        }
+    }
+
+    /// Drapeau d'annulation: une fois levé, la recherche s'arrête comme à la limite de nœuds
+    pub fn set_cancel_flag(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
+    }
+
+    /// Nœuds explorés lors du dernier comptage
+    pub fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    /// Compte les solutions compatibles avec les cases déjà connues de la grille
+    pub fn count(&mut self, grid: &Grid, constraints: &Constraints) -> Result<SolutionCount, String> {
+        if grid.width() != constraints.width || grid.height() != constraints.height {
+            return Err(format!(
+                "Dimensions incompatibles: grille {}x{}, contraintes {}x{}",
+                grid.width(), grid.height(), constraints.width, constraints.height
+            ));
+        }
+
+        self.nodes = 0;
+        let mut solutions = Vec::new();
+        let complete = self.search(grid.clone(), constraints, &mut solutions)?;
+
+        let mut solutions = solutions.into_iter();
+        Ok(match (solutions.next(), solutions.next()) {
+            (Some(first), Some(second)) => SolutionCount::Multiple(first, second),
+            (Some(first), None) if complete => SolutionCount::Unique(first),
+            (None, _) if complete => SolutionCount::None,
+            _ => SolutionCount::Unknown,
+        })
+    }
+
+    /// Retourne faux si la recherche a été interrompue (limite de nœuds ou annulation)
+    fn search(&mut self, mut grid: Grid, constraints: &Constraints, solutions: &mut Vec<Grid>) -> Result<bool, String> {
+        if self.nodes >= self.max_nodes || self.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            return Ok(false);
+        }
+        self.nodes += 1;
+
+        if NonogramSolver::new().solve(&mut grid, constraints).is_err() {
+            return Ok(true);
+        }
+
+        if grid.count_empty_cells() == 0 {
+            if constraints.is_satisfied_by(&grid) {
+                solutions.push(grid);
+            }
+            return Ok(true);
+        }
+
+        let branch = match self.heuristic.choose(&grid, constraints) {
+            Some(branch) => branch,
+            // Une ligne n'a plus aucun placement
+            None => return Ok(true),
+        };
+
+        for alternative in branch.alternatives {
+            let mut child = grid.clone();
+            for (row, col, state) in alternative {
+                child.set(row, col, state)?;
+            }
+            if !self.search(child, constraints, solutions)? {
+                return Ok(false);
+            }
+            if solutions.len() >= 2 {
+                break;
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+impl Default for SolutionSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::CellState;
+
+    #[test]
+    fn test_count_distinguishes_unique_and_multiple() {
+        let constraints = Constraints::new(3, 3, vec![vec![1], vec![3], vec![1]], vec![vec![1], vec![3], vec![1]]).unwrap();
+        let count = SolutionSearch::new().count(&Grid::new(3, 3), &constraints).unwrap();
+        assert!(matches!(count, SolutionCount::Unique(_)));
+
+        let constraints = Constraints::new(2, 2, vec![vec![1], vec![1]], vec![vec![1], vec![1]]).unwrap();
+        let count = SolutionSearch::new().count(&Grid::new(2, 2), &constraints).unwrap();
+        assert!(matches!(count, SolutionCount::Multiple(_, _)));
+
+        let mut grid = Grid::new(2, 2);
+        grid.set(0, 0, CellState::Filled).unwrap();
+        grid.set(1, 0, CellState::Filled).unwrap();
+        let count = SolutionSearch::new().count(&grid, &constraints).unwrap();
+        assert!(matches!(count, SolutionCount::None));
+
+        // Recherche annulée: rien n'est conclu
+        let mut search = SolutionSearch::new();
+        search.set_cancel_flag(Arc::new(AtomicBool::new(true)));
+        assert!(matches!(search.count(&Grid::new(2, 2), &constraints).unwrap(), SolutionCount::Unknown));
+    }
+}